@@ -0,0 +1,310 @@
+//! One-shot subcommands (`lyricsmpris cache dedupe`, ...).
+//!
+//! Subcommands run instead of the lyrics viewer and exit when done. They
+//! share the global options (`--database`, `--config`, ...) with the viewer.
+
+use clap::Subcommand;
+use std::error::Error;
+
+/// Top-level subcommands.
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Maintain the local lyrics cache (requires --database)
+    #[command(subcommand)]
+    Cache(CacheCommand),
+    /// Show per-provider lookup statistics recorded in the cache (requires --database)
+    Stats,
+    /// Fetch the current (or given) track from every configured provider and compare them side by side
+    Compare {
+        /// Artist to look up instead of the playing track
+        #[arg(long, requires = "title")]
+        artist: Option<String>,
+        /// Title to look up instead of the playing track
+        #[arg(long, requires = "artist")]
+        title: Option<String>,
+        /// Album of the given track
+        #[arg(long, requires = "title", default_value = "")]
+        album: String,
+        /// Duration of the given track in seconds
+        #[arg(long, value_name = "SECONDS", requires = "title")]
+        duration: Option<f64>,
+    },
+    /// Fill the cache with lyrics for every track of a playlist, e.g. before going offline (requires --database)
+    Prefetch {
+        /// M3U playlist to read (default: the active player's MPRIS track list)
+        playlist: Option<std::path::PathBuf>,
+        /// Pause between tracks that needed a provider lookup, to go easy on their APIs
+        #[arg(long, value_name = "MS", default_value_t = 1500)]
+        delay: u64,
+    },
+    /// Serve the cache over HTTP with an LRCLIB-compatible `GET /api/get` (requires --database)
+    Serve {
+        /// Address to listen on (use 0.0.0.0:PORT to allow other machines)
+        #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:8910")]
+        listen: String,
+    },
+}
+
+/// `cache` subcommands.
+#[derive(Subcommand, Debug, Clone)]
+pub enum CacheCommand {
+    /// Merge duplicate entries, keeping word-level lyrics over line-level ones
+    Dedupe,
+    /// Import an old JSON lyrics database (`db.json`) as LRC, keeping tracks already cached
+    ImportLegacy {
+        /// Legacy database to read (default: ~/.config/lyricsmpris/db.json)
+        path: Option<std::path::PathBuf>,
+    },
+}
+
+/// Runs a subcommand to completion.
+pub async fn run(command: &Command, config: &crate::Config) -> Result<(), Box<dyn Error + Send + Sync>> {
+    match command {
+        Command::Cache(cache) => run_cache(cache, config).await,
+        Command::Stats => run_stats(config).await,
+        Command::Compare { artist, title, album, duration } => {
+            let track = artist.as_ref().zip(title.as_ref()).map(|(artist, title)| crate::mpris::TrackMetadata {
+                artist: artist.clone(),
+                title: title.clone(),
+                album: album.clone(),
+                length: *duration,
+                ..Default::default()
+            });
+            run_compare(track, config).await
+        }
+        Command::Prefetch { playlist, delay } => run_prefetch(playlist.as_deref(), *delay, config).await,
+        Command::Serve { listen } => {
+            if config.database.is_none() {
+                return Err("serve requires --database PATH".into());
+            }
+            let tolerance = if config.ignore_duration { f64::INFINITY } else { config.duration_tolerance / 100.0 };
+            crate::server::serve(listen, tolerance).await?;
+            Ok(())
+        }
+    }
+}
+
+async fn run_stats(config: &crate::Config) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if config.database.is_none() {
+        return Err("stats requires --database PATH".into());
+    }
+
+    let stats = crate::lyrics::database::fetch_stats()
+        .await
+        .ok_or("failed to open the database")??;
+    if stats.is_empty() {
+        println!("No lookups recorded yet");
+        return Ok(());
+    }
+
+    println!("{:<24} {:>8} {:>8} {:>8} {:>8} {:>12}", "provider", "lookups", "found", "missed", "failed", "avg latency");
+    for s in &stats {
+        let rate = 100.0 * s.found as f64 / s.fetches.max(1) as f64;
+        println!(
+            "{:<24} {:>8} {:>3} {:>3.0}% {:>8} {:>8} {:>9.0} ms",
+            s.provider, s.fetches, s.found, rate, s.missed, s.failed, s.avg_latency_ms
+        );
+    }
+    Ok(())
+}
+
+async fn run_compare(
+    track: Option<crate::mpris::TrackMetadata>,
+    config: &crate::Config,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    use crate::lyrics::compare::{self, Source};
+
+    if track.is_none() {
+        crate::exit::require_dbus().await?;
+    }
+    let (query, providers) = crate::pool::lookup_plan(config.clone(), track)
+        .await
+        .ok_or_else(|| crate::exit::Failure::NoPlayer("no track is playing; pass --artist and --title".into()))?;
+    println!("{} - {}", query.artist, query.title);
+    println!();
+    let loop_config = crate::pool::LoopConfig::new(config.clone());
+    // Providers get what a live lookup would send them
+    let provider_query = if loop_config.musicbrainz() {
+        crate::lyrics::musicbrainz::normalize(&query).await
+    } else {
+        query.clone()
+    };
+    if provider_query.artist != query.artist || provider_query.title != query.title {
+        println!("providers get {} - {} (MusicBrainz match)", provider_query.artist, provider_query.title);
+        println!();
+    }
+
+    let mut sources = Vec::new();
+    for provider in providers {
+        match crate::event::lookup(&provider, &query, &provider_query, &loop_config).await {
+            Ok(Some((lines, kind))) => {
+                let sync = if kind.is_word_synced() { "word-synced" } else { "line-synced" };
+                println!("{:<24} {} lines, {} ({})", provider, lines.len(), sync, kind.name());
+                sources.push(Source { name: provider, lines });
+            }
+            Ok(None) => println!("{:<24} not found", provider),
+            Err(e) => println!("{:<24} error: {}", provider, e),
+        }
+    }
+
+    let width = crossterm::terminal::size().map_or(120, |(cols, _)| cols as usize);
+    let table = compare::render(&sources, width);
+    if !table.is_empty() {
+        println!();
+        print!("{}", table);
+    }
+    Ok(())
+}
+
+/// Handles `--explain`: prints how lyrics for the playing track would be
+/// looked up, without sending anything.
+pub async fn run_explain(config: &crate::Config) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (query, _) = crate::pool::lookup_plan(config.clone(), None)
+        .await
+        .ok_or_else(|| crate::exit::Failure::NoPlayer("no track is playing".into()))?;
+    let loop_config = crate::pool::LoopConfig::new(config.clone());
+    let plan = crate::event::explain(&query, &loop_config).await;
+
+    println!("{} - {}", query.artist, query.title);
+    println!("album:       {}", if query.album.is_empty() { "-" } else { &query.album });
+    println!("length:      {}", query.length.map_or("-".to_string(), |l| format!("{:.0}s", l)));
+    println!("spotify id:  {}", query.spotify_id.as_deref().unwrap_or("-"));
+    println!();
+
+    let (artist, title, album) = crate::lyrics::database::cache_key(&query.artist, &query.title, &query.album);
+    println!("cache key:   artist={:?} title={:?} album={:?}", artist, title, album);
+    match (&config.database, plan.cached) {
+        (None, _) => println!("cache:       disabled (no --database)"),
+        (Some(_), Some((lines, provider))) => {
+            let provider = provider.map_or("unknown", crate::state::Provider::name);
+            println!("cache:       hit, {} lines from {} (providers are only asked on a miss)", lines, provider);
+        }
+        (Some(_), None) => println!("cache:       miss"),
+    }
+    let file_name = crate::lyrics::providers::local::local_file_name(&query.artist, &query.title);
+    for dir in crate::lyrics::providers::local::lyrics_dirs() {
+        let path = dir.join(&file_name);
+        let found = if path.is_file() { " (found, used before anything else)" } else { "" };
+        println!("local file:  {}{}", path.display(), found);
+    }
+    if let Some(pinned) = &plan.pinned {
+        println!("pinned:      {}", pinned);
+    }
+    if !plan.rejected.is_empty() {
+        println!("rejected:    {}", plan.rejected.join(", "));
+    }
+    println!();
+
+    if plan.musicbrainz_pending {
+        println!("MusicBrainz search first (--musicbrainz); providers may get its canonical names instead");
+    } else if plan.provider_query.artist != query.artist || plan.provider_query.title != query.title {
+        println!("providers get {} - {} (cached MusicBrainz match)", plan.provider_query.artist, plan.provider_query.title);
+    }
+    println!("providers, asked in order until one has lyrics:");
+    for (provider, requests) in &plan.requests {
+        println!("  {}", provider);
+        for request in requests {
+            println!("    {}", request);
+        }
+    }
+    Ok(())
+}
+
+async fn run_prefetch(
+    playlist: Option<&std::path::Path>,
+    delay: u64,
+    config: &crate::Config,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    use crate::event::Warmed;
+
+    if config.database.is_none() {
+        return Err("prefetch requires --database PATH".into());
+    }
+
+    let loop_config = crate::pool::LoopConfig::new(config.clone());
+    let tracks = match playlist {
+        Some(path) => {
+            let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            crate::playlist::parse_m3u(&text)
+        }
+        None => {
+            crate::exit::require_dbus().await?;
+            let service = crate::pool::discover_active_player(&loop_config)
+                .await
+                .ok_or_else(|| crate::exit::Failure::NoPlayer("no active player; pass an M3U playlist".into()))?;
+            crate::mpris::metadata::get_track_list(&service)
+                .await
+                .map_err(|e| format!("{} has no track list ({}); pass an M3U playlist", service, e))?
+        }
+    };
+    if tracks.is_empty() {
+        return Err("no tracks found in the playlist".into());
+    }
+
+    let (mut fetched, mut cached, mut missing, mut failed) = (0, 0, 0, 0);
+    for (i, track) in tracks.iter().enumerate() {
+        let outcome = crate::event::warm_cache(track, &loop_config).await;
+        let status = match &outcome {
+            Warmed::Ignored => "ignored".to_string(),
+            Warmed::Local => "local file".to_string(),
+            Warmed::Cached => "cached".to_string(),
+            Warmed::Fetched(provider) => format!("fetched ({})", provider.name()),
+            Warmed::NotFound => "not found".to_string(),
+            Warmed::Unreachable => "not found (some providers unreachable)".to_string(),
+            Warmed::Failed(e) => format!("error: {}", e),
+        };
+        match outcome {
+            Warmed::Fetched(_) => fetched += 1,
+            Warmed::Cached | Warmed::Local => cached += 1,
+            Warmed::NotFound => missing += 1,
+            Warmed::Unreachable | Warmed::Failed(_) => failed += 1,
+            Warmed::Ignored => {}
+        }
+        println!("[{}/{}] {} - {}: {}", i + 1, tracks.len(), track.artist, track.title, status);
+
+        if outcome.queried_providers() && i + 1 < tracks.len() {
+            tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+        }
+    }
+
+    println!();
+    println!("{} fetched, {} already available, {} without lyrics, {} failed (run again to retry)", fetched, cached, missing, failed);
+    Ok(())
+}
+
+async fn run_cache(command: &CacheCommand, config: &crate::Config) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if config.database.is_none() {
+        return Err("cache commands require --database PATH".into());
+    }
+
+    match command {
+        CacheCommand::Dedupe => {
+            let stats = crate::lyrics::database::dedupe()
+                .await
+                .ok_or("failed to open the database")??;
+
+            println!(
+                "{} duplicate tracks, {} rows removed ({} upgraded to word-level), {} rows renormalized",
+                stats.duplicate_groups, stats.removed, stats.upgraded, stats.renormalized
+            );
+            Ok(())
+        }
+        CacheCommand::ImportLegacy { path } => {
+            let path = path
+                .clone()
+                .or_else(|| crate::config::default_config_path().map(|p| p.with_file_name("db.json")))
+                .ok_or("no path given and no config directory found")?;
+            let json = std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            let legacy = crate::lyrics::database::parse_legacy(&json)?;
+            let stats = crate::lyrics::database::import_legacy(&legacy)
+                .await
+                .ok_or("failed to open the database")??;
+
+            println!(
+                "{} entries imported, {} already cached, {} unreadable",
+                stats.imported, stats.existing, stats.invalid
+            );
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,634 @@
+//! Configuration file support.
+//!
+//! Settings that don't fit on the command line are read from a TOML file.
+//! By default the file lives at `$XDG_CONFIG_HOME/lyricsmpris/config.toml`
+//! (falling back to `~/.config/lyricsmpris/config.toml`); `--config PATH`
+//! selects a different file.
+//!
+//! # Example
+//!
+//! ```toml
+//! # Per-player overrides, keyed by a (case-insensitive) substring of the
+//! # MPRIS bus name, e.g. "org.mpris.MediaPlayer2.spotify".
+//! [players.spotify]
+//! offset = 0.25                        # seconds added to reported positions
+//! ignore_track_change_position = true  # position is stale right after a skip
+//!                                      # (built in for Spotify; set false to disable)
+//!
+//! [players.chromium]
+//! blocked = true
+//!
+//! # Lyrics providers (environment variables, if set, take precedence)
+//! [providers]
+//! order = ["lrclib", "musixmatch"]     # when neither --providers nor LYRIC_PROVIDERS is set
+//! contact = "me@example.com"           # appended to the User-Agent sent to LRCLIB
+//!
+//! [providers.musixmatch]
+//! token = "…"                          # MUSIXMATCH_USERTOKEN
+//!
+//! [providers.lrclib]
+//! url = "http://192.168.1.10:8910"     # LRCLIB_URL
+//! enabled = false                      # skip even if listed in --providers
+//!
+//! [providers.languages]                # order for tracks detected as a language
+//! zh = ["amll", "lrclib"]
+//!
+//! # TUI key bindings: a preset (default | vim | emacs) plus per-action keys
+//! [keys]
+//! preset = "vim"
+//! quit = ["q", "ctrl-c"]
+//!
+//! # Regex substitutions applied to metadata before lookups
+//! [[rewrite]]
+//! field = "artist"                     # artist | title | album | all
+//! pattern = "^NCS Release$"
+//! replace = "NoCopyrightSounds"
+//!
+//! # Lines that flash the TUI when they become active (or ring the bell)
+//! [cues]
+//! patterns = ["(?i)^chorus", "🎤"]
+//! action = "flash"                     # flash | bell | both
+//!
+//! # Tracks never looked up (regex, matched against the player's metadata)
+//! [[ignore]]
+//! field = "title"
+//! pattern = "(?i)white noise"
+//!
+//! # Provider asked first for an artist, album or track
+//! [[pin]]
+//! artist = "Daft Punk"
+//! album = "Discovery"                  # optional; add `title` for one track
+//! provider = "musixmatch"
+//! ```
+//!
+//! A missing default file is not an error: every setting has a default.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Settings loaded from the configuration file.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct FileConfig {
+    /// Per-player overrides keyed by bus-name substring.
+    pub players: HashMap<String, PlayerOverride>,
+    /// Metadata rewrite rules, applied in order.
+    pub rewrite: Vec<RewriteRule>,
+    /// Patterns for tracks that never trigger a lyrics fetch.
+    pub ignore: Vec<IgnoreRule>,
+    /// Providers pinned for an artist, album or track.
+    pub pin: Vec<PinRule>,
+    /// TUI key bindings.
+    pub keys: KeyConfig,
+    /// TUI colors.
+    pub colors: ColorsConfig,
+    /// Lines that flash the TUI or ring the bell.
+    pub cues: CuesConfig,
+    /// Provider order, credentials and options.
+    pub providers: ProvidersConfig,
+}
+
+/// The `[providers]` section.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ProvidersConfig {
+    /// Provider order used when neither `--providers` nor `LYRIC_PROVIDERS` is set.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub order: Vec<String>,
+    pub lrclib: ProviderSettings,
+    pub musixmatch: ProviderSettings,
+    pub amll: ProviderSettings,
+    /// Provider order for tracks whose title/artist is detected as a
+    /// language (see [`crate::text_utils::detect_language`]), e.g. `zh = ["amll", "lrclib"]`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub languages: BTreeMap<String, Vec<String>>,
+    /// `User-Agent` of provider requests instead of [`DEFAULT_USER_AGENT`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// Email or URL appended to the user agent, so LRCLIB can reach heavy users.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<String>,
+}
+
+/// `User-Agent` of provider requests unless `[providers] user_agent` is set.
+pub const DEFAULT_USER_AGENT: &str = concat!("LyricsMPRIS/1.0 (lyricsmpris ", env!("CARGO_PKG_VERSION"), ")");
+
+/// Contact sent to MusicBrainz unless `[providers] contact` is set; it asks
+/// every client to identify itself with one.
+pub const DEFAULT_CONTACT: &str = "https://github.com/BEST8OY/LyricsMPRIS-Rust";
+
+/// Settings for one provider. Unset fields fall back to the environment
+/// variables and built-in defaults.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ProviderSettings {
+    /// `false` skips the provider even if it is in the provider list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// API token (Musixmatch user token).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Base URL of the service (LRCLIB instance, AMLL DB mirror).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl ProvidersConfig {
+    /// Settings for the built-in provider `name`, if it has any.
+    pub fn settings(&self, name: &str) -> Option<&ProviderSettings> {
+        use crate::event::ProviderName;
+        match ProviderName::parse(name)? {
+            ProviderName::Lrclib => Some(&self.lrclib),
+            ProviderName::Musixmatch => Some(&self.musixmatch),
+            ProviderName::Amll => Some(&self.amll),
+            ProviderName::Exec(_) => None,
+        }
+    }
+
+    /// Mutable settings for the built-in provider `name`.
+    pub fn settings_mut(&mut self, name: &str) -> Option<&mut ProviderSettings> {
+        use crate::event::ProviderName;
+        match ProviderName::parse(name)? {
+            ProviderName::Lrclib => Some(&mut self.lrclib),
+            ProviderName::Musixmatch => Some(&mut self.musixmatch),
+            ProviderName::Amll => Some(&mut self.amll),
+            ProviderName::Exec(_) => None,
+        }
+    }
+
+    /// `User-Agent` of provider requests: `user_agent` (or the default)
+    /// followed by the contact, if any.
+    pub fn user_agent(&self) -> String {
+        self.user_agent_with_contact(None)
+    }
+
+    /// `User-Agent` of MusicBrainz requests: like [`Self::user_agent`], but
+    /// always with a contact ([`DEFAULT_CONTACT`] if none is set).
+    pub fn musicbrainz_user_agent(&self) -> String {
+        self.user_agent_with_contact(Some(DEFAULT_CONTACT))
+    }
+
+    fn user_agent_with_contact(&self, fallback: Option<&str>) -> String {
+        let agent = self.user_agent.as_deref().filter(|a| !a.is_empty()).unwrap_or(DEFAULT_USER_AGENT);
+        match self.contact.as_deref().filter(|c| !c.is_empty()).or(fallback) {
+            Some(contact) => format!("{} ({})", agent, contact),
+            None => agent.to_string(),
+        }
+    }
+
+    /// Whether `name` may be queried (providers are enabled unless disabled explicitly).
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.settings(name).and_then(|s| s.enabled).unwrap_or(true)
+    }
+}
+
+/// The `[keys]` section: a preset plus per-action overrides.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct KeyConfig {
+    /// Built-in bindings to start from.
+    pub preset: KeyPreset,
+    /// Keys replacing the preset's for an action, e.g. `quit = ["q", "ctrl-c"]`.
+    #[serde(flatten)]
+    pub bindings: HashMap<crate::ui::keymap::Action, KeySpecs>,
+}
+
+/// The `[colors]` section.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ColorsConfig {
+    /// Colors of the vocal parts in duets, in order of appearance (names like
+    /// `"cyan"` or `"#ff8800"`). Unset uses the theme's palette; `[]` turns
+    /// part coloring off.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parts: Option<Vec<String>>,
+}
+
+/// The `[cues]` section: lines (chorus, sing-along moments, automation
+/// cues) that flash the TUI or ring the terminal bell when they become
+/// active. LRC files can also mark a line with a `# cue` comment before it.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CuesConfig {
+    /// Regular expressions; a line whose text matches any of them is a cue.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<String>,
+    /// What a cue does when its line becomes active.
+    pub action: CueAction,
+}
+
+/// What an active cue line does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CueAction {
+    /// Briefly invert the screen
+    #[default]
+    Flash,
+    /// Ring the terminal bell
+    Bell,
+    /// Flash and ring the bell
+    Both,
+}
+
+/// Built-in key binding presets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyPreset {
+    #[default]
+    Default,
+    Vim,
+    Emacs,
+}
+
+/// One key spec or a list of them.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum KeySpecs {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeySpecs {
+    /// Iterates over the key specs.
+    pub fn iter(&self) -> impl Iterator<Item = &String> {
+        match self {
+            KeySpecs::One(spec) => std::slice::from_ref(spec).iter(),
+            KeySpecs::Many(specs) => specs.iter(),
+        }
+    }
+}
+
+/// Overrides applied while a matching player is the active one.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PlayerOverride {
+    /// Seconds added to every position reported by this player.
+    /// Positive values make lyrics appear earlier.
+    pub offset: f64,
+    /// Never monitor this player (same effect as listing it in `--block`).
+    pub blocked: bool,
+    /// Drop `Seeked` notifications from this player.
+    pub ignore_seeked: bool,
+    /// Don't trust the position reported right after a track change;
+    /// estimate from zero instead. Unset keeps the built-in quirk profile
+    /// (see [`crate::mpris::quirks`]).
+    pub ignore_track_change_position: Option<bool>,
+    /// Drop `Seeked` notifications that match regular playback progress.
+    /// Unset keeps the built-in quirk profile.
+    pub ignore_redundant_seeked: Option<bool>,
+}
+
+/// A regex substitution applied to track metadata before lookups.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RewriteRule {
+    /// Which field the rule applies to.
+    #[serde(default)]
+    pub field: RewriteField,
+    /// Regular expression to search for.
+    pub pattern: String,
+    /// Replacement text (`$1` etc. refer to capture groups).
+    #[serde(default)]
+    pub replace: String,
+}
+
+/// A regex marking tracks whose lyrics are never fetched.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct IgnoreRule {
+    /// Which field is matched.
+    #[serde(default)]
+    pub field: RewriteField,
+    /// Regular expression; a match anywhere in the field ignores the track.
+    pub pattern: String,
+}
+
+/// A provider asked first (and whose cached lyrics are the only ones
+/// used) for the tracks of an artist, optionally narrowed to an album or title.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PinRule {
+    pub artist: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub album: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Provider name as in `--providers`.
+    pub provider: String,
+}
+
+impl PinRule {
+    /// How specifically the rule matches a track (title over album over
+    /// artist), or `None` if it doesn't. Names compare case-insensitively.
+    fn specificity(&self, artist: &str, title: &str, album: &str) -> Option<u8> {
+        let same = |a: &str, b: &str| a.trim().to_lowercase() == b.trim().to_lowercase();
+        if !same(&self.artist, artist) {
+            return None;
+        }
+        let album_matches = self.album.as_deref().is_none_or(|a| same(a, album));
+        let title_matches = self.title.as_deref().is_none_or(|t| same(t, title));
+        (album_matches && title_matches)
+            .then(|| u8::from(self.album.is_some()) + 2 * u8::from(self.title.is_some()))
+    }
+}
+
+/// Metadata field targeted by a [`RewriteRule`] or [`IgnoreRule`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RewriteField {
+    Artist,
+    Title,
+    Album,
+    /// Artist, title and album
+    #[default]
+    All,
+}
+
+impl FileConfig {
+    /// Loads the configuration file.
+    ///
+    /// With `path == None` the default location is used, and a missing file
+    /// silently yields defaults. Read or parse errors are logged and also
+    /// fall back to defaults so a typo never prevents startup.
+    pub fn load(path: Option<&Path>) -> Self {
+        let explicit = path.is_some();
+        let Some(path) = path.map(Path::to_path_buf).or_else(default_config_path) else {
+            return Self::default();
+        };
+
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) => {
+                if explicit || e.kind() != std::io::ErrorKind::NotFound {
+                    tracing::warn!(path = %path.display(), error = %e, "Failed to read config file");
+                }
+                return Self::default();
+            }
+        };
+
+        match toml::from_str(&text) {
+            Ok(config) => {
+                tracing::debug!(path = %path.display(), "Loaded config file");
+                config
+            }
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Invalid config file, using defaults");
+                Self::default()
+            }
+        }
+    }
+
+    /// Returns the override section matching `service`, if any.
+    ///
+    /// Keys are matched case-insensitively as substrings of the bus name,
+    /// like `--block`. When several keys match, the longest one wins.
+    pub fn player_override(&self, service: &str) -> Option<&PlayerOverride> {
+        let service_lower = service.to_lowercase();
+        self.players
+            .iter()
+            .filter(|(key, _)| service_lower.contains(&key.to_lowercase()))
+            .max_by_key(|(key, _)| key.len())
+            .map(|(_, player)| player)
+    }
+
+    /// Returns the provider pinned by the most specific `[[pin]]` rule
+    /// matching a track, if any.
+    pub fn pinned_provider(&self, artist: &str, title: &str, album: &str) -> Option<&str> {
+        self.pin
+            .iter()
+            .filter_map(|rule| Some((rule.specificity(artist, title, album)?, rule)))
+            .max_by_key(|(specificity, _)| *specificity)
+            .map(|(_, rule)| rule.provider.as_str())
+    }
+
+    /// Returns the keys of all player sections marked `blocked = true`.
+    pub fn blocked_players(&self) -> impl Iterator<Item = &String> {
+        self.players
+            .iter()
+            .filter(|(_, player)| player.blocked)
+            .map(|(key, _)| key)
+    }
+}
+
+/// Commented starter file written by `--init-config`.
+///
+/// Everything is commented out, so the file behaves exactly like the defaults
+/// until the user edits it.
+pub const STARTER: &str = r#"# lyricsmpris configuration
+#
+# Every setting is optional; uncomment and edit what you need.
+# `lyricsmpris --print-config` shows the settings currently in effect.
+
+# Per-player overrides, keyed by a (case-insensitive) substring of the
+# MPRIS bus name, e.g. "org.mpris.MediaPlayer2.spotify".
+#
+# [players.spotify]
+# offset = 0.25                        # seconds added to reported positions
+# ignore_seeked = false                # drop Seeked signals from this player
+# ignore_track_change_position = true  # position is stale right after a skip
+# ignore_redundant_seeked = false      # drop Seeked signals matching normal playback
+#
+# [players.chromium]
+# blocked = true                       # never monitor this player
+
+# Lyrics providers. Environment variables (MUSIXMATCH_USERTOKEN, LRCLIB_URL,
+# AMLL_DB_URL, LYRIC_PROVIDERS) take precedence when set.
+#
+# [providers]
+# order = ["lrclib", "musixmatch"]     # used when --providers isn't given
+# user_agent = "LyricsMPRIS/1.0"       # default: LyricsMPRIS/1.0 (lyricsmpris VERSION)
+# contact = "me@example.com"           # appended to the user agent; LRCLIB asks heavy users for one
+#                                      # (MusicBrainz gets the project URL without it)
+#
+# [providers.musixmatch]
+# token = "your-token-here"
+#
+# [providers.lrclib]
+# url = "https://lrclib.net"           # e.g. a host running `lyricsmpris serve`
+#
+# [providers.amll]
+# enabled = false                      # skip even if listed in --providers
+#
+# Order for tracks whose title/artist is in a detected language
+# (ja, ko, zh, ru, el, ar, he, th, hi); other tracks use the order above.
+# [providers.languages]
+# ja = ["musixmatch", "lrclib"]
+
+# TUI key bindings: a preset (default | vim | emacs) plus per-action keys.
+# Actions: quit, toggle-karaoke, cycle-translation, scroll-up, scroll-down,
+# page-up, page-down, scroll-top, scroll-bottom, follow, wrong-match,
+# pin-provider, teleprompter-faster, teleprompter-slower, help
+#
+# [keys]
+# preset = "default"
+# quit = ["q", "ctrl-c"]
+
+# Colors of the vocal parts when lyrics mark singers (duets in AMLL TTML),
+# in order of appearance; [] disables part coloring.
+#
+# [colors]
+# parts = ["lightcyan", "lightmagenta", "lightyellow"]   # color names or hex RGB
+
+# Regex substitutions applied to metadata before lookups, in order.
+#
+# [[rewrite]]
+# field = "artist"                     # artist | title | album | all
+# pattern = "^NCS Release$"
+# replace = "NoCopyrightSounds"
+
+# Lines that flash the TUI or ring the terminal bell when they become
+# active (regex, matched against the line's text). LRC files can also mark
+# the next line with a `# cue` comment.
+#
+# [cues]
+# patterns = ["(?i)^chorus"]
+# action = "flash"                     # flash | bell | both
+
+# Tracks that never trigger a lyrics fetch (matched against the player's
+# metadata, before rewrites).
+#
+# [[ignore]]
+# field = "title"                      # artist | title | album | all
+# pattern = "(?i)white noise|rain sounds"
+
+# Provider asked first for an artist, optionally narrowed to an album or
+# a title (the `P` key in the TUI pins the current provider for a track).
+#
+# [[pin]]
+# artist = "Daft Punk"
+# album = "Discovery"
+# provider = "musixmatch"
+"#;
+
+/// Writes [`STARTER`] to `path`, creating parent directories.
+///
+/// Fails with `AlreadyExists` rather than overwriting an existing file.
+pub fn write_starter(path: &Path) -> std::io::Result<()> {
+    use std::io::Write;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = std::fs::OpenOptions::new().write(true).create_new(true).open(path)?;
+    file.write_all(STARTER.as_bytes())
+}
+
+/// Default configuration file location.
+pub fn default_config_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("lyricsmpris").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_player_override_matching() {
+        let config: FileConfig = toml::from_str(
+            r#"
+            [players.spotify]
+            offset = 0.5
+
+            [players.chromium]
+            blocked = true
+            "#,
+        )
+        .unwrap();
+
+        let spotify = config.player_override("org.mpris.MediaPlayer2.Spotify").unwrap();
+        assert_eq!(spotify.offset, 0.5);
+        assert!(!spotify.blocked);
+        assert!(config.player_override("org.mpris.MediaPlayer2.vlc").is_none());
+        assert_eq!(config.blocked_players().collect::<Vec<_>>(), vec!["chromium"]);
+    }
+
+    #[test]
+    fn test_providers_section() {
+        let config: FileConfig = toml::from_str(
+            r#"
+            [providers]
+            order = ["musixmatch", "lrclib"]
+
+            [providers.musixmatch]
+            token = "secret"
+
+            [providers.amll]
+            enabled = false
+
+            [providers.languages]
+            zh = ["amll", "lrclib"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.providers.order, ["musixmatch", "lrclib"]);
+        assert_eq!(config.providers.musixmatch.token.as_deref(), Some("secret"));
+        assert!(config.providers.is_enabled("lrclib"));
+        assert!(!config.providers.is_enabled("amll"));
+        assert!(config.providers.is_enabled("exec:/bin/script"));
+        assert_eq!(config.providers.languages["zh"], ["amll", "lrclib"]);
+        assert_eq!(config.providers.user_agent(), DEFAULT_USER_AGENT);
+        assert_eq!(
+            config.providers.musicbrainz_user_agent(),
+            format!("{} ({})", DEFAULT_USER_AGENT, DEFAULT_CONTACT)
+        );
+
+        let identified = ProvidersConfig {
+            user_agent: Some("my-scrobbler/2.1".into()),
+            contact: Some("me@example.com".into()),
+            ..Default::default()
+        };
+        assert_eq!(identified.user_agent(), "my-scrobbler/2.1 (me@example.com)");
+        assert_eq!(identified.musicbrainz_user_agent(), identified.user_agent());
+    }
+
+    #[test]
+    fn test_starter_parses_and_uncommented_examples_are_valid() {
+        let config: FileConfig = toml::from_str(STARTER).unwrap();
+        assert!(config.players.is_empty() && config.rewrite.is_empty() && config.ignore.is_empty());
+
+        let uncommented: String = STARTER
+            .lines()
+            .filter_map(|line| line.strip_prefix("# "))
+            .filter(|line| line.starts_with('[') || line.contains(" = "))
+            .map(|line| format!("{line}\n"))
+            .collect();
+        let config: FileConfig = toml::from_str(&uncommented).unwrap();
+        assert_eq!(config.player_override("spotify").unwrap().offset, 0.25);
+        assert_eq!(config.keys.preset, KeyPreset::Default);
+        assert_eq!(config.colors.parts.as_ref().map(Vec::len), Some(3));
+        assert_eq!(config.rewrite.len(), 1);
+        assert_eq!(config.ignore.len(), 1);
+        assert_eq!(config.pin.len(), 1);
+        assert_eq!(config.cues.patterns.len(), 1);
+    }
+
+    #[test]
+    fn test_pinned_provider() {
+        let config: FileConfig = toml::from_str(
+            r#"
+            [[pin]]
+            artist = "Daft Punk"
+            provider = "lrclib"
+
+            [[pin]]
+            artist = "daft punk"
+            album = "Discovery"
+            provider = "musixmatch"
+
+            [[pin]]
+            artist = "Daft Punk"
+            title = "Veridis Quo"
+            provider = "amll"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.pinned_provider("Daft Punk", "Around the World", "Homework"), Some("lrclib"));
+        assert_eq!(config.pinned_provider("DAFT PUNK", "One More Time", "Discovery"), Some("musixmatch"));
+        assert_eq!(config.pinned_provider("Daft Punk", "Veridis Quo", "Discovery"), Some("amll"));
+        assert_eq!(config.pinned_provider("Justice", "D.A.N.C.E.", "†"), None);
+    }
+}
@@ -5,9 +5,9 @@
 //!
 //! # Architecture
 //!
-//! - [`Event`]: Top-level event types (MPRIS, Shutdown)
+//! - [`Event`]: Top-level event types (MPRIS, drop-folder files, Shutdown)
 //! - [`MprisEvent`]: Player-specific events (updates, seeks)
-//! - Update tracking: Avoids redundant UI updates using atomic version tracking
+//! - Update tracking: Avoids redundant UI updates by remembering the last sent version
 //! - Lyrics fetching: Async provider coordination with fallback logic
 //!
 //! # Flow
@@ -16,10 +16,11 @@
 //! 2. State is updated (player metadata, position, lyrics)
 //! 3. UI update is sent (if state changed meaningfully)
 
+use crate::lyrics::database::SessionChoices;
 use crate::mpris::TrackMetadata;
-use crate::state::{Provider, StateBundle, Update};
+use crate::pool::LoopConfig;
+use crate::state::{Provider, StateBundle, TrackTransition, Update};
 use tokio::sync::mpsc;
-use std::sync::atomic::{AtomicU64, Ordering};
 
 // ============================================================================
 // Event Types
@@ -33,7 +34,7 @@ struct NewTrackContext<'a> {
     playback_status: Option<String>,
     state: &'a mut StateBundle,
     update_tx: &'a mpsc::Sender<Update>,
-    providers: &'a [String],
+    config: &'a LoopConfig,
 }
 
 /// Events originating from MPRIS player interface.
@@ -63,19 +64,41 @@ pub enum MprisEvent {
 pub enum Event {
     /// MPRIS player event
     Mpris(MprisEvent),
+    /// An `.lrc` or `.yrc` file was written to the drop folder (`--drop-dir`)
+    LyricsFile(std::path::PathBuf),
+    /// A background lookup found word-synced lyrics for a track that was
+    /// loaded with line-level lyrics
+    LyricsUpgrade(Box<LyricsUpgrade>),
+    /// Request from the UI
+    User(UserCommand),
     /// Shutdown signal (graceful termination)
     Shutdown,
 }
 
+/// Requests a UI can send to the event loop (see [`crate::pool::listen`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserCommand {
+    /// The current lyrics are for another song or version: never use this
+    /// provider for the track again and look up the next one
+    WrongMatch,
+    /// Always use the provider of the current lyrics for the track (or
+    /// remove the pin if it is already pinned)
+    PinProvider,
+    /// Scroll unsynced lyrics faster (teleprompter mode)
+    TeleprompterFaster,
+    /// Scroll unsynced lyrics slower (teleprompter mode)
+    TeleprompterSlower,
+}
+
 // ============================================================================
 // Update Tracking
 // ============================================================================
 
-/// Tracks the last sent state to avoid redundant UI updates.
+/// Computes a composite state key from version and playing status.
 ///
-/// This atomic variable stores a composite key: `(version << 1) | playing_bit`.
 /// By combining version and playing state, we can detect meaningful changes
-/// without explicit comparison.
+/// without explicit comparison. Stored in [`StateBundle::last_sent`], so
+/// every event loop tracks its own updates.
 ///
 /// # Format
 ///
@@ -83,28 +106,21 @@ pub enum Event {
 /// [63:1] - Version counter
 /// [0:0]  - Playing bit (1 = playing, 0 = paused)
 /// ```
-static LAST_SENT_VERSION: AtomicU64 = AtomicU64::new(0);
-
-/// Computes a composite state key from version and playing status.
-///
-/// This packs both values into a single u64 for atomic comparison.
 #[inline]
 fn state_key(version: u64, playing: bool) -> u64 {
     (version << 1) | u64::from(playing)
 }
 
 /// Checks if the state has changed since the last sent update.
-///
-/// Uses relaxed ordering since this is an optimization hint, not a critical sync point.
 #[inline]
-fn state_changed(version: u64, playing: bool) -> bool {
-    state_key(version, playing) != LAST_SENT_VERSION.load(Ordering::Relaxed)
+fn state_changed(state: &StateBundle) -> bool {
+    state.last_sent != Some(state_key(state.version, state.player_state.playing))
 }
 
 /// Marks the current state as sent to prevent redundant updates.
 #[inline]
-fn mark_state_sent(version: u64, playing: bool) {
-    LAST_SENT_VERSION.store(state_key(version, playing), Ordering::Relaxed);
+fn mark_state_sent(state: &mut StateBundle) {
+    state.last_sent = Some(state_key(state.version, state.player_state.playing));
 }
 
 // ============================================================================
@@ -127,7 +143,7 @@ fn should_send_update(state: &StateBundle, force: bool) -> bool {
         return true;
     }
 
-    if !state_changed(state.version, state.player_state.playing) {
+    if !state_changed(state) {
         return false;
     }
 
@@ -152,15 +168,16 @@ fn should_send_update(state: &StateBundle, force: bool) -> bool {
 /// # Errors
 ///
 /// If the channel is closed, the update is silently dropped (receiver is gone).
-pub async fn send_update(state: &StateBundle, update_tx: &mpsc::Sender<Update>, force: bool) {
+pub async fn send_update(state: &mut StateBundle, update_tx: &mpsc::Sender<Update>, force: bool) {
     if !should_send_update(state, force) {
         return;
     }
 
     let update = state.create_update();
 
-    if update_tx.send(update).await.is_ok() {
-        mark_state_sent(state.version, state.player_state.playing);
+    if update_tx.send(update.clone()).await.is_ok() {
+        mark_state_sent(state);
+        state.last_sent_update = Some(update);
     }
 }
 
@@ -174,62 +191,534 @@ pub async fn send_update(state: &StateBundle, update_tx: &mpsc::Sender<Update>,
 /// or non-transient (stop trying and report error).
 enum FetchResult {
     /// Lyrics fetched successfully
-    Success,
-    /// Transient error (no lyrics found, network issue) - try next provider
+    Success(FetchedLyrics),
+    /// The provider has no lyrics for the track - try next provider
+    NotFound,
+    /// Recoverable error (network issue, failed script) - try next provider
     Transient,
+    /// Only unsynced lyrics, timed evenly over the track - try next
+    /// provider, fall back to these in teleprompter mode
+    Unsynced(FetchedLyrics),
     /// Non-transient error (API error, parse error) - stop trying
     NonTransient(crate::lyrics::LyricsError),
 }
 
+/// Lyrics returned by a provider, ready to be loaded into state and cached.
+#[derive(Debug)]
+struct FetchedLyrics {
+    lines: Vec<crate::lyrics::LyricLine>,
+    provider: Provider,
+    raw: Option<String>,
+    /// Track length the provider matched, if it reports one
+    duration: Option<f64>,
+    /// HTTP validators for conditional refetches
+    validators: crate::lyrics::types::HttpValidators,
+}
+
+/// Lyrics found in the background for `meta`: word-synced ones, or a
+/// changed version of a stale cache entry.
+#[derive(Debug)]
+pub struct LyricsUpgrade {
+    /// Track the lookup was started for, as reported by the player
+    meta: TrackMetadata,
+    /// Rewritten metadata the cache entry is keyed by
+    query: TrackMetadata,
+    fetched: FetchedLyrics,
+    /// Found by a `--cache-max-age` refresh rather than a word-synced search
+    refresh: bool,
+}
+
+/// Providers that can return per-word timings.
+const WORD_SYNCED_PROVIDERS: &[&str] = &[MUSIXMATCH, AMLL];
+
+/// How long a cached track whose word-synced search found nothing is left
+/// alone before cache hits search again.
+const UPGRADE_RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(7 * 86_400);
+
+/// Word-capable providers worth retrying after line-level lyrics were found.
+///
+/// `after` is the index of the provider that returned the line-level
+/// lyrics; only providers later in the order are tried (Musixmatch already
+/// falls back from richsync to subtitles itself). `None` (lyrics from a
+/// provider outside the order) considers the whole order.
+fn upgrade_candidates(providers: &[String], after: Option<usize>) -> Vec<String> {
+    let skip = after.map_or(0, |i| i + 1);
+    providers
+        .iter()
+        .skip(skip)
+        .filter(|p| WORD_SYNCED_PROVIDERS.contains(&p.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Looks for word-synced lyrics in the background and reports the first
+/// hit to the event loop, which swaps them in if the track is still playing.
+///
+/// A search that finds nothing is recorded in the cache entry, so the
+/// track isn't searched again on every play (see [`UPGRADE_RETRY_INTERVAL`]).
+fn spawn_lyrics_upgrade(
+    meta: &TrackMetadata,
+    query: &TrackMetadata,
+    candidates: Vec<String>,
+    config: &LoopConfig,
+) {
+    let Some(events) = config.events() else {
+        return;
+    };
+    if candidates.is_empty() {
+        return;
+    }
+
+    let meta = meta.clone();
+    let query = query.clone();
+    let musicbrainz = config.musicbrainz();
+    let tolerance = config.match_tolerance();
+    tracing::debug!(title = %meta.title, providers = ?candidates, "Looking for word-synced lyrics in the background");
+    tokio::spawn(async move {
+        let provider_query = if musicbrainz {
+            crate::lyrics::musicbrainz::normalize(&query).await
+        } else {
+            query.clone()
+        };
+        for provider in candidates {
+            if let FetchResult::Success(fetched) = try_provider(&provider, &query, &provider_query, tolerance).await
+                && fetched.provider.is_word_synced()
+            {
+                let upgrade = LyricsUpgrade { meta, query, fetched, refresh: false };
+                let _ = events.send(Event::LyricsUpgrade(Box::new(upgrade))).await;
+                return;
+            }
+        }
+        crate::lyrics::database::mark_upgrade_failed(&query.artist, &query.title, &query.album).await;
+    });
+}
+
+/// Refetches a stale cache entry in the background (`--cache-max-age`).
+///
+/// `providers` are the `--providers` entries the cached lyrics came from,
+/// asked in order. Lyrics that are unchanged (or reported as such by a
+/// conditional request) only renew the entry's age; changed ones are sent
+/// to the event loop like an upgrade.
+fn spawn_cache_refresh(meta: &TrackMetadata, query: &TrackMetadata, providers: &[String], config: &LoopConfig) {
+    let Some(events) = config.events() else {
+        return;
+    };
+
+    let meta = meta.clone();
+    let query = query.clone();
+    let providers = providers.to_vec();
+    let musicbrainz = config.musicbrainz();
+    let tolerance = config.match_tolerance();
+    tracing::debug!(title = %meta.title, "Refreshing stale cached lyrics in the background");
+    tokio::spawn(async move {
+        let provider_query = if musicbrainz {
+            crate::lyrics::musicbrainz::normalize(&query).await
+        } else {
+            query.clone()
+        };
+        for provider in providers {
+            let FetchResult::Success(fetched) = try_provider(&provider, &query, &provider_query, tolerance).await else {
+                continue;
+            };
+            let cached = crate::lyrics::database::fetch_from_database(&query.artist, &query.title, &query.album, None, 0.0)
+                .await
+                .and_then(|result| result.ok())
+                .map(|cached| cached.raw);
+            if fetched.raw.is_none() || fetched.raw == cached {
+                break;
+            }
+            let upgrade = LyricsUpgrade { meta, query, fetched, refresh: true };
+            let _ = events.send(Event::LyricsUpgrade(Box::new(upgrade))).await;
+            return;
+        }
+        // Unchanged or gone everywhere: keep the entry, check again after max-age
+        crate::lyrics::database::touch(&query.artist, &query.title, &query.album).await;
+    });
+}
+
 /// Attempts to fetch lyrics from a single provider by name.
 ///
+/// `query` is the metadata sent to the provider; `key` the (rewritten)
+/// metadata the cache is keyed by, which differs with `--musicbrainz`.
+///
 /// # Returns
 ///
-/// - `Success` if lyrics were fetched and stored
-/// - `Transient` if the provider didn't have lyrics or had a recoverable error
+/// - `Success` with the fetched lyrics
+/// - `NotFound` if the provider didn't have lyrics
+/// - `Transient` if the provider had a recoverable error
 /// - `NonTransient` if a fatal error occurred
-async fn try_provider(provider: &str, meta: &TrackMetadata, state: &mut StateBundle) -> FetchResult {
-    match provider {
-        "lrclib" => try_lrclib(meta, state).await,
-        "musixmatch" => try_musixmatch(meta, state).await,
-        _ => {
-            // Unknown provider - treat as transient to continue to next
-            FetchResult::Transient
+///
+/// `tolerance` is the allowed duration mismatch (a fraction) for reusing
+/// the cached copy of lyrics the provider reports unchanged.
+///
+/// Each attempt is recorded in the database's fetch metrics.
+async fn try_provider(provider: &str, key: &TrackMetadata, query: &TrackMetadata, tolerance: f64) -> FetchResult {
+    use crate::lyrics::database::FetchOutcome;
+
+    let started = std::time::Instant::now();
+    let result = query_provider(provider, key, query, tolerance).await;
+    let outcome = match &result {
+        FetchResult::Success(_) => FetchOutcome::Found,
+        FetchResult::NotFound | FetchResult::Transient | FetchResult::Unsynced(_) => FetchOutcome::Missed,
+        FetchResult::NonTransient(_) => FetchOutcome::Failed,
+    };
+    crate::lyrics::database::record_fetch(
+        &query.artist,
+        &query.title,
+        &query.album,
+        provider,
+        outcome,
+        started.elapsed(),
+    )
+    .await;
+    result
+}
+
+/// Result of [`lookup_providers`].
+enum Outcome {
+    /// Synced lyrics from `providers[index]`
+    Found { index: usize, fetched: FetchedLyrics },
+    /// No provider had synced lyrics
+    Missing {
+        /// The first unsynced lyrics found, in teleprompter mode
+        unsynced: Option<FetchedLyrics>,
+        /// A provider couldn't be reached, so a later try may find lyrics
+        unreachable: bool,
+    },
+    /// `providers[index]` failed with a non-transient error
+    Failed { index: usize, err: crate::lyrics::LyricsError },
+}
+
+/// Asks `providers` in order for `query` (the metadata sent to them) until
+/// one has synced lyrics or one fails for good. Nothing is cached; `key` is
+/// the metadata the cache is keyed by, as for [`try_provider`].
+async fn lookup_providers(key: &TrackMetadata, query: &TrackMetadata, providers: &[String], config: &LoopConfig) -> Outcome {
+    let mut unsynced = None;
+    let mut unreachable = false;
+    for (index, provider) in providers.iter().enumerate() {
+        match try_provider(provider, key, query, config.match_tolerance()).await {
+            FetchResult::Success(fetched) => return Outcome::Found { index, fetched },
+            FetchResult::NotFound => {}
+            FetchResult::Transient => unreachable = true,
+            FetchResult::Unsynced(fetched) => {
+                if config.teleprompter() && unsynced.is_none() {
+                    unsynced = Some(fetched);
+                }
+            }
+            FetchResult::NonTransient(err) => return Outcome::Failed { index, err },
         }
     }
+    Outcome::Missing { unsynced, unreachable }
+}
+
+/// Asks `provider` alone for `query`, bypassing the cache (used by `compare`).
+///
+/// `key` and `query` are as for [`try_provider`]. Returns `Ok(None)` if the
+/// provider has no lyrics for the track.
+pub async fn lookup(
+    provider: &str,
+    key: &TrackMetadata,
+    query: &TrackMetadata,
+    config: &LoopConfig,
+) -> Result<Option<(Vec<crate::lyrics::LyricLine>, Provider)>, crate::lyrics::LyricsError> {
+    match lookup_providers(key, query, &[provider.to_string()], config).await {
+        Outcome::Found { fetched, .. } => Ok(Some((fetched.lines, fetched.provider))),
+        Outcome::Missing { .. } => Ok(None),
+        Outcome::Failed { err, .. } => Err(err),
+    }
+}
+
+/// What a lookup of `query` would do (`--explain`).
+pub struct Explanation {
+    /// Cached lines and their provider, if the cache has a match
+    pub cached: Option<(usize, Option<Provider>)>,
+    pub pinned: Option<String>,
+    pub rejected: Vec<String>,
+    /// Metadata sent to providers: the query, or its cached MusicBrainz
+    /// resolution with `--musicbrainz`
+    pub provider_query: TrackMetadata,
+    /// `--musicbrainz` is on but the track was never resolved, so a
+    /// MusicBrainz search would come first
+    pub musicbrainz_pending: bool,
+    /// Providers in the order they would be asked, with their requests
+    pub requests: Vec<(String, Vec<String>)>,
+}
+
+/// Works out what fetching lyrics for `query` (already rewritten) would
+/// send where, reading only the local cache.
+pub async fn explain(query: &TrackMetadata, config: &LoopConfig) -> Explanation {
+    // A one-shot command has no session of its own
+    let session = &SessionChoices::default();
+    let pinned = pinned_provider(config, session, query).await;
+    let rejected = crate::lyrics::database::rejected_providers(session, &query.artist, &query.title, &query.album).await;
+    let cached = try_database(query, config).await.map(|cached| (cached.lines.len(), Some(cached.format.provider())));
+
+    let resolved = if config.musicbrainz() {
+        crate::lyrics::database::fetch_musicbrainz_match(&query.artist, &query.title, &query.album).await
+    } else {
+        None
+    };
+    let musicbrainz_pending = config.musicbrainz() && resolved.is_none();
+    let provider_query = resolved.map_or_else(|| query.clone(), |mb| mb.apply(query));
+
+    let mut requests = Vec::new();
+    for provider in allowed_providers(config, session, query, pinned.as_deref()).await {
+        let request = explain_provider(&provider, query, &provider_query).await;
+        requests.push((provider, request));
+    }
+    Explanation { cached, pinned, rejected, provider_query, musicbrainz_pending, requests }
+}
+
+/// The requests [`query_provider`] would make for `key` and `query`.
+async fn explain_provider(provider: &str, key: &TrackMetadata, query: &TrackMetadata) -> Vec<String> {
+    use crate::lyrics::providers::{amll, exec, lrclib, musixmatch};
+
+    let (artist, title, album) = (&query.artist, &query.title, &query.album);
+    match ProviderName::parse(provider) {
+        Some(ProviderName::Lrclib) => {
+            let validators = crate::lyrics::database::fetch_validators(&key.artist, &key.title, &key.album).await;
+            lrclib::explain(artist, title, album, query.length, validators.as_ref())
+        }
+        Some(ProviderName::Musixmatch) => {
+            musixmatch::explain(artist, title, album, query.length, query.spotify_id.as_deref()).await
+        }
+        Some(ProviderName::Amll) => amll::explain(query.spotify_id.as_deref()),
+        Some(ProviderName::Exec(path)) => exec::explain(path, artist, title, album, query.length),
+        None => vec!["skipped: unknown provider".to_string()],
+    }
+}
+
+/// Outcome of [`warm_cache`] for one track.
+#[derive(Debug)]
+pub enum Warmed {
+    /// The track matches an ignore rule
+    Ignored,
+    /// A local lyrics file exists (those are never cached)
+    Local,
+    /// The cache already had lyrics
+    Cached,
+    /// Lyrics were fetched from a provider and cached
+    Fetched(Provider),
+    /// No provider had lyrics
+    NotFound,
+    /// No provider had lyrics, but some couldn't be reached
+    Unreachable,
+    /// A provider failed with a non-transient error
+    Failed(crate::lyrics::LyricsError),
+}
+
+impl Warmed {
+    /// Whether a provider was asked, so the next lookup should be delayed.
+    pub fn queried_providers(&self) -> bool {
+        matches!(self, Self::Fetched(_) | Self::NotFound | Self::Unreachable | Self::Failed(_))
+    }
+}
+
+/// Makes sure lyrics for `meta` are cached, asking providers only on a
+/// cache miss (used by `prefetch`).
+///
+/// Follows the lookup of [`fetch_api_lyrics`] without touching any state.
+pub async fn warm_cache(meta: &TrackMetadata, config: &LoopConfig) -> Warmed {
+    if config.ignore_rules().matches(meta) {
+        return Warmed::Ignored;
+    }
+
+    let query = config.rewrite_rules().apply(meta);
+    if try_local_files(&query).await.is_some() {
+        return Warmed::Local;
+    }
+    if try_database(&query, config).await.is_some() {
+        return Warmed::Cached;
+    }
+
+    let provider_query = if config.musicbrainz() {
+        crate::lyrics::musicbrainz::normalize(&query).await
+    } else {
+        query.clone()
+    };
+    let session = &SessionChoices::default();
+    let pinned = pinned_provider(config, session, &query).await;
+    let providers = allowed_providers(config, session, &query, pinned.as_deref()).await;
+    match lookup_providers(&query, &provider_query, &providers, config).await {
+        Outcome::Found { fetched, .. } => {
+            let format = provider_to_db_format(fetched.provider);
+            store_lyrics_in_cache(&query, fetched.raw, fetched.duration, &fetched.validators, format).await;
+            Warmed::Fetched(fetched.provider)
+        }
+        Outcome::Missing { unreachable: true, .. } => Warmed::Unreachable,
+        Outcome::Missing { unreachable: false, .. } => Warmed::NotFound,
+        Outcome::Failed { err, .. } => Warmed::Failed(err),
+    }
+}
+
+/// Whether the provider `name` (`exec:` standing for every script) covers `provider`.
+fn provider_matches(name: &str, provider: &str) -> bool {
+    name == provider || (name.ends_with(':') && provider.starts_with(name))
+}
+
+/// Provider pinned for `query`: with the pin key (kept in the database)
+/// or, failing that, by a `[[pin]]` rule in the config file.
+async fn pinned_provider(config: &LoopConfig, session: &SessionChoices, query: &TrackMetadata) -> Option<String> {
+    let pinned = crate::lyrics::database::pinned_provider(session, &query.artist, &query.title, &query.album).await;
+    pinned.or_else(|| config.pinned_provider(query).map(str::to_string))
+}
+
+/// Providers to ask for `query`, without those the user rejected for it
+/// and with the `pinned` one first.
+async fn allowed_providers(
+    config: &LoopConfig,
+    session: &SessionChoices,
+    query: &TrackMetadata,
+    pinned: Option<&str>,
+) -> Vec<String> {
+    let rejected = crate::lyrics::database::rejected_providers(session, &query.artist, &query.title, &query.album).await;
+    let mut providers = config.providers_for(query).to_vec();
+    if let Some(pin) = pinned {
+        if !pin.ends_with(':') && !providers.iter().any(|p| p == pin) {
+            providers.push(pin.to_string());
+        }
+        // Stable: keeps the configured order otherwise
+        providers.sort_by_key(|provider| !provider_matches(pin, provider));
+    }
+    providers.retain(|provider| !rejected.iter().any(|r| provider_matches(r, provider)));
+    providers
+}
+
+pub const LRCLIB: &str = "lrclib";
+pub const MUSIXMATCH: &str = "musixmatch";
+pub const AMLL: &str = "amll";
+
+/// Built-in `--providers` names [`query_provider`] dispatches, in the default
+/// order. Scripts are named with [`EXEC_PREFIX`](crate::lyrics::providers::exec::EXEC_PREFIX)
+/// followed by their path.
+pub const PROVIDERS: &[&str] = &[LRCLIB, MUSIXMATCH, AMLL];
+
+/// A `--providers` entry. Code that treats providers differently matches on
+/// this, so a new provider can't be left out of one of those places.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderName<'a> {
+    Lrclib,
+    Musixmatch,
+    Amll,
+    /// A script, with its path
+    Exec(&'a str),
+}
+
+impl<'a> ProviderName<'a> {
+    /// Parses one of [`PROVIDERS`] or an `exec:PATH` entry.
+    pub fn parse(name: &'a str) -> Option<Self> {
+        match name {
+            LRCLIB => Some(Self::Lrclib),
+            MUSIXMATCH => Some(Self::Musixmatch),
+            AMLL => Some(Self::Amll),
+            _ => name.strip_prefix(crate::lyrics::providers::exec::EXEC_PREFIX).map(Self::Exec),
+        }
+    }
+}
+
+/// Dispatches a lookup to the provider named `provider`.
+async fn query_provider(provider: &str, key: &TrackMetadata, query: &TrackMetadata, tolerance: f64) -> FetchResult {
+    match ProviderName::parse(provider) {
+        Some(ProviderName::Lrclib) => try_lrclib(key, query, tolerance).await,
+        Some(ProviderName::Musixmatch) => try_musixmatch(query).await,
+        Some(ProviderName::Amll) => try_amll(query).await,
+        Some(ProviderName::Exec(path)) => try_exec(path, query).await,
+        // Unknown provider - has nothing, continue to next
+        None => FetchResult::NotFound,
+    }
 }
 
 /// Stores fetched lyrics in the database cache.
 ///
+/// Without `raw`, LRCLIB reported its cached version unchanged; it is made
+/// the preferred version instead.
+///
 /// Helper to reduce duplication across provider implementations.
 async fn store_lyrics_in_cache(
     meta: &TrackMetadata,
     raw: Option<String>,
+    provider_duration: Option<f64>,
+    validators: &crate::lyrics::types::HttpValidators,
     format: crate::lyrics::database::LyricsFormat,
 ) {
-    if let Some(raw_text) = raw {
-        crate::lyrics::database::store_in_database(
-            &meta.artist,
-            &meta.title,
-            &meta.album,
-            meta.length,
-            format,
-            raw_text,
-        ).await;
+    match raw {
+        Some(raw_text) => {
+            crate::lyrics::database::store_in_database(
+                &meta.artist,
+                &meta.title,
+                &meta.album,
+                meta.length,
+                provider_duration,
+                validators,
+                format,
+                raw_text,
+            ).await;
+        }
+        None if format == crate::lyrics::database::LyricsFormat::Lrclib => {
+            crate::lyrics::database::prefer_version(&meta.artist, &meta.title, &meta.album, LRCLIB).await;
+        }
+        None => {}
     }
 }
 
 /// Fetches lyrics from LRCLIB.
 ///
+/// If the track is cached with HTTP validators the request is conditional;
+/// when LRCLIB reports the lyrics unchanged, the cached copy is used (and,
+/// without `raw`, not stored again) if its duration is within `tolerance`
+/// of the track's, as on a cache hit. The cache entry is the one for `key`,
+/// while `meta` is sent to LRCLIB.
+///
 /// Network errors are treated as transient to allow fallback to other providers.
-async fn try_lrclib(meta: &TrackMetadata, state: &mut StateBundle) -> FetchResult {
-    match crate::lyrics::fetch_lyrics_from_lrclib(&meta.artist, &meta.title, &meta.album, meta.length).await {
-        Ok((lines, raw)) if !lines.is_empty() => {
-            state.update_lyrics(lines, meta, None, Some(Provider::LRCLIB));
-            store_lyrics_in_cache(meta, raw, crate::lyrics::database::LyricsFormat::Lrclib).await;
-            FetchResult::Success
+async fn try_lrclib(key: &TrackMetadata, meta: &TrackMetadata, tolerance: f64) -> FetchResult {
+    use crate::lyrics::providers::lrclib::{LrclibFetch, LrclibLyrics};
+
+    let validators = crate::lyrics::database::fetch_validators(&key.artist, &key.title, &key.album).await;
+    let response = crate::lyrics::fetch_lyrics_from_lrclib(
+        &meta.artist,
+        &meta.title,
+        &meta.album,
+        meta.length,
+        validators.as_ref(),
+    )
+    .await;
+    match response {
+        Ok(LrclibFetch::Lyrics(lyrics)) if !lyrics.lines.is_empty() => FetchResult::Success(FetchedLyrics {
+            lines: lyrics.lines,
+            provider: Provider::LRCLIB,
+            raw: lyrics.raw,
+            duration: lyrics.duration,
+            validators: lyrics.validators,
+        }),
+        Ok(LrclibFetch::Lyrics(LrclibLyrics { plain: Some(plain), duration, .. })) => {
+            FetchResult::Unsynced(FetchedLyrics {
+                lines: crate::lyrics::parse::time_plain_lyrics(&plain, meta.length.or(duration)),
+                provider: Provider::LrclibPlain,
+                // Not cached: the timing is made up
+                raw: None,
+                duration,
+                validators: Default::default(),
+            })
+        }
+        Ok(LrclibFetch::Lyrics(_)) => FetchResult::NotFound,
+        Ok(LrclibFetch::NotModified) => {
+            tracing::debug!(title = %meta.title, artist = %meta.artist, "LRCLIB lyrics unchanged");
+            // The LRCLIB version may not be the preferred one; it only becomes
+            // that once these lyrics are used (see `store_lyrics_in_cache`)
+            let cached =
+                crate::lyrics::database::fetch_version(&key.artist, &key.title, &key.album, LRCLIB, key.length, tolerance)
+                    .await;
+            match cached {
+                Some(Ok(cached)) if !cached.lines.is_empty() => FetchResult::Success(FetchedLyrics {
+                    lines: cached.lines,
+                    provider: Provider::LRCLIB,
+                    raw: None,
+                    duration: cached.provider_duration,
+                    validators: Default::default(),
+                }),
+                _ => FetchResult::NotFound,
+            }
         }
-        Ok(_) => FetchResult::Transient,
         Err(crate::lyrics::LyricsError::Network(_)) => FetchResult::Transient,
         Err(e) => FetchResult::NonTransient(e),
     }
@@ -238,9 +727,51 @@ async fn try_lrclib(meta: &TrackMetadata, state: &mut StateBundle) -> FetchResul
 /// Maps a Provider enum to the corresponding database LyricsFormat.
 fn provider_to_db_format(provider: Provider) -> crate::lyrics::database::LyricsFormat {
     match provider {
-        Provider::LRCLIB => crate::lyrics::database::LyricsFormat::Lrclib,
+        Provider::LRCLIB | Provider::LrclibPlain => crate::lyrics::database::LyricsFormat::Lrclib,
         Provider::MusixmatchRichsync => crate::lyrics::database::LyricsFormat::Richsync,
         Provider::MusixmatchSubtitles => crate::lyrics::database::LyricsFormat::Subtitles,
+        Provider::AmllTtml => crate::lyrics::database::LyricsFormat::Ttml,
+        Provider::NeteaseYrc => crate::lyrics::database::LyricsFormat::Yrc,
+        Provider::Exec => crate::lyrics::database::LyricsFormat::Exec,
+        Provider::LocalFile => crate::lyrics::database::LyricsFormat::Local,
+    }
+}
+
+/// Runs a user script provider.
+///
+/// A script that can't be started is logged and skipped, like a network error.
+async fn try_exec(path: &str, meta: &TrackMetadata) -> FetchResult {
+    match crate::lyrics::fetch_lyrics_from_exec(path, &meta.artist, &meta.title, &meta.album, meta.length).await {
+        Ok((lines, raw)) if !lines.is_empty() => FetchResult::Success(FetchedLyrics {
+            lines,
+            provider: Provider::Exec,
+            raw,
+            duration: None,
+            validators: Default::default(),
+        }),
+        Ok(_) => FetchResult::NotFound,
+        Err(e) => {
+            tracing::warn!(error = %e, "Lyrics script failed");
+            FetchResult::Transient
+        }
+    }
+}
+
+/// Fetches TTML lyrics from the AMLL database (Spotify tracks only).
+///
+/// Network errors are treated as transient.
+async fn try_amll(meta: &TrackMetadata) -> FetchResult {
+    match crate::lyrics::fetch_lyrics_from_amll(meta.spotify_id.as_deref()).await {
+        Ok((lines, raw)) if !lines.is_empty() => FetchResult::Success(FetchedLyrics {
+            lines,
+            provider: Provider::AmllTtml,
+            raw,
+            duration: None,
+            validators: Default::default(),
+        }),
+        Ok(_) => FetchResult::NotFound,
+        Err(crate::lyrics::LyricsError::Network(_)) => FetchResult::Transient,
+        Err(e) => FetchResult::NonTransient(e),
     }
 }
 
@@ -248,7 +779,7 @@ fn provider_to_db_format(provider: Provider) -> crate::lyrics::database::LyricsF
 ///
 /// Automatically detects whether the response is Richsync or Subtitles format.
 /// Network errors are treated as transient.
-async fn try_musixmatch(meta: &TrackMetadata, state: &mut StateBundle) -> FetchResult {
+async fn try_musixmatch(meta: &TrackMetadata) -> FetchResult {
     match crate::lyrics::fetch_lyrics_from_musixmatch_usertoken(
         &meta.artist,
         &meta.title,
@@ -260,14 +791,20 @@ async fn try_musixmatch(meta: &TrackMetadata, state: &mut StateBundle) -> FetchR
     {
         Ok((lines, raw)) if !lines.is_empty() => {
             let provider = determine_musixmatch_provider(&lines, &raw);
-            state.update_lyrics(lines, meta, None, Some(provider));
-            
-            let format = provider_to_db_format(provider);
-            store_lyrics_in_cache(meta, raw, format).await;
-            
-            FetchResult::Success
-        }
-        Ok(_) => FetchResult::Transient,
+            // Known when the track was found through `track.search`
+            let duration = crate::lyrics::database::fetch_musixmatch_track(&meta.artist, &meta.title, &meta.album)
+                .await
+                .and_then(|(_, length)| length)
+                .map(|length| length as f64);
+            FetchResult::Success(FetchedLyrics {
+                lines,
+                provider,
+                raw,
+                duration,
+                validators: Default::default(),
+            })
+        }
+        Ok(_) => FetchResult::NotFound,
         Err(crate::lyrics::LyricsError::Network(_)) => FetchResult::Transient,
         Err(e) => FetchResult::NonTransient(e),
     }
@@ -290,77 +827,42 @@ fn determine_musixmatch_provider(lines: &[crate::lyrics::LyricLine], raw: &Optio
     }
 }
 
-/// Determines provider type from raw lyrics format.
-///
-/// Used when retrieving lyrics from the database cache.
-/// Detects based on JSON structure since raw is now the original JSON.
-///
-/// # Format Detection
-///
-/// - **Richsync**: `[{"ts":29.26,"te":31.597,"l":[{"c":"Have","o":0}...],"x":"text"}...]`
-///   - Has `"ts"`, `"te"`, `"l"`, or `"words"` fields
-///   - Contains word-level timing data
+/// Looks for an `Artist - Title.lrc` file in the local lyrics directories.
 ///
-/// - **Subtitles**: `[{"text":"lyrics","time":{"total":29.26,"minutes":0,"seconds":29,"hundredths":26}}...]`
-///   - Has `"time"` object with `"total"`, `"minutes"`, `"seconds"` fields
-///   - Line-level timing only
-///
-/// - **LRC**: `[00:29.26]Have you got colour in your cheeks?`
-///   - Plain text with timestamp markers
-fn detect_provider_from_raw(raw: &Option<String>) -> Option<Provider> {
-    raw.as_deref().map(|text| {
-        let trimmed = text.trim_start();
-        if trimmed.starts_with("[{") {
-            // JSON array - distinguish between richsync and subtitles
-            // Richsync has word-level timing: "l":[...] or "words":[...]
-            // Subtitles has line-level timing: "time":{"total":...}
-            if trimmed.contains("\"ts\":") || trimmed.contains("\"l\":[") || trimmed.contains("\"words\":[") {
-                Provider::MusixmatchRichsync
-            } else if trimmed.contains("\"time\":{") {
-                Provider::MusixmatchSubtitles
-            } else {
-                // Unknown JSON format, default to subtitles
-                Provider::MusixmatchSubtitles
-            }
-        } else if trimmed.starts_with('[') {
-            // LRC format starts with [MM:SS.CC]
-            Provider::LRCLIB
-        } else {
-            // Default to LRCLIB
-            Provider::LRCLIB
-        }
-    })
+/// Local files are never cached: editing the file takes effect on the next play.
+async fn try_local_files(meta: &TrackMetadata) -> Option<Vec<crate::lyrics::LyricLine>> {
+    match crate::lyrics::fetch_lyrics_from_local_dirs(&meta.artist, &meta.title).await {
+        Ok((lines, _)) if !lines.is_empty() => Some(lines),
+        _ => None,
+    }
 }
 
 /// Attempts to fetch lyrics from the database cache.
 ///
-/// Returns `true` if lyrics were found and loaded successfully.
+/// Returns the cached lyrics on a hit. Durations are compared using the
+/// configured tolerance unless `--ignore-duration` is set.
 async fn try_database(
     meta: &TrackMetadata,
-    state: &mut StateBundle,
-) -> bool {
-    let Some(db_result) = crate::lyrics::database::fetch_from_database(
+    config: &LoopConfig,
+) -> Option<crate::lyrics::database::CachedLyrics> {
+    let duration = if config.ignore_duration() { None } else { meta.length };
+    let db_result = crate::lyrics::database::fetch_from_database(
         &meta.artist,
         &meta.title,
         &meta.album,
-        meta.length,
-    ).await else {
-        return false;
-    };
+        duration,
+        config.duration_tolerance(),
+    ).await?;
 
     match db_result {
-        Ok((lines, raw)) if !lines.is_empty() => {
-            let provider = detect_provider_from_raw(&raw);
-            let line_count = lines.len();
-            state.update_lyrics(lines, meta, None, provider);
-            
+        Ok(cached) if !cached.lines.is_empty() => {
             tracing::debug!(
                 title = %meta.title,
                 artist = %meta.artist,
-                lines = line_count,
+                lines = cached.lines.len(),
                 "Database cache hit"
             );
-            true
+            Some(cached)
         }
         Ok(_) => {
             tracing::debug!(
@@ -368,7 +870,7 @@ async fn try_database(
                 artist = %meta.artist,
                 "Empty lyrics in database cache"
             );
-            false
+            None
         }
         Err(e) => {
             tracing::warn!(
@@ -377,7 +879,7 @@ async fn try_database(
                 error = %e,
                 "Failed to parse cached lyrics"
             );
-            false
+            None
         }
     }
 }
@@ -388,63 +890,161 @@ async fn try_database(
 ///
 /// # Behavior
 ///
-/// 1. Check database first
-/// 2. Try each provider in order
-/// 3. On success: update state and return
-/// 4. On transient error: try next provider
-/// 5. On non-transient error: log, update state with error, return
-/// 6. If all fail: update state with empty lyrics
+/// 1. Apply user rewrite rules to build the query metadata
+/// 2. Check local lyrics directories, then the database
+/// 3. Try each provider in order
+/// 4. On success: update state, cache and return
+/// 5. On transient error: try next provider
+/// 6. On non-transient error: log, update state with error, return
+/// 7. If all fail: update state with empty lyrics
+///
+/// State always keeps the player's original metadata; only lookups use
+/// the rewritten query.
 async fn fetch_api_lyrics(
     meta: &TrackMetadata,
     state: &mut StateBundle,
-    providers: &[String],
+    config: &LoopConfig,
 ) {
-    // Try database cache first
-    if try_database(meta, state).await {
+    if config.ignore_rules().matches(meta) {
+        tracing::debug!(title = %meta.title, artist = %meta.artist, "Track matches an ignore rule, not fetching lyrics");
+        state.update_lyrics(Vec::new(), meta, None, None);
         return;
     }
 
-    // Database miss - try external providers
-    for provider in providers {
-        match try_provider(provider, meta, state).await {
-            FetchResult::Success => return,
-            FetchResult::Transient => continue,
-            FetchResult::NonTransient(err) => {
-                tracing::warn!(
-                    provider = %provider,
-                    error = %err,
-                    track = %meta.title,
-                    artist = %meta.artist,
-                    "Provider failed to fetch lyrics"
-                );
-                state.update_lyrics(Vec::new(), meta, Some(err.to_string()), None);
-                return;
+    let query = config.rewrite_rules().apply(meta);
+    let pinned = pinned_provider(config, &state.choices, &query).await;
+    let providers = &allowed_providers(config, &state.choices, &query, pinned.as_deref()).await;
+
+    // User-provided files in the standard lyrics directories win over everything
+    if let Some(lines) = try_local_files(&query).await {
+        state.update_lyrics(lines, meta, None, Some(Provider::LocalFile));
+        return;
+    }
+
+    // Then the database cache
+    let started = std::time::Instant::now();
+    // A version cached from another provider than the pinned one doesn't
+    // count; switch to the pinned provider's version if there is one
+    let from_pinned = |cached: &crate::lyrics::database::CachedLyrics| {
+        pinned.as_deref().is_none_or(|pin| cached.format.provider().source().is_some_and(|s| provider_matches(s, pin)))
+    };
+    let mut cached = try_database(&query, config).await;
+    if let (Some(pin), Some(entry)) = (pinned.as_deref(), &cached)
+        && !from_pinned(entry)
+    {
+        cached = None;
+        if crate::lyrics::database::prefer_version(&query.artist, &query.title, &query.album, pin).await {
+            cached = try_database(&query, config).await.filter(from_pinned);
+        }
+    }
+    if cached.is_some() {
+        crate::lyrics::database::record_fetch(
+            &query.artist,
+            &query.title,
+            &query.album,
+            "cache",
+            crate::lyrics::database::FetchOutcome::Found,
+            started.elapsed(),
+        )
+        .await;
+    }
+    if let Some(cached) = cached {
+        let provider = cached.format.provider();
+        // Only the provider the entry came from can tell whether it changed
+        let origin: Vec<String> = provider
+            .source()
+            .map(|source| providers.iter().filter(|p| provider_matches(source, p)).cloned().collect())
+            .unwrap_or_default();
+        let stale = match config.cache_max_age() {
+            Some(max_age) if !origin.is_empty() => {
+                crate::lyrics::database::is_stale(&query.artist, &query.title, &query.album, max_age).await
             }
+            _ => false,
+        };
+        if stale {
+            spawn_cache_refresh(meta, &query, &origin, config);
+        } else if pinned.is_none()
+            && !provider.is_word_synced()
+            && !crate::lyrics::database::upgrade_failed_recently(
+                &query.artist,
+                &query.title,
+                &query.album,
+                UPGRADE_RETRY_INTERVAL,
+            )
+            .await
+        {
+            let after = provider
+                .source()
+                .and_then(|source| providers.iter().position(|p| provider_matches(source, p)));
+            spawn_lyrics_upgrade(meta, &query, upgrade_candidates(providers, after), config);
         }
+        state.update_lyrics(cached.lines, meta, None, Some(provider));
+        state.set_provider_duration(cached.provider_duration);
+        return;
     }
 
-    // No provider succeeded - update with empty lyrics
-    state.update_lyrics(Vec::new(), meta, None, None);
+    // Database miss - optionally canonicalize via MusicBrainz before asking
+    // providers. The cache stays keyed by the pre-normalization query.
+    let provider_query = if config.musicbrainz() {
+        crate::lyrics::musicbrainz::normalize(&query).await
+    } else {
+        query.clone()
+    };
+
+    match lookup_providers(&query, &provider_query, providers, config).await {
+        Outcome::Found { index, fetched } => {
+            if pinned.is_none() && !fetched.provider.is_word_synced() {
+                let candidates = upgrade_candidates(providers, Some(index));
+                spawn_lyrics_upgrade(meta, &query, candidates, config);
+            }
+            let format = provider_to_db_format(fetched.provider);
+            state.update_lyrics(fetched.lines, meta, None, Some(fetched.provider));
+            state.set_provider_duration(fetched.duration);
+            store_lyrics_in_cache(&query, fetched.raw, fetched.duration, &fetched.validators, format).await;
+        }
+        // No synced lyrics anywhere - scroll unsynced ones in teleprompter mode
+        Outcome::Missing { unsynced: Some(fetched), .. } => {
+            tracing::debug!(title = %meta.title, artist = %meta.artist, "Only unsynced lyrics found, using teleprompter mode");
+            state.update_lyrics(fetched.lines, meta, None, Some(fetched.provider));
+            state.set_provider_duration(fetched.duration);
+        }
+        // No provider succeeded - update with empty lyrics
+        Outcome::Missing { unsynced: None, .. } => state.update_lyrics(Vec::new(), meta, None, None),
+        Outcome::Failed { index, err } => {
+            tracing::warn!(
+                provider = %providers[index],
+                error = %err,
+                track = %meta.title,
+                artist = %meta.artist,
+                "Provider failed to fetch lyrics"
+            );
+            state.update_lyrics(Vec::new(), meta, Some((&err).into()), None);
+        }
+    }
 }
 
 /// Fetches a fresh position from the player or estimates it.
 ///
 /// Falls back to estimation if D-Bus query fails or no service is provided.
+/// Radio streams are always estimated, as their `Position` is stream time.
+/// Positions read from D-Bus have the player's configured offset applied.
 async fn fetch_fresh_position(
     service: Option<&str>,
     state: &StateBundle,
+    config: &LoopConfig,
 ) -> f64 {
-    let Some(svc) = service else {
+    let Some(svc) = service.filter(|_| !state.player_state.stream) else {
         let estimated = state.player_state.estimate_position();
         tracing::debug!(
             position = %format!("{:.3}s", estimated),
-            "Using estimated position (no service)"
+            "Using estimated position (no service or radio stream)"
         );
         return estimated;
     };
 
     match crate::mpris::playback::get_position(svc).await {
         Ok(pos) => {
+            let pos = pos + player_offset(config, svc);
             tracing::debug!(
                 service = %svc,
                 position = %format!("{:.3}s", pos),
@@ -479,16 +1079,24 @@ async fn fetch_fresh_position(
 pub async fn fetch_and_update_lyrics(
     meta: &TrackMetadata,
     state: &mut StateBundle,
-    providers: &[String],
+    config: &LoopConfig,
     service: Option<&str>,
 ) -> f64 {
     let position_before = state.player_state.estimate_position();
     let start_time = std::time::Instant::now();
     
-    fetch_api_lyrics(meta, state, providers).await;
+    fetch_api_lyrics(meta, state, config).await;
     
     let fetch_duration = start_time.elapsed();
-    let position = fetch_fresh_position(service, state).await;
+    let mut position = fetch_fresh_position(service, state, config).await;
+    if state.transition.is_some_and(|t| t.is_stale(position)) {
+        // Gapless playback: Position hasn't reset for the new track yet
+        tracing::debug!(
+            reported = %format!("{:.3}s", position),
+            "Fresh position still belongs to the previous track, keeping the estimate"
+        );
+        position = state.player_state.estimate_position();
+    }
     let position_change = position - position_before;
     
     // Note: position_change can be negative if user seeked backward during fetch,
@@ -512,6 +1120,16 @@ pub async fn fetch_and_update_lyrics(
 // Event Processing
 // ============================================================================
 
+/// Renders the final position of a burst of seeks once it has settled
+/// (the event loop calls this when [`crate::state::SeekBurst::flush_in`] elapses).
+pub async fn flush_seeks(state: &mut StateBundle, update_tx: &mpsc::Sender<Update>) {
+    if state.seeks.take_pending() {
+        let position = state.player_state.estimate_position();
+        state.update_index(position);
+        send_update(state, update_tx, true).await;
+    }
+}
+
 /// Processes a single event from the event loop.
 ///
 /// This is the main entry point for event handling. It dispatches to
@@ -520,15 +1138,29 @@ pub async fn fetch_and_update_lyrics(
 /// # Event Types
 ///
 /// - `Event::Mpris`: Player state change (update, seek)
+/// - `Event::LyricsFile`: Manual lyrics dropped into the watched folder
+/// - `Event::LyricsUpgrade`: Word-synced lyrics found in the background
+/// - `Event::User`: Request from the UI (wrong-match report, provider pin,
+///   teleprompter speed)
 /// - `Event::Shutdown`: Graceful shutdown signal
 pub async fn process_event(
     event: Event,
     state: &mut StateBundle,
     update_tx: &mpsc::Sender<Update>,
-    providers: &[String],
+    config: &LoopConfig,
 ) {
     match event {
-        Event::Mpris(ev) => handle_mpris_event(ev, state, update_tx, providers).await,
+        Event::Mpris(ev) => handle_mpris_event(ev, state, update_tx, config).await,
+        Event::LyricsFile(path) => handle_lyrics_file(&path, state, update_tx, config).await,
+        Event::LyricsUpgrade(upgrade) => handle_lyrics_upgrade(*upgrade, state, update_tx).await,
+        Event::User(UserCommand::WrongMatch) => handle_wrong_match(state, update_tx, config).await,
+        Event::User(UserCommand::PinProvider) => handle_pin_provider(state, config).await,
+        Event::User(UserCommand::TeleprompterFaster) => {
+            handle_teleprompter_speed(state, update_tx, TELEPROMPTER_SPEED_STEP).await
+        }
+        Event::User(UserCommand::TeleprompterSlower) => {
+            handle_teleprompter_speed(state, update_tx, 1.0 / TELEPROMPTER_SPEED_STEP).await
+        }
         Event::Shutdown => send_update(state, update_tx, true).await,
     }
 }
@@ -552,7 +1184,7 @@ async fn handle_mpris_event(
     event: MprisEvent,
     state: &mut StateBundle,
     update_tx: &mpsc::Sender<Update>,
-    providers: &[String],
+    config: &LoopConfig,
 ) {
     let (meta, position, service, is_full_update) = match event {
         MprisEvent::PlayerUpdate(m, p, s) => (m, p, s, true),
@@ -565,6 +1197,16 @@ async fn handle_mpris_event(
         return;
     }
 
+    let position = position + player_offset(config, &service);
+    if state.service.as_deref() != Some(service.as_str()) {
+        state.service = Some(service.clone());
+    }
+
+    if !is_full_update && config.player_override(&service).is_some_and(|p| p.ignore_seeked) {
+        tracing::debug!(service = %service, "Ignoring Seeked event (ignore_seeked override)");
+        return;
+    }
+
     // Only fetch playback status for full updates (optimization)
     let playback_status = if is_full_update {
         get_playback_status(&service).await
@@ -587,12 +1229,34 @@ async fn handle_mpris_event(
             playback_status,
             state,
             update_tx,
-            providers,
+            config,
         })
         .await;
         return;
     }
 
+    // Gapless/crossfading players keep reporting the previous track's
+    // position for a moment after the metadata changed
+    let stale = state.transition.is_some_and(|t| t.is_recent() && t.is_stale(position));
+    if stale {
+        tracing::debug!(
+            position = %format!("{:.3}s", position),
+            "Ignoring position from the previous track"
+        );
+        if !is_full_update {
+            return;
+        }
+    }
+    // Radio streams: Position is stream time, unrelated to the song
+    if meta.is_stream && !is_full_update {
+        return;
+    }
+    let position = if stale || meta.is_stream {
+        state.player_state.estimate_position()
+    } else {
+        position
+    };
+
     // For seek events, ignore them within 2 seconds after lyrics load
     if !is_full_update {
         // After lyrics are loaded, we fetch a fresh position from D-Bus.
@@ -602,25 +1266,27 @@ async fn handle_mpris_event(
         if state.player_state.title == meta.title 
             && state.player_state.artist == meta.artist 
             && state.has_lyrics()
+            && let Some(loaded_at) = state.lyrics_loaded_at
         {
-            if let Some(loaded_at) = state.lyrics_loaded_at {
-                let elapsed = loaded_at.elapsed();
-                if elapsed.as_secs_f64() < 0.5 {
-                    tracing::debug!(
-                        seek_position = %format!("{:.3}s", position),
-                        current_position = %format!("{:.3}s", state.player_state.estimate_position()),
-                        time_since_load = %format!("{:.3}s", elapsed.as_secs_f64()),
-                        "Ignoring Seeked event within 2s of lyrics load"
-                    );
-                    return;
-                }
+            let elapsed = loaded_at.elapsed();
+            if elapsed.as_secs_f64() < 0.5 {
+                tracing::debug!(
+                    seek_position = %format!("{:.3}s", position),
+                    current_position = %format!("{:.3}s", state.player_state.estimate_position()),
+                    time_since_load = %format!("{:.3}s", elapsed.as_secs_f64()),
+                    "Ignoring Seeked event within 2s of lyrics load"
+                );
+                return;
             }
         }
         
-        // Legitimate seek event - update position immediately
+        // Legitimate seek event - update position immediately, but while
+        // scrubbing only render the burst's final position (see `flush_seeks`)
         state.player_state.set_position(position);
         state.update_index(position);
-        send_update(state, update_tx, true).await;
+        if state.seeks.record() {
+            send_update(state, update_tx, true).await;
+        }
         return;
     }
 
@@ -637,9 +1303,234 @@ async fn handle_mpris_event(
 async fn handle_no_player(state: &mut StateBundle, update_tx: &mpsc::Sender<Update>) {
     state.clear_lyrics();
     state.player_state = Default::default();
+    state.transition = None;
+    state.service = None;
     send_update(state, update_tx, true).await;
 }
 
+/// Loads an `.lrc` file dropped into the watched folder.
+///
+/// The file applies if it is named `current.lrc` or matches the playing
+/// track's `Artist - Title.lrc`. Matching lyrics replace the current ones
+/// immediately and are imported into the cache under the lookup metadata.
+async fn handle_lyrics_file(
+    path: &std::path::Path,
+    state: &mut StateBundle,
+    update_tx: &mpsc::Sender<Update>,
+    config: &LoopConfig,
+) {
+    let player = &state.player_state;
+    if player.title.is_empty() {
+        return;
+    }
+
+    let meta = TrackMetadata {
+        title: player.title.clone(),
+        artist: player.artist.clone(),
+        album: player.album.clone(),
+        length: player.length,
+        ..Default::default()
+    };
+    let query = config.rewrite_rules().apply(&meta);
+
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let current_files = crate::watch::current_file_names();
+    let track_files = crate::lyrics::providers::local::local_file_names(&query.artist, &query.title);
+    if !current_files.iter().chain(&track_files).any(|name| name.to_lowercase() == file_name) {
+        tracing::debug!(path = %path.display(), "Dropped lyrics file doesn't match current track");
+        return;
+    }
+
+    let raw = match tokio::fs::read_to_string(path).await {
+        Ok(raw) => raw,
+        Err(e) => {
+            // Renamed away or still being written; a later event will follow
+            tracing::debug!(path = %path.display(), error = %e, "Failed to read dropped lyrics file");
+            return;
+        }
+    };
+
+    let lines = crate::lyrics::parse::parse_lyrics_file(&raw);
+    if lines.is_empty() {
+        tracing::debug!(path = %path.display(), "Dropped lyrics file has no synced lines");
+        return;
+    }
+
+    tracing::info!(
+        path = %path.display(),
+        title = %meta.title,
+        artist = %meta.artist,
+        lines = lines.len(),
+        "Loaded lyrics from drop folder"
+    );
+
+    state.update_lyrics(lines, &meta, None, Some(Provider::LocalFile));
+    let position = state.player_state.estimate_position();
+    state.update_index(position);
+    send_update(state, update_tx, true).await;
+
+    store_lyrics_in_cache(
+        &query,
+        Some(raw),
+        None,
+        &Default::default(),
+        crate::lyrics::database::LyricsFormat::Local,
+    )
+    .await;
+}
+
+/// Rejects the provider of the current lyrics for this track and looks
+/// the track up again without it.
+///
+/// The rejection is stored in the database (with `--database`), so the
+/// correction sticks: the provider is skipped for the track from now on.
+/// On a track left without lyrics the key undoes its rejections instead.
+async fn handle_wrong_match(state: &mut StateBundle, update_tx: &mpsc::Sender<Update>, config: &LoopConfig) {
+    if state.player_state.title.is_empty() {
+        return;
+    }
+    let meta = current_metadata(state).await;
+    let query = config.rewrite_rules().apply(&meta);
+
+    if !state.has_lyrics() {
+        let (artist, title, album) = (&query.artist, &query.title, &query.album);
+        if !crate::lyrics::database::forget_rejections(&mut state.choices, artist, title, album).await {
+            return;
+        }
+        tracing::info!(title = %meta.title, artist = %meta.artist, "Rejected providers restored");
+    } else {
+        let Some(source) = state.provider.and_then(Provider::source) else {
+            tracing::warn!("These lyrics come from a local file; edit or remove the file instead");
+            return;
+        };
+        tracing::info!(title = %meta.title, artist = %meta.artist, provider = source, "Lyrics reported as a wrong match");
+        crate::lyrics::database::reject_provider(&mut state.choices, &query.artist, &query.title, &query.album, source).await;
+    }
+
+    fetch_api_lyrics(&meta, state, config).await;
+    let position = state.player_state.estimate_position();
+    state.update_index(position);
+    send_update(state, update_tx, true).await;
+}
+
+/// Pins the provider of the current lyrics for this track, or unpins it
+/// if it already was pinned.
+///
+/// Pins are stored in the database (with `--database`); `[[pin]]` rules
+/// from the config file apply to tracks without one. The result is shown
+/// in the status bar.
+async fn handle_pin_provider(state: &mut StateBundle, config: &LoopConfig) {
+    if state.player_state.title.is_empty() || !state.has_lyrics() {
+        return;
+    }
+    let Some(source) = state.provider.and_then(Provider::source) else {
+        tracing::warn!("These lyrics come from a local file, which always wins");
+        return;
+    };
+
+    let query = config.rewrite_rules().apply(&current_metadata(state).await);
+    let (artist, title, album) = (&query.artist, &query.title, &query.album);
+    let session = &mut state.choices;
+    let msg = if crate::lyrics::database::pinned_provider(session, artist, title, album).await.as_deref() == Some(source) {
+        tracing::info!(title = %title, artist = %artist, provider = source, "Provider unpinned");
+        crate::lyrics::database::pin_provider(session, artist, title, album, None).await;
+        crate::i18n::Msg::Unpinned
+    } else {
+        tracing::info!(title = %title, artist = %artist, provider = source, "Provider pinned");
+        crate::lyrics::database::pin_provider(session, artist, title, album, Some(source)).await;
+        crate::i18n::Msg::Pinned
+    };
+    crate::ui::status::show(format!("{}: {}", crate::i18n::tr(msg), source));
+}
+
+/// Speed change per key press in teleprompter mode.
+const TELEPROMPTER_SPEED_STEP: f64 = 1.25;
+
+/// Makes unsynced lyrics scroll `factor` times faster from here on.
+///
+/// Synced lyrics follow the player and are left alone.
+async fn handle_teleprompter_speed(state: &mut StateBundle, update_tx: &mpsc::Sender<Update>, factor: f64) {
+    if state.provider != Some(Provider::LrclibPlain) {
+        return;
+    }
+    tracing::debug!(factor, "Changing teleprompter speed");
+    state.retime_lyrics(factor);
+    send_update(state, update_tx, true).await;
+}
+
+/// Metadata of the track in `state`, re-read from the player for what the
+/// player state doesn't keep (Spotify ID).
+async fn current_metadata(state: &StateBundle) -> TrackMetadata {
+    let player = &state.player_state;
+    let fallback = TrackMetadata {
+        title: player.title.clone(),
+        artist: player.artist.clone(),
+        album: player.album.clone(),
+        length: player.length,
+        ..Default::default()
+    };
+    match state.service.as_deref() {
+        Some(service) => crate::mpris::metadata::get_metadata(service)
+            .await
+            .ok()
+            .filter(|m| m.title == fallback.title && m.artist == fallback.artist)
+            .unwrap_or(fallback),
+        None => fallback,
+    }
+}
+
+/// Swaps background-fetched lyrics in, keeping the position.
+///
+/// Dropped if the track changed meanwhile or the current lyrics were loaded
+/// from a local file. Word-synced lyrics are only replaced by a refresh
+/// that is word-synced too.
+async fn handle_lyrics_upgrade(
+    upgrade: LyricsUpgrade,
+    state: &mut StateBundle,
+    update_tx: &mpsc::Sender<Update>,
+) {
+    let LyricsUpgrade { meta, query, fetched, refresh } = upgrade;
+    let player = &state.player_state;
+    let same_track = player.title == meta.title && player.artist == meta.artist && player.album == meta.album;
+    let keep_current = state.provider.is_some_and(|p| {
+        p == Provider::LocalFile || (p.is_word_synced() && !(refresh && fetched.provider.is_word_synced()))
+    });
+    if refresh && !same_track {
+        // Nothing to swap on screen, but the cache still gets the new version
+        let format = provider_to_db_format(fetched.provider);
+        store_lyrics_in_cache(&query, fetched.raw, fetched.duration, &fetched.validators, format).await;
+        return;
+    }
+    if !same_track || keep_current {
+        tracing::debug!(title = %meta.title, "Discarding stale lyrics upgrade");
+        if refresh {
+            crate::lyrics::database::touch(&query.artist, &query.title, &query.album).await;
+        }
+        return;
+    }
+
+    tracing::info!(
+        title = %meta.title,
+        artist = %meta.artist,
+        provider = fetched.provider.name(),
+        "{}",
+        if refresh { "Replaced stale cached lyrics" } else { "Upgraded to word-synced lyrics" }
+    );
+
+    let format = provider_to_db_format(fetched.provider);
+    state.update_lyrics(fetched.lines, &meta, None, Some(fetched.provider));
+    state.set_provider_duration(fetched.duration);
+    let position = state.player_state.estimate_position();
+    state.update_index(position);
+    send_update(state, update_tx, true).await;
+
+    // Replaces the older cache entry
+    store_lyrics_in_cache(&query, fetched.raw, fetched.duration, &fetched.validators, format).await;
+}
+
 /// Handles detection of a new track.
 ///
 /// This function orchestrates the multi-step process of responding to a track change:
@@ -657,14 +1548,16 @@ async fn handle_no_player(state: &mut StateBundle, update_tx: &mpsc::Sender<Upda
 async fn handle_new_track(ctx: NewTrackContext<'_>) {
     let NewTrackContext {
         meta,
-        position: _event_position,  // Ignored - often stale from previous track
+        position: event_position,
         service,
         playback_status,
         state,
         update_tx,
-        providers,
+        config,
     } = ctx;
 
+    let transition = TrackTransition::new(&state.player_state);
+    let stale_position_quirk = config.quirks(&service).stale_track_change_position;
     state.clear_lyrics();
     
     // Update metadata immediately so first update has correct track info
@@ -672,8 +1565,14 @@ async fn handle_new_track(ctx: NewTrackContext<'_>) {
 
     // IMPORTANT: On track changes, the position from the MPRIS event is often stale
     // (still from the previous track). We'll fetch a fresh position after lyrics.
-    // Set position to 0 first to establish a clean anchor point.
-    state.player_state.set_position(0.0);
+    // Anchor at 0 first, or at the offset a crossfaded track already played.
+    let start = if stale_position_quirk || meta.is_stream {
+        0.0
+    } else {
+        transition.start_offset(event_position, meta.length)
+    };
+    state.player_state.set_position(start);
+    state.transition = Some(transition);
     
     if let Some(status) = playback_status {
         let playing = status == "Playing";
@@ -688,8 +1587,14 @@ async fn handle_new_track(ctx: NewTrackContext<'_>) {
 
     // Fetch lyrics synchronously and update state.
     // This will also fetch a FRESH position from D-Bus, avoiding the stale
-    // event position from the previous track.
-    let _ = fetch_and_update_lyrics(&meta, state, providers, Some(&service)).await;
+    // event position from the previous track. Players with the stale
+    // track-change position quirk keep estimating from zero instead.
+    let position_source = if stale_position_quirk {
+        None
+    } else {
+        Some(service.as_str())
+    };
+    let _ = fetch_and_update_lyrics(&meta, state, config, position_source).await;
     
     // After fetching, send another forced update to refresh UI with lyrics
     send_update(state, update_tx, true).await;
@@ -707,6 +1612,7 @@ async fn handle_new_track(ctx: NewTrackContext<'_>) {
 /// Updates are sent only if:
 /// - Playing state changed (play ↔ pause)
 /// - Active lyric line changed
+/// - Position moved backwards (rewind, A-B repeat), even within the same line
 async fn handle_state_update(
     position: f64,
     playback_status: Option<String>,
@@ -714,6 +1620,14 @@ async fn handle_state_update(
     update_tx: &mpsc::Sender<Update>,
 ) {
     let prev_playing = state.player_state.playing;
+    let rewound = state.player_state.is_backward_jump(position);
+    if rewound {
+        tracing::debug!(
+            position = %format!("{:.3}s", position),
+            estimated = %format!("{:.3}s", state.player_state.estimate_position()),
+            "Position moved backwards"
+        );
+    }
 
     // Update playback state
     if let Some(status) = playback_status {
@@ -727,10 +1641,77 @@ async fn handle_state_update(
     let current_position = state.player_state.estimate_position();
     let changed_index = state.update_index(current_position);
 
-    // Send update if meaningful change occurred
+    // Send update if meaningful change occurred. A rewind within the same
+    // line must be forced through so the UI re-anchors its word highlight.
     let playing_changed = prev_playing != state.player_state.playing;
-    if playing_changed || changed_index {
-        send_update(state, update_tx, false).await;
+    if playing_changed || changed_index || rewound {
+        send_update(state, update_tx, rewound).await;
+    }
+}
+
+/// Minimum delay between position re-reads, so a player that stalls just
+/// before a boundary isn't queried in a tight loop.
+const MIN_BOUNDARY_RECHECK: f64 = 0.25;
+
+/// Delay after a line start before its position is re-read.
+const BOUNDARY_GRACE: f64 = 0.05;
+
+/// Minimum interval between position re-reads over D-Bus at line boundaries;
+/// boundaries in between use the estimated position.
+const BOUNDARY_RESYNC_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Difference (in seconds) between the re-read and the estimated position
+/// above which the position is re-anchored.
+const BOUNDARY_DRIFT_THRESHOLD: f64 = 0.2;
+
+/// Returns the time until the next lyric line starts, if playing.
+///
+/// The event loop re-reads the real position at that point (see
+/// [`handle_line_boundary`]); UIs clamp their own extrapolation to the
+/// same boundary until the resulting update arrives.
+pub fn next_line_boundary(state: &StateBundle) -> Option<std::time::Duration> {
+    if !state.player_state.playing || state.service.is_none() {
+        return None;
+    }
+
+    let position = state.player_state.estimate_position();
+    let next = state
+        .lyric_state
+        .lines
+        .iter()
+        .map(|line| line.time)
+        .find(|&time| time.is_finite() && time > position)?;
+
+    let delay = (next - position + BOUNDARY_GRACE).max(MIN_BOUNDARY_RECHECK);
+    Some(std::time::Duration::from_secs_f64(delay))
+}
+
+/// Advances the active line at a line boundary and publishes it.
+///
+/// This reconciles the UIs' clamped extrapolation with real playback. At
+/// most every [`BOUNDARY_RESYNC_INTERVAL`] the position is re-read from the
+/// player, and re-anchored if it drifted, so a player that fell behind
+/// re-anchors the UI instead of letting it run ahead. An update is only
+/// sent when the line or the anchor changed.
+pub async fn handle_line_boundary(
+    state: &mut StateBundle,
+    update_tx: &mpsc::Sender<Update>,
+    config: &LoopConfig,
+) {
+    let mut position = state.player_state.estimate_position();
+    let mut resynced = false;
+    if state.boundary_synced_at.is_none_or(|at| at.elapsed() >= BOUNDARY_RESYNC_INTERVAL) {
+        state.boundary_synced_at = Some(std::time::Instant::now());
+        let fresh = fetch_fresh_position(state.service.as_deref(), state, config).await;
+        if (fresh - position).abs() > BOUNDARY_DRIFT_THRESHOLD {
+            state.player_state.set_position(fresh);
+            position = fresh;
+            resynced = true;
+        }
+    }
+
+    if state.update_index(position) || resynced {
+        send_update(state, update_tx, true).await;
     }
 }
 
@@ -742,4 +1723,59 @@ async fn get_playback_status(service: &str) -> Option<String> {
         .await
         .ok()
         .filter(|s| !s.is_empty())
-}
\ No newline at end of file
+}
+
+/// Returns the configured position offset (in seconds) for a player service.
+fn player_offset(config: &LoopConfig, service: &str) -> f64 {
+    config.player_override(service).map_or(0.0, |p| p.offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_names() {
+        let parsed: Vec<_> = PROVIDERS.iter().filter_map(|name| ProviderName::parse(name)).collect();
+        assert_eq!(parsed, [ProviderName::Lrclib, ProviderName::Musixmatch, ProviderName::Amll]);
+        assert_eq!(ProviderName::parse("exec:/bin/lyrics"), Some(ProviderName::Exec("/bin/lyrics")));
+        assert_eq!(ProviderName::parse("netease"), None);
+    }
+
+    #[test]
+    fn test_upgrade_candidates() {
+        let providers: Vec<String> = ["lrclib", "musixmatch", "exec:/bin/lyrics", "amll"]
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        assert_eq!(upgrade_candidates(&providers, None), ["musixmatch", "amll"]);
+        assert_eq!(upgrade_candidates(&providers, Some(0)), ["musixmatch", "amll"]);
+        // Musixmatch returned subtitles: its richsync was already tried
+        assert_eq!(upgrade_candidates(&providers, Some(1)), ["amll"]);
+        assert!(upgrade_candidates(&providers, Some(3)).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_redundant_updates_are_skipped_per_session() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let mut first = StateBundle::new();
+        let err = crate::state::LookupError { kind: crate::state::ErrorKind::Api, detail: "no lyrics".into() };
+        first.player_state.err = Some(err.clone());
+        let mut second = StateBundle::new();
+        second.player_state.err = Some(err);
+
+        send_update(&mut first, &tx, false).await;
+        send_update(&mut first, &tx, false).await;
+        // Another session in the same state still sends its own update
+        send_update(&mut second, &tx, false).await;
+        first.version += 1;
+        send_update(&mut first, &tx, false).await;
+        drop(tx);
+
+        let mut sent = 0;
+        while rx.recv().await.is_some() {
+            sent += 1;
+        }
+        assert_eq!(sent, 3);
+    }
+}
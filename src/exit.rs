@@ -0,0 +1,65 @@
+//! Process exit codes.
+//!
+//! Scripts wrapping the binary can branch on why it stopped:
+//!
+//! | Code | Meaning |
+//! |------|---------|
+//! | 0 | Success |
+//! | 1 | Any other error (missing `--database`, unreadable file, ...) |
+//! | 2 | Invalid command line |
+//! | 3 | No D-Bus session bus |
+//! | 4 | No active player (`--dump`, `compare`, `prefetch` without a playlist) |
+//! | 5 | No lyrics found, or the provider failed (`--dump`) |
+//! | 6 | Terminal error (the TUI couldn't set up or draw to the terminal) |
+
+use std::error::Error;
+
+/// Errors that end the process with their own exit code.
+#[derive(thiserror::Error, Debug)]
+pub enum Failure {
+    #[error("cannot connect to the D-Bus session bus")]
+    NoDbus,
+    #[error("{0}")]
+    NoPlayer(String),
+    #[error("{0}")]
+    NoLyrics(String),
+    #[error("terminal error: {0}")]
+    Terminal(#[source] std::io::Error),
+}
+
+impl Failure {
+    /// Exit code for this failure (see the module docs).
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::NoDbus => 3,
+            Self::NoPlayer(_) => 4,
+            Self::NoLyrics(_) => 5,
+            Self::Terminal(_) => 6,
+        }
+    }
+}
+
+/// Exit code for an error returned from `main`: the [`Failure`]'s, or 1.
+pub fn code(err: &(dyn Error + 'static)) -> u8 {
+    err.downcast_ref::<Failure>().map_or(1, Failure::code)
+}
+
+/// Fails with [`Failure::NoDbus`] unless the session bus is reachable.
+pub async fn require_dbus() -> Result<(), Failure> {
+    crate::mpris::connection::get_dbus_conn().await.map(drop).map_err(|_| Failure::NoDbus)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes() {
+        let boxed: Box<dyn Error + Send + Sync> = Failure::NoPlayer("no active player".into()).into();
+        assert_eq!(code(&*boxed), 4);
+        assert_eq!(boxed.to_string(), "no active player");
+
+        let other: Box<dyn Error + Send + Sync> = "stats requires --database PATH".into();
+        assert_eq!(code(&*other), 1);
+    }
+}
@@ -0,0 +1,166 @@
+//! User commands run on track and line changes.
+//!
+//! With `--on-track-change CMD` / `--on-line-change CMD`, the command is run
+//! through `sh -c` whenever a new track starts or a new lyric line becomes
+//! active, e.g. to switch OBS scenes or drive LED lights. The event is
+//! described in environment variables:
+//!
+//! | Variable | Value |
+//! |---|---|
+//! | `LYRIC_TEXT` | Active line (empty on track change) |
+//! | `ARTIST`, `TITLE`, `ALBUM` | Track metadata |
+//! | `POSITION` | Playback position in seconds |
+//!
+//! Hooks don't block the lyrics: they run in the background with stdin and
+//! stdout/stderr detached (so they can't garble the TUI or the pipe output).
+
+use crate::state::Update;
+use std::process::Stdio;
+use tokio::sync::mpsc;
+
+/// A change that triggers a hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Trigger {
+    TrackChange,
+    LineChange(usize),
+}
+
+/// Configured hook commands plus what they last fired for.
+pub struct Hooks {
+    on_track_change: Option<String>,
+    on_line_change: Option<String>,
+    last_track_id: Option<(String, String, String)>,
+    last_index: Option<usize>,
+}
+
+impl Hooks {
+    /// Returns `None` if no hook is configured.
+    pub fn from_config(config: &crate::Config) -> Option<Self> {
+        if config.on_track_change.is_none() && config.on_line_change.is_none() {
+            return None;
+        }
+        Some(Self {
+            on_track_change: config.on_track_change.clone(),
+            on_line_change: config.on_line_change.clone(),
+            last_track_id: None,
+            last_index: None,
+        })
+    }
+
+    /// Inserts the hooks between the event loop and `update_tx`: every
+    /// update is inspected, then forwarded unchanged.
+    pub fn tap(mut self, update_tx: mpsc::Sender<Update>) -> mpsc::Sender<Update> {
+        let (tx, mut rx) = mpsc::channel(32);
+        tokio::spawn(async move {
+            while let Some(upd) = rx.recv().await {
+                self.observe(&upd);
+                if update_tx.send(upd).await.is_err() {
+                    break;
+                }
+            }
+        });
+        tx
+    }
+
+    /// Runs the hooks triggered by `upd`.
+    fn observe(&mut self, upd: &Update) {
+        for trigger in self.triggers(upd) {
+            let command = match trigger {
+                Trigger::TrackChange => &self.on_track_change,
+                Trigger::LineChange(_) => &self.on_line_change,
+            };
+            if let Some(command) = command {
+                spawn(command, trigger, upd);
+            }
+        }
+    }
+
+    /// Works out which changes `upd` represents.
+    fn triggers(&mut self, upd: &Update) -> Vec<Trigger> {
+        let mut triggers = Vec::new();
+
+        let track_id = crate::ui::track_id(upd);
+        if self.last_track_id.as_ref() != Some(&track_id) {
+            self.last_index = None;
+            if !upd.title.is_empty() {
+                triggers.push(Trigger::TrackChange);
+            }
+            self.last_track_id = Some(track_id);
+        }
+
+        if upd.index != self.last_index {
+            self.last_index = upd.index;
+            if let Some(idx) = upd.index.filter(|&i| i < upd.lines.len()) {
+                triggers.push(Trigger::LineChange(idx));
+            }
+        }
+
+        triggers
+    }
+}
+
+/// Starts `command` for `trigger` without waiting for it.
+fn spawn(command: &str, trigger: Trigger, upd: &Update) {
+    let text = match trigger {
+        Trigger::TrackChange => "",
+        Trigger::LineChange(idx) => upd.lines[idx].text.as_str(),
+    };
+
+    let child = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("LYRIC_TEXT", text)
+        .env("ARTIST", &upd.artist)
+        .env("TITLE", &upd.title)
+        .env("ALBUM", &upd.album)
+        .env("POSITION", format!("{:.3}", upd.position))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+
+    match child {
+        Ok(mut child) => {
+            let command = command.to_string();
+            tokio::spawn(async move {
+                match child.wait().await {
+                    Ok(status) if !status.success() => {
+                        tracing::debug!(command = %command, status = %status, "Hook command failed");
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!(command = %command, error = %e, "Failed to wait for hook command"),
+                }
+            });
+        }
+        Err(e) => tracing::warn!(command = %command, error = %e, "Failed to run hook command"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lyrics::LyricLine;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_hook_triggers() {
+        let mut hooks = Hooks {
+            on_track_change: Some("true".into()),
+            on_line_change: Some("true".into()),
+            last_track_id: None,
+            last_index: None,
+        };
+        let lines = Arc::new(vec![LyricLine { text: "hello".into(), ..Default::default() }]);
+        let upd = Update { title: "Song".into(), lines, ..Default::default() };
+
+        assert_eq!(hooks.triggers(&upd), vec![Trigger::TrackChange]);
+        assert!(hooks.triggers(&upd).is_empty());
+
+        let active = Update { index: Some(0), ..upd.clone() };
+        assert_eq!(hooks.triggers(&active), vec![Trigger::LineChange(0)]);
+        assert!(hooks.triggers(&active).is_empty());
+
+        let next_track = Update { title: "Next".into(), index: Some(0), ..upd };
+        assert_eq!(hooks.triggers(&next_track), vec![Trigger::TrackChange, Trigger::LineChange(0)]);
+    }
+}
@@ -0,0 +1,270 @@
+//! Message catalog for user-facing strings.
+//!
+//! The language is picked once at startup ([`init`]) from `LC_ALL`,
+//! `LC_MESSAGES` or `LANG` (the first one set): `de_DE.UTF-8` selects
+//! German, `C`/`POSIX` and languages without a translation fall back to
+//! English, as does everything before [`init`] (tests). Log messages and
+//! machine-readable output stay in English.
+
+use crate::state::{ErrorKind, LookupError};
+use crate::ui::keymap::Action;
+use once_cell::sync::OnceCell;
+
+/// Languages with a translation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+    Es,
+    Fr,
+}
+
+/// User-facing messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    /// Badge shown while playback is paused
+    Paused,
+    /// Indicator shown after manual scrolling
+    Scrolled,
+    /// Badge shown when the lyrics may belong to another version of the track
+    LowConfidence,
+    /// Title of the help overlay
+    HelpTitle,
+    /// Shown until the first update arrives at startup
+    Loading,
+    NoLyrics,
+    NoPlayer,
+    /// Prefix of network failures
+    NetworkError,
+    /// Prefix of errors reported by a provider
+    ApiError,
+    /// Prefix of unreadable provider responses
+    ParseError,
+    /// Status after the pin key pinned a provider
+    Pinned,
+    /// Status after the pin key removed a pin
+    Unpinned,
+    /// Description of a key binding in the help overlay
+    Action(Action),
+}
+
+static LOCALE: OnceCell<Locale> = OnceCell::new();
+
+/// Selects the locale from the environment for the rest of the run.
+pub fn init() {
+    let var = |name| std::env::var(name).ok().filter(|v| !v.is_empty());
+    let value = var("LC_ALL").or_else(|| var("LC_MESSAGES")).or_else(|| var("LANG"));
+    let _ = LOCALE.set(Locale::from_env(value.as_deref()));
+}
+
+impl Locale {
+    /// The locale selected by [`init`], English before that.
+    pub fn current() -> Self {
+        LOCALE.get().copied().unwrap_or_default()
+    }
+
+    /// Parses a POSIX locale name (`fr_CA.UTF-8@euro`).
+    fn from_env(value: Option<&str>) -> Self {
+        let language = value
+            .unwrap_or("")
+            .split(['_', '.', '@'])
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        match language.as_str() {
+            "de" => Self::De,
+            "es" => Self::Es,
+            "fr" => Self::Fr,
+            _ => Self::En,
+        }
+    }
+}
+
+/// `msg` in the current locale.
+pub fn tr(msg: Msg) -> &'static str {
+    translate(Locale::current(), msg)
+}
+
+/// Localized text of a lyrics lookup error; the detail (from the network
+/// stack or the provider) is passed through untranslated.
+pub fn lyrics_error(err: &LookupError) -> String {
+    let prefix = match err.kind {
+        ErrorKind::Network => Msg::NetworkError,
+        ErrorKind::Api => Msg::ApiError,
+        ErrorKind::Parse => Msg::ParseError,
+    };
+    format!("{}: {}", tr(prefix), err.detail)
+}
+
+fn translate(locale: Locale, msg: Msg) -> &'static str {
+    match locale {
+        Locale::En => english(msg),
+        Locale::De => german(msg),
+        Locale::Es => spanish(msg),
+        Locale::Fr => french(msg),
+    }
+}
+
+fn english(msg: Msg) -> &'static str {
+    match msg {
+        Msg::Paused => "paused",
+        Msg::Scrolled => "scrolled (Esc to follow)",
+        Msg::LowConfidence => "lyrics may not match",
+        Msg::HelpTitle => "Keys",
+        Msg::Loading => "loading",
+        Msg::NoLyrics => "no lyrics found",
+        Msg::NoPlayer => "no active player",
+        Msg::NetworkError => "Network error",
+        Msg::ApiError => "API error",
+        Msg::ParseError => "Invalid response",
+        Msg::Pinned => "Pinned for this track",
+        Msg::Unpinned => "Unpinned for this track",
+        Msg::Action(action) => match action {
+            Action::Quit => "Quit",
+            Action::ToggleKaraoke => "Toggle karaoke highlighting",
+            Action::CycleTranslation => "Cycle original / translation / both",
+            Action::ScrollUp => "Scroll up one line (paused)",
+            Action::ScrollDown => "Scroll down one line (paused)",
+            Action::PageUp => "Scroll up one page (paused)",
+            Action::PageDown => "Scroll down one page (paused)",
+            Action::ScrollTop => "Jump to the first line (paused)",
+            Action::ScrollBottom => "Jump to the last line (paused)",
+            Action::Follow => "Follow the live line again",
+            Action::WrongMatch => "Wrong lyrics: try the next provider, remember for this track",
+            Action::PinProvider => "Always use this provider for this track (again: unpin)",
+            Action::TeleprompterFaster => "Scroll unsynced lyrics faster",
+            Action::TeleprompterSlower => "Scroll unsynced lyrics slower",
+            Action::Help => "Show / hide this help",
+        },
+    }
+}
+
+fn german(msg: Msg) -> &'static str {
+    match msg {
+        Msg::Paused => "pausiert",
+        Msg::Scrolled => "gescrollt (Esc zum Folgen)",
+        Msg::LowConfidence => "Songtext passt evtl. nicht",
+        Msg::HelpTitle => "Tasten",
+        Msg::Loading => "wird geladen",
+        Msg::NoLyrics => "kein Songtext gefunden",
+        Msg::NoPlayer => "kein aktiver Player",
+        Msg::NetworkError => "Netzwerkfehler",
+        Msg::ApiError => "API-Fehler",
+        Msg::ParseError => "Ungültige Antwort",
+        Msg::Pinned => "Für diesen Titel festgelegt",
+        Msg::Unpinned => "Für diesen Titel gelöst",
+        Msg::Action(action) => match action {
+            Action::Quit => "Beenden",
+            Action::ToggleKaraoke => "Karaoke-Hervorhebung umschalten",
+            Action::CycleTranslation => "Original / Übersetzung / beides",
+            Action::ScrollUp => "Eine Zeile hoch (pausiert)",
+            Action::ScrollDown => "Eine Zeile runter (pausiert)",
+            Action::PageUp => "Eine Seite hoch (pausiert)",
+            Action::PageDown => "Eine Seite runter (pausiert)",
+            Action::ScrollTop => "Zur ersten Zeile (pausiert)",
+            Action::ScrollBottom => "Zur letzten Zeile (pausiert)",
+            Action::Follow => "Wieder der aktuellen Zeile folgen",
+            Action::WrongMatch => "Falscher Songtext: nächsten Anbieter versuchen, für diesen Titel merken",
+            Action::PinProvider => "Diesen Anbieter für diesen Titel festlegen (erneut: lösen)",
+            Action::TeleprompterFaster => "Ungesyncten Songtext schneller scrollen",
+            Action::TeleprompterSlower => "Ungesyncten Songtext langsamer scrollen",
+            Action::Help => "Diese Hilfe ein-/ausblenden",
+        },
+    }
+}
+
+fn spanish(msg: Msg) -> &'static str {
+    match msg {
+        Msg::Paused => "en pausa",
+        Msg::Scrolled => "desplazado (Esc para seguir)",
+        Msg::LowConfidence => "puede que la letra no coincida",
+        Msg::HelpTitle => "Teclas",
+        Msg::Loading => "cargando",
+        Msg::NoLyrics => "no se encontró la letra",
+        Msg::NoPlayer => "ningún reproductor activo",
+        Msg::NetworkError => "Error de red",
+        Msg::ApiError => "Error de la API",
+        Msg::ParseError => "Respuesta no válida",
+        Msg::Pinned => "Fijado para esta pista",
+        Msg::Unpinned => "Soltado para esta pista",
+        Msg::Action(action) => match action {
+            Action::Quit => "Salir",
+            Action::ToggleKaraoke => "Activar/desactivar el resaltado karaoke",
+            Action::CycleTranslation => "Original / traducción / ambos",
+            Action::ScrollUp => "Subir una línea (en pausa)",
+            Action::ScrollDown => "Bajar una línea (en pausa)",
+            Action::PageUp => "Subir una página (en pausa)",
+            Action::PageDown => "Bajar una página (en pausa)",
+            Action::ScrollTop => "Ir a la primera línea (en pausa)",
+            Action::ScrollBottom => "Ir a la última línea (en pausa)",
+            Action::Follow => "Volver a seguir la línea actual",
+            Action::WrongMatch => "Letra incorrecta: probar el siguiente proveedor y recordarlo",
+            Action::PinProvider => "Usar siempre este proveedor para esta pista (otra vez: soltar)",
+            Action::TeleprompterFaster => "Desplazar más rápido la letra sin sincronizar",
+            Action::TeleprompterSlower => "Desplazar más despacio la letra sin sincronizar",
+            Action::Help => "Mostrar / ocultar esta ayuda",
+        },
+    }
+}
+
+fn french(msg: Msg) -> &'static str {
+    match msg {
+        Msg::Paused => "en pause",
+        Msg::Scrolled => "défilé (Échap pour suivre)",
+        Msg::LowConfidence => "paroles peut-être erronées",
+        Msg::HelpTitle => "Touches",
+        Msg::Loading => "chargement",
+        Msg::NoLyrics => "aucunes paroles trouvées",
+        Msg::NoPlayer => "aucun lecteur actif",
+        Msg::NetworkError => "Erreur réseau",
+        Msg::ApiError => "Erreur de l'API",
+        Msg::ParseError => "Réponse invalide",
+        Msg::Pinned => "Épinglé pour ce titre",
+        Msg::Unpinned => "Désépinglé pour ce titre",
+        Msg::Action(action) => match action {
+            Action::Quit => "Quitter",
+            Action::ToggleKaraoke => "Activer/désactiver le surlignage karaoké",
+            Action::CycleTranslation => "Original / traduction / les deux",
+            Action::ScrollUp => "Monter d'une ligne (en pause)",
+            Action::ScrollDown => "Descendre d'une ligne (en pause)",
+            Action::PageUp => "Monter d'une page (en pause)",
+            Action::PageDown => "Descendre d'une page (en pause)",
+            Action::ScrollTop => "Aller à la première ligne (en pause)",
+            Action::ScrollBottom => "Aller à la dernière ligne (en pause)",
+            Action::Follow => "Suivre à nouveau la ligne en cours",
+            Action::WrongMatch => "Mauvaises paroles : essayer le fournisseur suivant et s'en souvenir",
+            Action::PinProvider => "Toujours utiliser ce fournisseur pour ce titre (encore : annuler)",
+            Action::TeleprompterFaster => "Faire défiler plus vite les paroles non synchronisées",
+            Action::TeleprompterSlower => "Faire défiler plus lentement les paroles non synchronisées",
+            Action::Help => "Afficher / masquer cette aide",
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_selection() {
+        assert_eq!(Locale::from_env(Some("de_DE.UTF-8")), Locale::De);
+        assert_eq!(Locale::from_env(Some("fr_CA.UTF-8@euro")), Locale::Fr);
+        assert_eq!(Locale::from_env(Some("es")), Locale::Es);
+        assert_eq!(Locale::from_env(Some("C")), Locale::En);
+        assert_eq!(Locale::from_env(Some("ja_JP.UTF-8")), Locale::En);
+        assert_eq!(Locale::from_env(None), Locale::En);
+
+        assert_eq!(translate(Locale::De, Msg::Paused), "pausiert");
+        assert_eq!(translate(Locale::Fr, Msg::Action(Action::Quit)), "Quitter");
+    }
+
+    #[test]
+    fn test_lyrics_error() {
+        // Tests never call `init`
+        assert_eq!(Locale::current(), Locale::En);
+        let err = LookupError { kind: ErrorKind::Network, detail: "timed out".into() };
+        assert_eq!(lyrics_error(&err), "Network error: timed out");
+        assert_eq!(err.kind.code(), "network");
+    }
+}
@@ -0,0 +1,174 @@
+//! Side-by-side comparison of lyrics from several providers (`compare`).
+//!
+//! The first provider with lyrics is the reference. Every other provider's
+//! lines are aligned to it by the closest start time; the table shows the
+//! timing offset of each match and flags lines whose text differs.
+
+use crate::lyrics::export::format_lrc_time;
+use crate::lyrics::types::LyricLine;
+use crate::text_utils::display_width;
+
+/// Lines further apart than this (in seconds) are not considered the same line.
+const MATCH_WINDOW: f64 = 3.0;
+
+/// Lyrics one provider returned.
+pub struct Source {
+    /// Provider name as configured (`lrclib`, `exec:/path`, ...)
+    pub name: String,
+    pub lines: Vec<LyricLine>,
+}
+
+/// Renders the comparison table, at most `width` columns wide.
+///
+/// Returns an empty string if no source has any lines.
+pub fn render(sources: &[Source], width: usize) -> String {
+    let sources: Vec<&Source> = sources.iter().filter(|s| !s.lines.is_empty()).collect();
+    let Some((reference, others)) = sources.split_first() else {
+        return String::new();
+    };
+
+    let time_width = 8;
+    let columns = 1 + others.len();
+    let column_width = (width.saturating_sub(time_width + 3 * columns) / columns).max(12);
+    let mut out = String::new();
+
+    out.push_str(&format!("{:<time_width$}", "time"));
+    for source in &sources {
+        out.push_str(" │ ");
+        out.push_str(&pad(&source.name, column_width));
+    }
+    out.push('\n');
+
+    let matches: Vec<Vec<Option<usize>>> = others.iter().map(|o| align(&reference.lines, &o.lines)).collect();
+    for (i, line) in reference.lines.iter().enumerate() {
+        out.push_str(&format_lrc_time(line.time));
+        out.push_str(" │ ");
+        out.push_str(&pad(&line.text, column_width));
+        for (other, matched) in others.iter().zip(&matches) {
+            out.push_str(" │ ");
+            let cell = match matched[i] {
+                Some(j) => {
+                    let candidate = &other.lines[j];
+                    let marker = if normalize(&candidate.text) == normalize(&line.text) { ' ' } else { '≠' };
+                    format!("{:+.2} {} {}", candidate.time - line.time, marker, candidate.text)
+                }
+                None => "—".to_string(),
+            };
+            out.push_str(&pad(&cell, column_width));
+        }
+        out.push('\n');
+    }
+
+    out.push('\n');
+    for (other, matched) in others.iter().zip(&matches) {
+        out.push_str(&summary(reference, other, matched));
+        out.push('\n');
+    }
+    out
+}
+
+/// For each reference line, the index of the closest line in `other`
+/// within [`MATCH_WINDOW`].
+fn align(reference: &[LyricLine], other: &[LyricLine]) -> Vec<Option<usize>> {
+    reference
+        .iter()
+        .map(|line| {
+            other
+                .iter()
+                .enumerate()
+                .map(|(j, candidate)| (j, (candidate.time - line.time).abs()))
+                .filter(|&(_, delta)| delta <= MATCH_WINDOW)
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(j, _)| j)
+        })
+        .collect()
+}
+
+/// One-line verdict for `other` against the reference.
+fn summary(reference: &Source, other: &Source, matched: &[Option<usize>]) -> String {
+    let pairs: Vec<(&LyricLine, &LyricLine)> = reference
+        .lines
+        .iter()
+        .zip(matched)
+        .filter_map(|(line, j)| Some((line, &other.lines[(*j)?])))
+        .collect();
+    let differing = pairs.iter().filter(|(a, b)| normalize(&a.text) != normalize(&b.text)).count();
+    let mean_offset = if pairs.is_empty() {
+        0.0
+    } else {
+        pairs.iter().map(|(a, b)| b.time - a.time).sum::<f64>() / pairs.len() as f64
+    };
+    let used: std::collections::HashSet<usize> = matched.iter().flatten().copied().collect();
+    format!(
+        "{}: {}/{} lines matched, {} with different text, mean offset {:+.2}s, {} unmatched lines",
+        other.name,
+        pairs.len(),
+        reference.lines.len(),
+        differing,
+        mean_offset,
+        other.lines.len() - used.len(),
+    )
+}
+
+/// Text reduced to lowercase letters and digits, for comparisons that
+/// ignore punctuation and spacing.
+fn normalize(text: &str) -> String {
+    text.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// `text` truncated or padded to exactly `width` columns.
+fn pad(text: &str, width: usize) -> String {
+    let text = text.replace('\n', " ");
+    let mut out = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = display_width(c.encode_utf8(&mut [0; 4]));
+        if used + w > width {
+            if let Some(last) = out.pop() {
+                used -= display_width(last.encode_utf8(&mut [0; 4]));
+            }
+            out.push('…');
+            used += 1;
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    out.push_str(&" ".repeat(width.saturating_sub(used)));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(time: f64, text: &str) -> LyricLine {
+        LyricLine { time, text: text.into(), ..Default::default() }
+    }
+
+    #[test]
+    fn test_compare_render() {
+        let sources = [
+            Source { name: "lrclib".into(), lines: vec![line(1.0, "Hello there"), line(5.0, "Second line"), line(20.0, "Outro")] },
+            Source { name: "amll".into(), lines: vec![] },
+            Source { name: "musixmatch".into(), lines: vec![line(1.5, "hello, there!"), line(5.0, "Second time"), line(9.0, "Extra")] },
+        ];
+        let table = render(&sources, 80);
+        let rows: Vec<&str> = table.lines().collect();
+
+        assert_eq!(
+            rows[..4],
+            [
+                "time     │ lrclib                            │ musixmatch                       ",
+                "00:01.00 │ Hello there                       │ +0.50   hello, there!            ",
+                "00:05.00 │ Second line                       │ +0.00 ≠ Second time              ",
+                "00:20.00 │ Outro                             │ —                                ",
+            ]
+        );
+        assert!(table.contains("musixmatch: 2/3 lines matched, 1 with different text, mean offset +0.25s, 1 unmatched lines"));
+
+        assert_eq!(pad("abcdef", 4), "abc…");
+        assert_eq!(display_width(&pad("日本語", 5)), 5);
+        assert!(render(&sources[1..2], 80).is_empty());
+    }
+}
@@ -0,0 +1,90 @@
+//! Heuristic confidence that the lyrics belong to the playing track and
+//! are in sync with it.
+//!
+//! Providers match by artist/title, so a live version, a radio edit or a
+//! different song with the same name can slip through. The signs checked
+//! here are cheap: what kind of provider supplied the lyrics, how well the
+//! provider's track length agrees with the player's, and whether the lyrics
+//! fit into the track at all.
+
+use crate::lyrics::types::LyricLine;
+use crate::state::Provider;
+
+/// Confidence below which the UI flags the lyrics as possibly wrong.
+pub const LOW_CONFIDENCE: f64 = 0.5;
+
+/// What is known about how the lyrics were matched.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Evidence {
+    pub provider: Option<Provider>,
+    /// Track length reported by the player, in seconds
+    pub track_length: Option<f64>,
+    /// Track length of the provider's match, in seconds
+    pub provider_duration: Option<f64>,
+}
+
+/// Confidence in `0.0..=1.0` that `lines` match the track, or `None`
+/// without lyrics.
+pub fn estimate(lines: &[LyricLine], evidence: &Evidence) -> Option<f64> {
+    let last_line = lines.iter().map(|line| line.time).reduce(f64::max)?;
+
+    // Lyrics the user picked (a local file, their own script) are taken as right
+    let mut confidence = match evidence.provider {
+        Some(Provider::LocalFile | Provider::Exec) => return Some(1.0),
+        Some(p) if p.is_word_synced() => 0.9,
+        Some(Provider::MusixmatchSubtitles) => 0.8,
+        Some(_) => 0.75,
+        None => 0.7,
+    };
+
+    let track_length = evidence.track_length.filter(|&len| len > 0.0);
+    if let (Some(track), Some(matched)) = (track_length, evidence.provider_duration) {
+        // A few seconds either way is normal (fades, silence, rounding)
+        let mismatch = ((track - matched).abs() - 3.0).max(0.0) / track;
+        confidence *= (1.0 - mismatch * 5.0).clamp(0.2, 1.0);
+    }
+    if let Some(track) = track_length {
+        if last_line > track + 5.0 {
+            // Lyrics that run past the end are for a longer version
+            confidence *= 0.4;
+        } else if lines.len() > 4 && last_line < track * 0.4 {
+            // All lyrics crammed into the start: likely an edit or another song
+            confidence *= 0.7;
+        }
+    }
+    Some(confidence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(times: &[f64]) -> Vec<LyricLine> {
+        times.iter().map(|&time| LyricLine { time, ..Default::default() }).collect()
+    }
+
+    #[test]
+    fn test_confidence() {
+        let song = lines(&[10.0, 60.0, 120.0, 170.0, 200.0]);
+        let evidence = |provider, track_length, provider_duration| Evidence {
+            provider: Some(provider),
+            track_length,
+            provider_duration,
+        };
+
+        assert_eq!(estimate(&[], &Evidence::default()), None);
+        assert_eq!(estimate(&song, &evidence(Provider::LocalFile, Some(30.0), None)), Some(1.0));
+
+        let good = estimate(&song, &evidence(Provider::LRCLIB, Some(210.0), Some(212.0))).unwrap();
+        assert!(good > LOW_CONFIDENCE);
+        let word = estimate(&song, &evidence(Provider::AmllTtml, Some(210.0), None)).unwrap();
+        assert!(word > good);
+
+        // Radio edit of a much longer album version
+        let edit = estimate(&song, &evidence(Provider::LRCLIB, Some(150.0), Some(260.0))).unwrap();
+        assert!(edit < LOW_CONFIDENCE);
+        // Lyrics run past the end of the track
+        let longer = estimate(&song, &evidence(Provider::MusixmatchSubtitles, Some(180.0), None)).unwrap();
+        assert!(longer < LOW_CONFIDENCE);
+    }
+}
@@ -10,6 +10,11 @@
 //! - **LRC format** (from LRCLIB): Stored as raw text with `[MM:SS.CC]` timestamps
 //! - **Richsync** (from Musixmatch): Stored as unparsed JSON (word-level timing)
 //! - **Subtitles** (from Musixmatch): Stored as unparsed JSON (line-level timing)
+//! - **TTML** (from AMLL): Stored as the original XML document (word-level timing)
+//! - **YRC** (NetEase): Stored as raw text with `[start,duration](start,duration,0)word` timing
+//! - **Exec** / **Local** (user scripts, local and dropped files): LRC like
+//!   LRCLIB's (or YRC for `.yrc` files), but kept as their own formats so they
+//!   don't replace LRCLIB's version
 //!
 //! # Memory Usage
 //!
@@ -20,17 +25,81 @@
 //!
 //! # Schema
 //!
+//! A track can have one version per format (e.g. LRCLIB and Musixmatch
+//! richsync); lookups use the `preferred` one, which is the last stored
+//! unless a pin or rejection picked another.
+//!
 //! ```sql
 //! CREATE TABLE lyrics (
 //!     id INTEGER PRIMARY KEY,
 //!     artist TEXT NOT NULL,
 //!     title TEXT NOT NULL,
 //!     album TEXT NOT NULL,
-//!     duration REAL,
+//!     duration REAL,                   -- as reported by the player
 //!     format TEXT NOT NULL,
-//!     raw_lyrics TEXT NOT NULL
+//!     raw_lyrics TEXT NOT NULL,
+//!     provider_duration REAL,           -- as reported by the provider, preferred
+//!     etag TEXT,                        -- HTTP validators of the provider response,
+//!     last_modified TEXT,               -- for conditional refetches
+//!     stored_at INTEGER,                -- Unix time of the last fetch, for `--cache-max-age`
+//!     upgrade_failed_at INTEGER,        -- Unix time of the last fruitless word-synced search
+//!     preferred INTEGER NOT NULL DEFAULT 1  -- 1 for the version lookups return
 //! );
 //! CREATE INDEX idx_lookup ON lyrics(artist, title, album);
+//!
+//! -- MusicBrainz resolutions for `--musicbrainz`, keyed by player metadata
+//! CREATE TABLE musicbrainz (
+//!     artist TEXT NOT NULL,
+//!     title TEXT NOT NULL,
+//!     album TEXT NOT NULL,
+//!     recording_mbid TEXT NOT NULL,
+//!     artist_mbid TEXT,
+//!     canonical_artist TEXT NOT NULL,
+//!     canonical_title TEXT NOT NULL,
+//!     length REAL,
+//!     PRIMARY KEY (artist, title, album)
+//! );
+//!
+//! -- Musixmatch track IDs resolved via `track.search`, keyed like `musicbrainz`
+//! CREATE TABLE musixmatch_tracks (
+//!     artist TEXT NOT NULL,
+//!     title TEXT NOT NULL,
+//!     album TEXT NOT NULL,
+//!     commontrack_id INTEGER NOT NULL,
+//!     track_length INTEGER,
+//!     PRIMARY KEY (artist, title, album)
+//! );
+//!
+//! -- Providers the user marked as having the wrong lyrics for a track
+//! CREATE TABLE rejected (
+//!     artist TEXT NOT NULL,
+//!     title TEXT NOT NULL,
+//!     album TEXT NOT NULL,
+//!     provider TEXT NOT NULL,           -- name as in `--providers` (`exec:` for all scripts)
+//!     PRIMARY KEY (artist, title, album, provider)
+//! );
+//!
+//! -- Provider the user pinned for a track (asked first, sole source of cache hits)
+//! CREATE TABLE pinned (
+//!     artist TEXT NOT NULL,
+//!     title TEXT NOT NULL,
+//!     album TEXT NOT NULL,
+//!     provider TEXT NOT NULL,
+//!     PRIMARY KEY (artist, title, album)
+//! );
+//!
+//! -- One row per lookup (cache or provider), summarized by `lyricsmpris stats`;
+//! -- rows older than `EVENT_RETENTION` are deleted at startup
+//! CREATE TABLE events (
+//!     id INTEGER PRIMARY KEY,
+//!     at INTEGER NOT NULL,              -- Unix time in seconds
+//!     artist TEXT NOT NULL,
+//!     title TEXT NOT NULL,
+//!     album TEXT NOT NULL,
+//!     provider TEXT NOT NULL,           -- provider name, or "cache"
+//!     outcome TEXT NOT NULL,            -- found | missed | failed
+//!     latency_ms INTEGER NOT NULL
+//! );
 //! ```
 //!
 //! # Architecture
@@ -58,11 +127,13 @@
 //! └─────────────────┘
 //! ```
 
-use crate::lyrics::parse::{parse_richsync_body, parse_subtitle_body, parse_synced_lyrics};
-use crate::lyrics::types::{LyricsError, ProviderResult};
+use crate::lyrics::parse::{parse_lyrics_file, parse_richsync_body, parse_subtitle_body, parse_synced_lyrics, parse_ttml, parse_yrc};
+use crate::lyrics::types::{HttpValidators, LyricLine, LyricsError, ProviderResult};
+use crate::state::Provider;
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use serde::Deserialize;
 use sqlx::Row;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 // ============================================================================
@@ -78,14 +149,58 @@ pub enum LyricsFormat {
     Richsync,
     /// Musixmatch subtitle format with line-level timestamps (JSON)
     Subtitles,
+    /// TTML document (from AMLL), usually with word-level timestamps
+    Ttml,
+    /// NetEase YRC format with word-level timestamps (text)
+    Yrc,
+    /// LRC printed by a user script (`--providers exec:PATH`)
+    Exec,
+    /// Local or `--drop-dir` lyrics file, LRC or YRC
+    Local,
 }
 
 impl LyricsFormat {
+    const ALL: [Self; 7] = [Self::Lrclib, Self::Richsync, Self::Subtitles, Self::Ttml, Self::Yrc, Self::Exec, Self::Local];
+
+    /// Returns `true` for formats carrying per-word timings.
+    fn is_word_level(&self) -> bool {
+        matches!(self, Self::Richsync | Self::Ttml | Self::Yrc)
+    }
+
     fn to_str(&self) -> &'static str {
         match self {
             Self::Lrclib => "lrclib",
             Self::Richsync => "richsync",
             Self::Subtitles => "subtitles",
+            Self::Ttml => "ttml",
+            Self::Yrc => "yrc",
+            Self::Exec => "exec",
+            Self::Local => "local",
+        }
+    }
+
+    /// Provider of lyrics stored in this format.
+    pub fn provider(&self) -> Provider {
+        match self {
+            Self::Lrclib => Provider::LRCLIB,
+            Self::Richsync => Provider::MusixmatchRichsync,
+            Self::Subtitles => Provider::MusixmatchSubtitles,
+            Self::Ttml => Provider::AmllTtml,
+            Self::Yrc => Provider::NeteaseYrc,
+            Self::Exec => Provider::Exec,
+            Self::Local => Provider::LocalFile,
+        }
+    }
+
+    /// Formats stored for lyrics from the `--providers` entry `name`.
+    fn for_provider(name: &str) -> &'static [Self] {
+        use crate::event::ProviderName;
+        match ProviderName::parse(name) {
+            Some(ProviderName::Lrclib) => &[Self::Lrclib],
+            Some(ProviderName::Musixmatch) => &[Self::Richsync, Self::Subtitles],
+            Some(ProviderName::Amll) => &[Self::Ttml],
+            Some(ProviderName::Exec(_)) => &[Self::Exec],
+            None => &[],
         }
     }
 
@@ -94,14 +209,30 @@ impl LyricsFormat {
             "lrclib" => Some(Self::Lrclib),
             "richsync" => Some(Self::Richsync),
             "subtitles" => Some(Self::Subtitles),
+            "ttml" => Some(Self::Ttml),
+            "yrc" => Some(Self::Yrc),
+            "exec" => Some(Self::Exec),
+            "local" => Some(Self::Local),
             _ => None,
         }
     }
 }
 
+/// Lyrics found in the cache.
+#[derive(Debug)]
+pub struct CachedLyrics {
+    pub lines: Vec<LyricLine>,
+    pub raw: String,
+    /// Format of the entry, which tells its provider
+    pub format: LyricsFormat,
+    /// Track length the provider matched, if it reported one
+    pub provider_duration: Option<f64>,
+}
+
 /// Database entry for a single track's lyrics (from SQL query).
 #[derive(Debug, Clone)]
 pub struct LyricsEntry {
+    /// Provider-reported duration, or the player's when the provider had none
     pub duration: Option<f64>,
     pub format: LyricsFormat,
     pub raw_lyrics: String,
@@ -112,8 +243,15 @@ pub struct LyricsEntry {
 // ============================================================================
 
 /// Normalizes a string for case-insensitive matching.
+///
+/// Trims, lowercases and collapses internal whitespace runs.
 fn normalize(s: &str) -> String {
-    s.trim().to_lowercase()
+    s.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Artist, title and album as stored in the cache.
+pub fn cache_key(artist: &str, title: &str, album: &str) -> (String, String, String) {
+    (normalize(artist), normalize(title), normalize(album))
 }
 
 // ============================================================================
@@ -131,13 +269,40 @@ async fn create_schema(pool: &SqlitePool) -> Result<(), sqlx::Error> {
             album TEXT NOT NULL,
             duration REAL,
             format TEXT NOT NULL,
-            raw_lyrics TEXT NOT NULL
+            raw_lyrics TEXT NOT NULL,
+            provider_duration REAL,
+            etag TEXT,
+            last_modified TEXT,
+            stored_at INTEGER,
+            upgrade_failed_at INTEGER,
+            preferred INTEGER NOT NULL DEFAULT 1
         )
         "#,
     )
     .execute(pool)
     .await?;
 
+    // Added after the first release; older databases lack these columns
+    for (column, kind) in [
+        ("provider_duration", "REAL"),
+        ("etag", "TEXT"),
+        ("last_modified", "TEXT"),
+        ("stored_at", "INTEGER"),
+        ("upgrade_failed_at", "INTEGER"),
+        ("preferred", "INTEGER NOT NULL DEFAULT 1"),
+    ] {
+        let exists = sqlx::query("SELECT 1 FROM pragma_table_info('lyrics') WHERE name = ?")
+            .bind(column)
+            .fetch_optional(pool)
+            .await?
+            .is_some();
+        if !exists {
+            sqlx::query(&format!("ALTER TABLE lyrics ADD COLUMN {} {}", column, kind))
+                .execute(pool)
+                .await?;
+        }
+    }
+
     // Create index for fast lookups by artist/title/album
     sqlx::query(
         r#"
@@ -148,11 +313,131 @@ async fn create_schema(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS musicbrainz (
+            artist TEXT NOT NULL,
+            title TEXT NOT NULL,
+            album TEXT NOT NULL,
+            recording_mbid TEXT NOT NULL,
+            artist_mbid TEXT,
+            canonical_artist TEXT NOT NULL,
+            canonical_title TEXT NOT NULL,
+            length REAL,
+            PRIMARY KEY (artist, title, album)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS musixmatch_tracks (
+            artist TEXT NOT NULL,
+            title TEXT NOT NULL,
+            album TEXT NOT NULL,
+            commontrack_id INTEGER NOT NULL,
+            track_length INTEGER,
+            PRIMARY KEY (artist, title, album)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS rejected (
+            artist TEXT NOT NULL,
+            title TEXT NOT NULL,
+            album TEXT NOT NULL,
+            provider TEXT NOT NULL,
+            PRIMARY KEY (artist, title, album, provider)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS pinned (
+            artist TEXT NOT NULL,
+            title TEXT NOT NULL,
+            album TEXT NOT NULL,
+            provider TEXT NOT NULL,
+            PRIMARY KEY (artist, title, album)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS events (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            at INTEGER NOT NULL,
+            artist TEXT NOT NULL,
+            title TEXT NOT NULL,
+            album TEXT NOT NULL,
+            provider TEXT NOT NULL,
+            outcome TEXT NOT NULL,
+            latency_ms INTEGER NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Version 1 collapses whitespace runs in keys; rewrite those stored before
+    let version: i64 = sqlx::query_scalar("PRAGMA user_version").fetch_one(pool).await?;
+    if version < 1 {
+        renormalize_keys(pool).await?;
+        sqlx::query("PRAGMA user_version = 1").execute(pool).await?;
+    }
+
+    Ok(())
+}
+
+/// Rewrites keys stored with an older [`normalize`], so lookups find them.
+///
+/// Cached lyrics are merged as by [`dedupe`]. In the other tables a row
+/// whose new key is taken is dropped in favour of the one already there.
+async fn renormalize_keys(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    dedupe_pool(pool).await?;
+    for table in ["musicbrainz", "musixmatch_tracks", "rejected", "pinned"] {
+        let rows = sqlx::query(&format!("SELECT rowid, artist, title, album FROM {}", table))
+            .fetch_all(pool)
+            .await?;
+        for row in rows {
+            let (artist, title, album): (String, String, String) = (row.get("artist"), row.get("title"), row.get("album"));
+            let key = cache_key(&artist, &title, &album);
+            if key == (artist, title, album) {
+                continue;
+            }
+            let rowid: i64 = row.get("rowid");
+            let updated = sqlx::query(&format!("UPDATE OR IGNORE {} SET artist = ?, title = ?, album = ? WHERE rowid = ?", table))
+                .bind(&key.0)
+                .bind(&key.1)
+                .bind(&key.2)
+                .bind(rowid)
+                .execute(pool)
+                .await?;
+            if updated.rows_affected() == 0 {
+                sqlx::query(&format!("DELETE FROM {} WHERE rowid = ?", table))
+                    .bind(rowid)
+                    .execute(pool)
+                    .await?;
+            }
+        }
+    }
     Ok(())
 }
 
 /// Opens or creates a SQLite database connection pool.
-async fn open_database(path: &PathBuf) -> Result<SqlitePool, sqlx::Error> {
+async fn open_database(path: &Path) -> Result<SqlitePool, sqlx::Error> {
     // Create parent directory if needed
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent).ok();
@@ -171,6 +456,7 @@ async fn open_database(path: &PathBuf) -> Result<SqlitePool, sqlx::Error> {
 
     // Initialize schema
     create_schema(&pool).await?;
+    prune_events(&pool, chrono::Utc::now().timestamp()).await?;
 
     Ok(pool)
 }
@@ -187,10 +473,14 @@ async fn open_database(path: &PathBuf) -> Result<SqlitePool, sqlx::Error> {
 /// - `Err` if parsing fails
 fn parse_stored_lyrics(entry: &LyricsEntry) -> ProviderResult {
     match entry.format {
-        LyricsFormat::Lrclib => {
+        LyricsFormat::Lrclib | LyricsFormat::Exec => {
             let lines = parse_synced_lyrics(&entry.raw_lyrics);
             Ok((lines, Some(entry.raw_lyrics.clone())))
         }
+        LyricsFormat::Local => {
+            let lines = parse_lyrics_file(&entry.raw_lyrics);
+            Ok((lines, Some(entry.raw_lyrics.clone())))
+        }
         LyricsFormat::Richsync => {
             // Parse the raw JSON body
             match parse_richsync_body(&entry.raw_lyrics) {
@@ -215,6 +505,18 @@ fn parse_stored_lyrics(entry: &LyricsEntry) -> ProviderResult {
                 )),
             }
         }
+        LyricsFormat::Ttml => match parse_ttml(&entry.raw_lyrics) {
+            Some(lines) => Ok((lines, Some(entry.raw_lyrics.clone()))),
+            None => Err(LyricsError::Api(
+                "Failed to parse TTML lyrics from database".to_string()
+            )),
+        },
+        LyricsFormat::Yrc => match parse_yrc(&entry.raw_lyrics) {
+            Some(lines) => Ok((lines, Some(entry.raw_lyrics.clone()))),
+            None => Err(LyricsError::Api(
+                "Failed to parse YRC lyrics from database".to_string()
+            )),
+        },
     }
 }
 
@@ -253,6 +555,10 @@ pub async fn initialize(path: PathBuf) {
 ///
 /// Uses indexed SQL query for fast lookup with minimal memory usage.
 ///
+/// When both `duration` and the stored duration are known, the entry is
+/// only returned if they differ by at most `tolerance` (a fraction of
+/// `duration`, e.g. `0.05` for 5%). Pass `duration: None` to skip the check.
+///
 /// # Returns
 ///
 /// - `Some(result)` if lyrics are found in the database
@@ -262,7 +568,8 @@ pub async fn fetch_from_database(
     title: &str,
     album: &str,
     duration: Option<f64>,
-) -> Option<ProviderResult> {
+    tolerance: f64,
+) -> Option<Result<CachedLyrics, LyricsError>> {
     let pool = DB_POOL.get()?;
     
     // Normalize search terms for case-insensitive matching
@@ -273,9 +580,10 @@ pub async fn fetch_from_database(
     // Query database with indexed lookup
     let row = sqlx::query(
         r#"
-        SELECT duration, format, raw_lyrics
+        SELECT COALESCE(provider_duration, duration) AS duration, provider_duration, format, raw_lyrics
         FROM lyrics
         WHERE artist = ? AND title = ? AND album = ?
+        ORDER BY preferred DESC, id DESC
         LIMIT 1
         "#,
     )
@@ -284,39 +592,179 @@ pub async fn fetch_from_database(
     .bind(&album_norm)
     .fetch_optional(pool)
     .await
-    .ok()??;
-    
-    // Extract fields from row
-    let entry = LyricsEntry {
-        duration: row.get("duration"),
-        format: LyricsFormat::from_str(row.get("format"))?,
-        raw_lyrics: row.get("raw_lyrics"),
+    .ok()?;
+
+    // Exact key missed - fall back to a fuzzy match on album/duration
+    let (entry, provider_duration) = match row {
+        Some(row) => (
+            LyricsEntry {
+                duration: row.get("duration"),
+                format: LyricsFormat::from_str(row.get("format"))?,
+                raw_lyrics: row.get("raw_lyrics"),
+            },
+            row.get("provider_duration"),
+        ),
+        None => fetch_fuzzy_match(pool, &artist_norm, &title_norm, &album_norm, duration).await?,
     };
-    
+    cached_lyrics(entry, provider_duration, duration, tolerance)
+}
+
+/// Like [`fetch_from_database`], but reads the newest stored version from
+/// `provider` (a `--providers` name) whether or not it is the preferred one.
+pub async fn fetch_version(
+    artist: &str,
+    title: &str,
+    album: &str,
+    provider: &str,
+    duration: Option<f64>,
+    tolerance: f64,
+) -> Option<Result<CachedLyrics, LyricsError>> {
+    let pool = DB_POOL.get()?;
+    let (artist, title, album) = (normalize(artist), normalize(title), normalize(album));
+
+    let mut newest: Option<(i64, LyricsEntry, Option<f64>)> = None;
+    for format in LyricsFormat::for_provider(provider) {
+        let row = sqlx::query(
+            r#"
+            SELECT id, COALESCE(provider_duration, duration) AS duration, provider_duration, raw_lyrics
+            FROM lyrics
+            WHERE artist = ? AND title = ? AND album = ? AND format = ?
+            ORDER BY id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(&artist)
+        .bind(&title)
+        .bind(&album)
+        .bind(format.to_str())
+        .fetch_optional(pool)
+        .await
+        .ok()
+        .flatten();
+        if let Some(row) = row
+            && newest.as_ref().is_none_or(|(id, _, _)| *id < row.get::<i64, _>("id"))
+        {
+            let entry = LyricsEntry { duration: row.get("duration"), format: format.clone(), raw_lyrics: row.get("raw_lyrics") };
+            newest = Some((row.get("id"), entry, row.get("provider_duration")));
+        }
+    }
+    let (_, entry, provider_duration) = newest?;
+    cached_lyrics(entry, provider_duration, duration, tolerance)
+}
+
+/// Parses a cache entry, unless its duration is off by more than
+/// `tolerance` from `duration`.
+fn cached_lyrics(
+    entry: LyricsEntry,
+    provider_duration: Option<f64>,
+    duration: Option<f64>,
+    tolerance: f64,
+) -> Option<Result<CachedLyrics, LyricsError>> {
     // Optional: Validate duration match if both are present
     if let (Some(query_duration), Some(entry_duration)) = (duration, entry.duration) {
-        // Allow 5% tolerance for duration mismatch
-        let tolerance = query_duration * 0.05;
-        if (query_duration - entry_duration).abs() > tolerance {
+        let allowed = query_duration * tolerance;
+        if (query_duration - entry_duration).abs() > allowed {
+            tracing::debug!(
+                query_duration,
+                entry_duration,
+                tolerance,
+                "Cached lyrics rejected: duration mismatch"
+            );
             return None;
         }
     }
     
     // Parse and return
-    Some(parse_stored_lyrics(&entry))
+    Some(parse_stored_lyrics(&entry).map(|(lines, _)| CachedLyrics {
+        lines,
+        raw: entry.raw_lyrics,
+        format: entry.format,
+        provider_duration,
+    }))
+}
+
+/// Looks up rows with the same artist and title but a different album.
+///
+/// Album names vary between releases ("AM" vs "AM (Deluxe)"), so candidates
+/// are scored with [`similarity`](crate::lyrics::similarity) on album and
+/// duration. Returns `None` unless one row is a confident match.
+async fn fetch_fuzzy_match(
+    pool: &SqlitePool,
+    artist: &str,
+    title: &str,
+    album: &str,
+    duration: Option<f64>,
+) -> Option<(LyricsEntry, Option<f64>)> {
+    let rows = sqlx::query(
+        r#"
+        SELECT album, COALESCE(provider_duration, duration) AS duration, provider_duration, format, raw_lyrics
+        FROM lyrics
+        WHERE artist = ? AND title = ? AND preferred = 1
+        LIMIT 20
+        "#,
+    )
+    .bind(artist)
+    .bind(title)
+    .fetch_all(pool)
+    .await
+    .ok()?;
+
+    if rows.is_empty() {
+        return None;
+    }
+
+    let candidates: Vec<serde_json::Value> = rows
+        .iter()
+        .map(|row| {
+            serde_json::json!({
+                "title": title,
+                "artist": artist,
+                "album": row.get::<String, _>("album"),
+                "duration": row.get::<Option<f64>, _>("duration"),
+            })
+        })
+        .collect();
+
+    let query_album = (!album.is_empty()).then_some(album);
+    let (idx, score) =
+        crate::lyrics::similarity::find_best_song_match(&candidates, title, artist, query_album, duration)?;
+    let row = &rows[idx];
+
+    tracing::debug!(
+        artist = %artist,
+        title = %title,
+        album = %album,
+        matched_album = %row.get::<String, _>("album"),
+        score = score.score,
+        "Fuzzy database cache hit"
+    );
+
+    let entry = LyricsEntry {
+        duration: row.get("duration"),
+        format: LyricsFormat::from_str(row.get("format"))?,
+        raw_lyrics: row.get("raw_lyrics"),
+    };
+    Some((entry, row.get("provider_duration")))
 }
 
 /// Stores lyrics in the database.
 ///
-/// Uses SQL DELETE + INSERT to replace existing entries.
-/// Minimal memory usage - only the new entry is in memory briefly.
+/// Replaces the track's version in the same format and makes the new one
+/// preferred; versions in other formats are kept for pins and rejections.
 ///
 /// This should be called after successfully fetching lyrics from a provider.
+/// `duration` is the player's track length; `provider_duration` the one the
+/// provider matched, which lookups prefer since web players often report
+/// lengths that are several seconds off. `validators` are kept for
+/// conditional requests when the entry is fetched again.
+#[allow(clippy::too_many_arguments)]
 pub async fn store_in_database(
     artist: &str,
     title: &str,
     album: &str,
     duration: Option<f64>,
+    provider_duration: Option<f64>,
+    validators: &HttpValidators,
     format: LyricsFormat,
     raw_lyrics: String,
 ) {
@@ -325,40 +773,9 @@ pub async fn store_in_database(
     };
     
     // Normalize for consistent storage
-    let artist_norm = normalize(artist);
-    let title_norm = normalize(title);
-    let album_norm = normalize(album);
-    
-    // Delete existing entry if it exists
-    let _ = sqlx::query(
-        r#"
-        DELETE FROM lyrics
-        WHERE artist = ? AND title = ? AND album = ?
-        "#,
-    )
-    .bind(&artist_norm)
-    .bind(&title_norm)
-    .bind(&album_norm)
-    .execute(pool)
-    .await;
-    
-    // Insert new entry
-    let result = sqlx::query(
-        r#"
-        INSERT INTO lyrics (artist, title, album, duration, format, raw_lyrics)
-        VALUES (?, ?, ?, ?, ?, ?)
-        "#,
-    )
-    .bind(&artist_norm)
-    .bind(&title_norm)
-    .bind(&album_norm)
-    .bind(duration)
-    .bind(format.to_str())
-    .bind(&raw_lyrics)
-    .execute(pool)
-    .await;
-    
-    if let Err(e) = result {
+    let key = (normalize(artist), normalize(title), normalize(album));
+    let entry = LyricsEntry { duration: provider_duration, format, raw_lyrics };
+    if let Err(e) = store_in_database_pool(pool, &key, duration, validators, &entry).await {
         tracing::warn!(
             artist = %artist,
             title = %title,
@@ -366,4 +783,1327 @@ pub async fn store_in_database(
             "Failed to store lyrics in database"
         );
     }
-}
\ No newline at end of file
+}
+
+async fn store_in_database_pool(
+    pool: &SqlitePool,
+    key: &TrackKey,
+    duration: Option<f64>,
+    validators: &HttpValidators,
+    entry: &LyricsEntry,
+) -> Result<(), sqlx::Error> {
+    let (artist, title, album) = key;
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("DELETE FROM lyrics WHERE artist = ? AND title = ? AND album = ? AND format = ?")
+        .bind(artist)
+        .bind(title)
+        .bind(album)
+        .bind(entry.format.to_str())
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("UPDATE lyrics SET preferred = 0 WHERE artist = ? AND title = ? AND album = ?")
+        .bind(artist)
+        .bind(title)
+        .bind(album)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(
+        r#"
+        INSERT INTO lyrics (artist, title, album, duration, provider_duration, etag, last_modified, format, raw_lyrics, stored_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(artist)
+    .bind(title)
+    .bind(album)
+    .bind(duration)
+    .bind(entry.duration)
+    .bind(&validators.etag)
+    .bind(&validators.last_modified)
+    .bind(entry.format.to_str())
+    .bind(&entry.raw_lyrics)
+    .bind(chrono::Utc::now().timestamp())
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await
+}
+
+/// Makes the newest stored version from `provider` (a `--providers` name)
+/// the preferred one.
+///
+/// Returns `false` (changing nothing) if the track has no such version.
+pub async fn prefer_version(artist: &str, title: &str, album: &str, provider: &str) -> bool {
+    let Some(pool) = DB_POOL.get() else {
+        return false;
+    };
+    let key = (normalize(artist), normalize(title), normalize(album));
+    match prefer_version_pool(pool, &key, LyricsFormat::for_provider(provider)).await {
+        Ok(found) => found,
+        Err(e) => {
+            tracing::warn!(artist = %artist, title = %title, error = %e, "Failed to switch cached lyrics version");
+            false
+        }
+    }
+}
+
+async fn prefer_version_pool(pool: &SqlitePool, key: &TrackKey, formats: &[LyricsFormat]) -> Result<bool, sqlx::Error> {
+    let (artist, title, album) = key;
+    let mut newest: Option<i64> = None;
+    for format in formats {
+        let id: Option<i64> = sqlx::query(
+            "SELECT MAX(id) AS id FROM lyrics WHERE artist = ? AND title = ? AND album = ? AND format = ?",
+        )
+        .bind(artist)
+        .bind(title)
+        .bind(album)
+        .bind(format.to_str())
+        .fetch_one(pool)
+        .await?
+        .get("id");
+        newest = newest.max(id);
+    }
+    let Some(id) = newest else {
+        return Ok(false);
+    };
+    sqlx::query("UPDATE lyrics SET preferred = (id = ?) WHERE artist = ? AND title = ? AND album = ?")
+        .bind(id)
+        .bind(artist)
+        .bind(title)
+        .bind(album)
+        .execute(pool)
+        .await?;
+    Ok(true)
+}
+
+/// Whether the cached entry for a track was fetched more than `max_age` ago.
+///
+/// Entries from before `stored_at` was recorded count as stale; tracks
+/// without an exact entry don't.
+pub async fn is_stale(artist: &str, title: &str, album: &str, max_age: std::time::Duration) -> bool {
+    let Some(pool) = DB_POOL.get() else {
+        return false;
+    };
+    is_stale_pool(pool, artist, title, album, max_age, chrono::Utc::now().timestamp()).await
+}
+
+async fn is_stale_pool(
+    pool: &SqlitePool,
+    artist: &str,
+    title: &str,
+    album: &str,
+    max_age: std::time::Duration,
+    now: i64,
+) -> bool {
+    let row = sqlx::query(
+        "SELECT stored_at FROM lyrics WHERE artist = ? AND title = ? AND album = ? ORDER BY preferred DESC, id DESC LIMIT 1",
+    )
+        .bind(normalize(artist))
+        .bind(normalize(title))
+        .bind(normalize(album))
+        .fetch_optional(pool)
+        .await;
+    match row {
+        Ok(Some(row)) => row
+            .get::<Option<i64>, _>("stored_at")
+            .is_none_or(|stored_at| now - stored_at > max_age.as_secs() as i64),
+        _ => false,
+    }
+}
+
+/// Marks the preferred cached version of a track as fetched now, after a refresh
+/// found it unchanged.
+pub async fn touch(artist: &str, title: &str, album: &str) {
+    let Some(pool) = DB_POOL.get() else {
+        return;
+    };
+    let result = sqlx::query("UPDATE lyrics SET stored_at = ? WHERE artist = ? AND title = ? AND album = ? AND preferred = 1")
+        .bind(chrono::Utc::now().timestamp())
+        .bind(normalize(artist))
+        .bind(normalize(title))
+        .bind(normalize(album))
+        .execute(pool)
+        .await;
+    if let Err(e) = result {
+        tracing::warn!(artist = %artist, title = %title, error = %e, "Failed to update cache entry age");
+    }
+}
+
+/// Records that a background search for word-synced lyrics found nothing
+/// for a track, so cache hits don't search again right away.
+pub async fn mark_upgrade_failed(artist: &str, title: &str, album: &str) {
+    let Some(pool) = DB_POOL.get() else {
+        return;
+    };
+    let result = sqlx::query("UPDATE lyrics SET upgrade_failed_at = ? WHERE artist = ? AND title = ? AND album = ? AND preferred = 1")
+        .bind(chrono::Utc::now().timestamp())
+        .bind(normalize(artist))
+        .bind(normalize(title))
+        .bind(normalize(album))
+        .execute(pool)
+        .await;
+    if let Err(e) = result {
+        tracing::warn!(artist = %artist, title = %title, error = %e, "Failed to record word-synced search");
+    }
+}
+
+/// Whether a word-synced search for the cached track found nothing less
+/// than `retry_after` ago.
+pub async fn upgrade_failed_recently(artist: &str, title: &str, album: &str, retry_after: std::time::Duration) -> bool {
+    let Some(pool) = DB_POOL.get() else {
+        return false;
+    };
+    upgrade_failed_recently_pool(pool, artist, title, album, retry_after, chrono::Utc::now().timestamp()).await
+}
+
+async fn upgrade_failed_recently_pool(
+    pool: &SqlitePool,
+    artist: &str,
+    title: &str,
+    album: &str,
+    retry_after: std::time::Duration,
+    now: i64,
+) -> bool {
+    let row = sqlx::query(
+        "SELECT upgrade_failed_at FROM lyrics WHERE artist = ? AND title = ? AND album = ? AND preferred = 1 LIMIT 1",
+    )
+        .bind(normalize(artist))
+        .bind(normalize(title))
+        .bind(normalize(album))
+        .fetch_optional(pool)
+        .await;
+    match row {
+        Ok(Some(row)) => row
+            .get::<Option<i64>, _>("upgrade_failed_at")
+            .is_some_and(|failed_at| now - failed_at < retry_after.as_secs() as i64),
+        _ => false,
+    }
+}
+
+/// Normalized artist, title and album of a track, and the rejected provider.
+type Rejection = (String, String, String, String);
+
+/// Normalized artist, title and album of a track.
+type TrackKey = (String, String, String);
+
+/// Rejections and pins made by the running event loop, for when there is
+/// no database to keep them.
+#[derive(Debug, Default)]
+pub struct SessionChoices {
+    rejected: Vec<Rejection>,
+    /// `None`: unpinned this session, hiding a stored pin
+    pinned: std::collections::HashMap<TrackKey, Option<String>>,
+}
+
+/// Remembers that `provider` has the wrong lyrics for a track and drops
+/// its cached versions, so the next lookup uses another cached version or
+/// asks the remaining providers.
+pub async fn reject_provider(session: &mut SessionChoices, artist: &str, title: &str, album: &str, provider: &str) {
+    let key = (normalize(artist), normalize(title), normalize(album), provider.to_string());
+    if !session.rejected.contains(&key) {
+        session.rejected.push(key.clone());
+    }
+
+    let Some(pool) = DB_POOL.get() else {
+        return;
+    };
+    if let Err(e) = reject_provider_pool(pool, &key).await {
+        tracing::warn!(artist = %artist, title = %title, error = %e, "Failed to store rejected lyrics");
+    }
+}
+
+async fn reject_provider_pool(pool: &SqlitePool, key: &Rejection) -> Result<(), sqlx::Error> {
+    let (artist, title, album, provider) = key;
+    sqlx::query("INSERT OR IGNORE INTO rejected (artist, title, album, provider) VALUES (?, ?, ?, ?)")
+        .bind(artist)
+        .bind(title)
+        .bind(album)
+        .bind(provider)
+        .execute(pool)
+        .await?;
+    for format in LyricsFormat::for_provider(provider) {
+        sqlx::query("DELETE FROM lyrics WHERE artist = ? AND title = ? AND album = ? AND format = ?")
+            .bind(artist)
+            .bind(title)
+            .bind(album)
+            .bind(format.to_str())
+            .execute(pool)
+            .await?;
+    }
+
+    // The newest remaining version takes over if the preferred one was dropped
+    let has_preferred = sqlx::query("SELECT 1 FROM lyrics WHERE artist = ? AND title = ? AND album = ? AND preferred = 1")
+        .bind(artist)
+        .bind(title)
+        .bind(album)
+        .fetch_optional(pool)
+        .await?
+        .is_some();
+    if !has_preferred {
+        prefer_version_pool(pool, &(artist.clone(), title.clone(), album.clone()), &LyricsFormat::ALL).await?;
+    }
+    Ok(())
+}
+
+/// Undoes every [`reject_provider`] for a track. Returns whether there
+/// were any.
+pub async fn forget_rejections(session: &mut SessionChoices, artist: &str, title: &str, album: &str) -> bool {
+    let key = (normalize(artist), normalize(title), normalize(album));
+    let before = session.rejected.len();
+    session.rejected.retain(|(a, t, al, _)| (a, t, al) != (&key.0, &key.1, &key.2));
+    let mut forgotten = session.rejected.len() < before;
+
+    if let Some(pool) = DB_POOL.get() {
+        match forget_rejections_pool(pool, &key).await {
+            Ok(removed) => forgotten |= removed,
+            Err(e) => tracing::warn!(artist = %artist, title = %title, error = %e, "Failed to forget rejected lyrics"),
+        }
+    }
+    forgotten
+}
+
+async fn forget_rejections_pool(pool: &SqlitePool, key: &TrackKey) -> Result<bool, sqlx::Error> {
+    let (artist, title, album) = key;
+    let result = sqlx::query("DELETE FROM rejected WHERE artist = ? AND title = ? AND album = ?")
+        .bind(artist)
+        .bind(title)
+        .bind(album)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Providers rejected for a track with [`reject_provider`].
+pub async fn rejected_providers(session: &SessionChoices, artist: &str, title: &str, album: &str) -> Vec<String> {
+    let (artist, title, album) = (normalize(artist), normalize(title), normalize(album));
+    let mut rejected: Vec<String> = session
+        .rejected
+        .iter()
+        .filter(|(a, t, al, _)| (a, t, al) == (&artist, &title, &album))
+        .map(|(_, _, _, provider)| provider.clone())
+        .collect();
+    if let Some(pool) = DB_POOL.get() {
+        for provider in rejected_providers_pool(pool, &artist, &title, &album).await {
+            if !rejected.contains(&provider) {
+                rejected.push(provider);
+            }
+        }
+    }
+    rejected
+}
+
+async fn rejected_providers_pool(pool: &SqlitePool, artist: &str, title: &str, album: &str) -> Vec<String> {
+    sqlx::query("SELECT provider FROM rejected WHERE artist = ? AND title = ? AND album = ?")
+        .bind(artist)
+        .bind(title)
+        .bind(album)
+        .fetch_all(pool)
+        .await
+        .map(|rows| rows.iter().map(|row| row.get("provider")).collect())
+        .unwrap_or_default()
+}
+
+/// Pins `provider` for a track, or removes the pin with `None`.
+pub async fn pin_provider(session: &mut SessionChoices, artist: &str, title: &str, album: &str, provider: Option<&str>) {
+    let key = (normalize(artist), normalize(title), normalize(album));
+    session.pinned.insert(key.clone(), provider.map(str::to_string));
+
+    let Some(pool) = DB_POOL.get() else {
+        return;
+    };
+    if let Err(e) = pin_provider_pool(pool, &key, provider).await {
+        tracing::warn!(artist = %artist, title = %title, error = %e, "Failed to store pinned provider");
+    }
+}
+
+async fn pin_provider_pool(pool: &SqlitePool, key: &TrackKey, provider: Option<&str>) -> Result<(), sqlx::Error> {
+    let (artist, title, album) = key;
+    let query = match provider {
+        Some(provider) => sqlx::query(
+            "INSERT OR REPLACE INTO pinned (artist, title, album, provider) VALUES (?, ?, ?, ?)",
+        )
+        .bind(artist)
+        .bind(title)
+        .bind(album)
+        .bind(provider),
+        None => sqlx::query("DELETE FROM pinned WHERE artist = ? AND title = ? AND album = ?")
+            .bind(artist)
+            .bind(title)
+            .bind(album),
+    };
+    query.execute(pool).await?;
+    Ok(())
+}
+
+/// Provider pinned for a track with [`pin_provider`], if any.
+pub async fn pinned_provider(session: &SessionChoices, artist: &str, title: &str, album: &str) -> Option<String> {
+    let key = (normalize(artist), normalize(title), normalize(album));
+    if let Some(pinned) = session.pinned.get(&key) {
+        return pinned.clone();
+    }
+    pinned_provider_pool(DB_POOL.get()?, &key).await
+}
+
+async fn pinned_provider_pool(pool: &SqlitePool, key: &TrackKey) -> Option<String> {
+    let (artist, title, album) = key;
+    sqlx::query("SELECT provider FROM pinned WHERE artist = ? AND title = ? AND album = ?")
+        .bind(artist)
+        .bind(title)
+        .bind(album)
+        .fetch_optional(pool)
+        .await
+        .ok()
+        .flatten()
+        .map(|row| row.get("provider"))
+}
+
+/// HTTP validators stored with the track's LRCLIB version, if any.
+pub async fn fetch_validators(artist: &str, title: &str, album: &str) -> Option<HttpValidators> {
+    let pool = DB_POOL.get()?;
+    fetch_validators_pool(pool, artist, title, album).await
+}
+
+async fn fetch_validators_pool(pool: &SqlitePool, artist: &str, title: &str, album: &str) -> Option<HttpValidators> {
+    let row = sqlx::query(
+        "SELECT etag, last_modified FROM lyrics WHERE artist = ? AND title = ? AND album = ? AND format = ? ORDER BY id DESC LIMIT 1",
+    )
+    .bind(normalize(artist))
+    .bind(normalize(title))
+    .bind(normalize(album))
+    .bind(LyricsFormat::Lrclib.to_str())
+    .fetch_optional(pool)
+    .await
+    .ok()??;
+    let validators = HttpValidators { etag: row.get("etag"), last_modified: row.get("last_modified") };
+    (!validators.is_empty()).then_some(validators)
+}
+
+/// Looks up a cached MusicBrainz resolution for the given player metadata.
+///
+/// Returns `None` if the database is disabled or the track was never resolved.
+pub async fn fetch_musicbrainz_match(
+    artist: &str,
+    title: &str,
+    album: &str,
+) -> Option<crate::lyrics::musicbrainz::MusicBrainzMatch> {
+    let pool = DB_POOL.get()?;
+
+    let row = sqlx::query(
+        r#"
+        SELECT recording_mbid, artist_mbid, canonical_artist, canonical_title, length
+        FROM musicbrainz
+        WHERE artist = ? AND title = ? AND album = ?
+        "#,
+    )
+    .bind(normalize(artist))
+    .bind(normalize(title))
+    .bind(normalize(album))
+    .fetch_optional(pool)
+    .await
+    .ok()??;
+
+    Some(crate::lyrics::musicbrainz::MusicBrainzMatch {
+        recording_id: row.get("recording_mbid"),
+        artist_id: row.get("artist_mbid"),
+        artist: row.get("canonical_artist"),
+        title: row.get("canonical_title"),
+        length: row.get("length"),
+    })
+}
+
+/// Stores a MusicBrainz resolution for the given player metadata.
+pub async fn store_musicbrainz_match(
+    artist: &str,
+    title: &str,
+    album: &str,
+    mb: &crate::lyrics::musicbrainz::MusicBrainzMatch,
+) {
+    let Some(pool) = DB_POOL.get() else {
+        return;
+    };
+
+    let result = sqlx::query(
+        r#"
+        INSERT OR REPLACE INTO musicbrainz
+            (artist, title, album, recording_mbid, artist_mbid, canonical_artist, canonical_title, length)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(normalize(artist))
+    .bind(normalize(title))
+    .bind(normalize(album))
+    .bind(&mb.recording_id)
+    .bind(&mb.artist_id)
+    .bind(&mb.artist)
+    .bind(&mb.title)
+    .bind(mb.length)
+    .execute(pool)
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!(
+            artist = %artist,
+            title = %title,
+            error = %e,
+            "Failed to store MusicBrainz match in database"
+        );
+    }
+}
+
+/// Looks up the Musixmatch `(commontrack_id, track_length)` resolved earlier
+/// for the given metadata.
+///
+/// Returns `None` if the database is disabled or the track was never resolved.
+pub async fn fetch_musixmatch_track(artist: &str, title: &str, album: &str) -> Option<(i64, Option<i64>)> {
+    let pool = DB_POOL.get()?;
+
+    let row = sqlx::query(
+        r#"
+        SELECT commontrack_id, track_length
+        FROM musixmatch_tracks
+        WHERE artist = ? AND title = ? AND album = ?
+        "#,
+    )
+    .bind(normalize(artist))
+    .bind(normalize(title))
+    .bind(normalize(album))
+    .fetch_optional(pool)
+    .await
+    .ok()??;
+
+    Some((row.get("commontrack_id"), row.get("track_length")))
+}
+
+/// Remembers the Musixmatch track a search resolved to, so later lookups
+/// of the same track skip `track.search`.
+pub async fn store_musixmatch_track(
+    artist: &str,
+    title: &str,
+    album: &str,
+    commontrack_id: i64,
+    track_length: Option<i64>,
+) {
+    let Some(pool) = DB_POOL.get() else {
+        return;
+    };
+
+    let result = sqlx::query(
+        r#"
+        INSERT OR REPLACE INTO musixmatch_tracks (artist, title, album, commontrack_id, track_length)
+        VALUES (?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(normalize(artist))
+    .bind(normalize(title))
+    .bind(normalize(album))
+    .bind(commontrack_id)
+    .bind(track_length)
+    .execute(pool)
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!(
+            artist = %artist,
+            title = %title,
+            error = %e,
+            "Failed to store Musixmatch track ID in database"
+        );
+    }
+}
+
+// ============================================================================
+// Fetch Metrics
+// ============================================================================
+
+/// Result of one lookup, as recorded in the `events` table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FetchOutcome {
+    /// Lyrics were returned
+    Found,
+    /// No lyrics, or a recoverable (network) error
+    Missed,
+    /// The provider reported an error
+    Failed,
+}
+
+impl FetchOutcome {
+    fn to_str(self) -> &'static str {
+        match self {
+            Self::Found => "found",
+            Self::Missed => "missed",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// Per-provider totals of the recorded lookups.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderStats {
+    pub provider: String,
+    pub fetches: i64,
+    pub found: i64,
+    pub missed: i64,
+    pub failed: i64,
+    /// Mean latency in milliseconds
+    pub avg_latency_ms: f64,
+}
+
+/// Records one lookup. Does nothing if the database is disabled.
+pub async fn record_fetch(
+    artist: &str,
+    title: &str,
+    album: &str,
+    provider: &str,
+    outcome: FetchOutcome,
+    latency: std::time::Duration,
+) {
+    let Some(pool) = DB_POOL.get() else {
+        return;
+    };
+    if let Err(e) = record_fetch_pool(pool, artist, title, album, provider, outcome, latency).await {
+        tracing::warn!(provider = %provider, error = %e, "Failed to record fetch event");
+    }
+}
+
+async fn record_fetch_pool(
+    pool: &SqlitePool,
+    artist: &str,
+    title: &str,
+    album: &str,
+    provider: &str,
+    outcome: FetchOutcome,
+    latency: std::time::Duration,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO events (at, artist, title, album, provider, outcome, latency_ms)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(chrono::Utc::now().timestamp())
+    .bind(artist)
+    .bind(title)
+    .bind(album)
+    .bind(provider)
+    .bind(outcome.to_str())
+    .bind(i64::try_from(latency.as_millis()).unwrap_or(i64::MAX))
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// How long recorded lookups are kept for `lyricsmpris stats`.
+const EVENT_RETENTION: std::time::Duration = std::time::Duration::from_secs(90 * 86_400);
+
+/// Deletes lookups recorded more than [`EVENT_RETENTION`] before `now`.
+async fn prune_events(pool: &SqlitePool, now: i64) -> Result<(), sqlx::Error> {
+    let cutoff = now - EVENT_RETENTION.as_secs() as i64;
+    let deleted = sqlx::query("DELETE FROM events WHERE at < ?")
+        .bind(cutoff)
+        .execute(pool)
+        .await?
+        .rows_affected();
+    if deleted > 0 {
+        tracing::debug!(deleted, "Pruned old fetch events");
+    }
+    Ok(())
+}
+
+/// Summarizes the recorded lookups per provider, most successful first.
+///
+/// # Returns
+///
+/// - `None` if no database is initialized
+/// - `Some(Err)` if the query failed
+pub async fn fetch_stats() -> Option<Result<Vec<ProviderStats>, sqlx::Error>> {
+    let pool = DB_POOL.get()?;
+    Some(fetch_stats_pool(pool).await)
+}
+
+async fn fetch_stats_pool(pool: &SqlitePool) -> Result<Vec<ProviderStats>, sqlx::Error> {
+    let rows = sqlx::query(
+        r#"
+        SELECT provider,
+               COUNT(*) AS fetches,
+               SUM(outcome = 'found') AS found,
+               SUM(outcome = 'missed') AS missed,
+               SUM(outcome = 'failed') AS failed,
+               AVG(latency_ms) AS avg_latency_ms
+        FROM events
+        GROUP BY provider
+        ORDER BY found DESC, fetches DESC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| ProviderStats {
+            provider: row.get("provider"),
+            fetches: row.get("fetches"),
+            found: row.get("found"),
+            missed: row.get("missed"),
+            failed: row.get("failed"),
+            avg_latency_ms: row.get("avg_latency_ms"),
+        })
+        .collect())
+}
+
+// ============================================================================
+// Maintenance
+// ============================================================================
+
+/// Summary of a [`dedupe`] run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DedupeStats {
+    /// Tracks that had duplicate rows
+    pub duplicate_groups: usize,
+    /// Rows deleted as duplicates
+    pub removed: usize,
+    /// Groups where a word-level version was preferred over a newer line-level one
+    pub upgraded: usize,
+    /// Kept rows whose artist/title/album were rewritten to the current normalization
+    pub renormalized: usize,
+}
+
+/// A cached row as seen by [`dedupe`].
+struct DedupeRow {
+    id: i64,
+    artist: String,
+    title: String,
+    album: String,
+    format: Option<LyricsFormat>,
+    preferred: bool,
+}
+
+/// Merges rows that differ only in normalization of artist/title/album.
+///
+/// Older versions stored keys with different case/whitespace handling, so
+/// the same track can have several rows. For each track the newest row per
+/// format is kept. If that leaves no single preferred version, a word-level
+/// one (richsync, TTML, YRC) wins over a line-level one, otherwise the most
+/// recently stored one. The kept rows' keys are rewritten to the current
+/// normalization.
+///
+/// # Returns
+///
+/// - `None` if no database is initialized
+/// - `Some(Err)` if a query failed (no changes are committed)
+pub async fn dedupe() -> Option<Result<DedupeStats, sqlx::Error>> {
+    let pool = DB_POOL.get()?;
+    Some(dedupe_pool(pool).await)
+}
+
+async fn dedupe_pool(pool: &SqlitePool) -> Result<DedupeStats, sqlx::Error> {
+    let rows: Vec<DedupeRow> = sqlx::query("SELECT id, artist, title, album, format, preferred FROM lyrics ORDER BY id")
+        .fetch_all(pool)
+        .await?
+        .iter()
+        .map(|row| DedupeRow {
+            id: row.get("id"),
+            artist: row.get("artist"),
+            title: row.get("title"),
+            album: row.get("album"),
+            format: LyricsFormat::from_str(row.get("format")),
+            preferred: row.get("preferred"),
+        })
+        .collect();
+
+    // Group by normalized key, preserving first-seen order
+    let mut groups: Vec<((String, String, String), Vec<DedupeRow>)> = Vec::new();
+    let mut index: std::collections::HashMap<(String, String, String), usize> = std::collections::HashMap::new();
+    for row in rows {
+        let key = (normalize(&row.artist), normalize(&row.title), normalize(&row.album));
+        match index.get(&key) {
+            Some(&i) => groups[i].1.push(row),
+            None => {
+                index.insert(key.clone(), groups.len());
+                groups.push((key, vec![row]));
+            }
+        }
+    }
+
+    let mut stats = DedupeStats::default();
+    let mut tx = pool.begin().await?;
+
+    for ((artist, title, album), group) in &groups {
+        // Newest row per format (rows are in id order)
+        let mut kept: Vec<&DedupeRow> = Vec::new();
+        for row in group.iter().rev() {
+            if !kept.iter().any(|k| k.format == row.format) {
+                kept.push(row);
+            }
+        }
+
+        let flagged: Vec<&DedupeRow> = kept.iter().copied().filter(|r| r.preferred).collect();
+        let preferred = match flagged.as_slice() {
+            [only] => only.id,
+            [] => kept[choose_keeper(&kept)].id,
+            many => many[choose_keeper(many)].id,
+        };
+
+        let removed: Vec<i64> = group.iter().map(|r| r.id).filter(|id| !kept.iter().any(|k| k.id == *id)).collect();
+        if !removed.is_empty() || flagged.len() > 1 {
+            stats.duplicate_groups += 1;
+            stats.removed += removed.len();
+            if flagged.len() != 1 && kept[0].id != preferred {
+                stats.upgraded += 1;
+            }
+        }
+
+        for id in removed {
+            sqlx::query("DELETE FROM lyrics WHERE id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        for row in &kept {
+            if (row.id == preferred) != row.preferred {
+                sqlx::query("UPDATE lyrics SET preferred = ? WHERE id = ?")
+                    .bind(row.id == preferred)
+                    .bind(row.id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            if (&row.artist, &row.title, &row.album) != (artist, title, album) {
+                stats.renormalized += 1;
+                sqlx::query("UPDATE lyrics SET artist = ?, title = ?, album = ? WHERE id = ?")
+                    .bind(artist)
+                    .bind(title)
+                    .bind(album)
+                    .bind(row.id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+    }
+
+    tx.commit().await?;
+
+    tracing::info!(
+        groups = stats.duplicate_groups,
+        removed = stats.removed,
+        upgraded = stats.upgraded,
+        renormalized = stats.renormalized,
+        "Cache deduplicated"
+    );
+
+    Ok(stats)
+}
+
+/// Picks the preferred version from a group of rows.
+///
+/// Word-level formats beat line-level ones, known formats beat unknown
+/// ones, and among equals the newest row (highest id) wins.
+fn choose_keeper(group: &[&DedupeRow]) -> usize {
+    group
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, row)| {
+            let quality = match &row.format {
+                Some(format) if format.is_word_level() => 2,
+                Some(_) => 1,
+                None => 0,
+            };
+            (quality, row.id)
+        })
+        .map_or(0, |(i, _)| i)
+}
+
+// ============================================================================
+// Legacy Import
+// ============================================================================
+
+/// Entries read from an old `LyricsDB` JSON file (`db.json`).
+#[derive(Debug, Default)]
+pub struct LegacyDb {
+    pub entries: Vec<LegacyEntry>,
+    /// Entries that couldn't be read (missing fields, no lyrics)
+    pub invalid: usize,
+}
+
+/// One track of a legacy database.
+#[derive(Debug, Deserialize)]
+pub struct LegacyEntry {
+    artist: String,
+    title: String,
+    #[serde(default)]
+    album: String,
+    #[serde(default, alias = "length")]
+    duration: Option<f64>,
+    #[serde(alias = "raw_lyrics")]
+    lyrics: LegacyLyrics,
+}
+
+/// Lyrics of a legacy entry: raw LRC text or already parsed lines.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum LegacyLyrics {
+    Lrc(String),
+    Lines(Vec<LegacyLine>),
+}
+
+#[derive(Debug, Deserialize)]
+struct LegacyLine {
+    time: f64,
+    text: String,
+}
+
+impl LegacyEntry {
+    /// The lyrics as LRC text, or `None` when there are none.
+    fn to_lrc(&self) -> Option<String> {
+        let lrc = match &self.lyrics {
+            LegacyLyrics::Lrc(text) => text.clone(),
+            LegacyLyrics::Lines(lines) => lines
+                .iter()
+                .map(|line| format!("[{}]{}\n", crate::lyrics::export::format_lrc_time(line.time), line.text))
+                .collect(),
+        };
+        (!lrc.trim().is_empty()).then_some(lrc)
+    }
+}
+
+/// Counts reported by [`import_legacy`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportStats {
+    /// Entries added to the cache
+    pub imported: usize,
+    /// Entries skipped because the track was already cached
+    pub existing: usize,
+    /// Entries that couldn't be read
+    pub invalid: usize,
+}
+
+/// Reads a legacy `LyricsDB` file.
+///
+/// The entries may be a map keyed by track or a list, either at the top
+/// level or under `"entries"`. Entries that don't parse are counted in
+/// [`LegacyDb::invalid`] instead of failing the whole file.
+pub fn parse_legacy(json: &str) -> Result<LegacyDb, serde_json::Error> {
+    let root: serde_json::Value = serde_json::from_str(json)?;
+    let entries = root.get("entries").unwrap_or(&root);
+    let values: Vec<&serde_json::Value> = match entries {
+        serde_json::Value::Object(map) => map.values().collect(),
+        serde_json::Value::Array(list) => list.iter().collect(),
+        _ => Vec::new(),
+    };
+
+    let mut db = LegacyDb::default();
+    for value in values {
+        match LegacyEntry::deserialize(value) {
+            Ok(entry) if entry.to_lrc().is_some() => db.entries.push(entry),
+            _ => db.invalid += 1,
+        }
+    }
+    Ok(db)
+}
+
+/// Inserts legacy entries into the cache as LRC.
+///
+/// Tracks already in the cache are left alone: their lyrics are at least
+/// as recent as the legacy file's.
+pub async fn import_legacy(db: &LegacyDb) -> Option<Result<ImportStats, sqlx::Error>> {
+    let pool = DB_POOL.get()?;
+    Some(import_legacy_pool(pool, db).await)
+}
+
+async fn import_legacy_pool(pool: &SqlitePool, db: &LegacyDb) -> Result<ImportStats, sqlx::Error> {
+    let mut stats = ImportStats { invalid: db.invalid, ..Default::default() };
+    let mut tx = pool.begin().await?;
+
+    for entry in &db.entries {
+        let (artist, title, album) = (normalize(&entry.artist), normalize(&entry.title), normalize(&entry.album));
+        let exists = sqlx::query("SELECT 1 FROM lyrics WHERE artist = ? AND title = ? AND album = ?")
+            .bind(&artist)
+            .bind(&title)
+            .bind(&album)
+            .fetch_optional(&mut *tx)
+            .await?
+            .is_some();
+        if exists {
+            stats.existing += 1;
+            continue;
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO lyrics (artist, title, album, duration, format, raw_lyrics)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&artist)
+        .bind(&title)
+        .bind(&album)
+        .bind(entry.duration)
+        .bind(LyricsFormat::Lrclib.to_str())
+        .bind(entry.to_lrc())
+        .execute(&mut *tx)
+        .await?;
+        stats.imported += 1;
+    }
+
+    tx.commit().await?;
+    tracing::info!(
+        imported = stats.imported,
+        existing = stats.existing,
+        invalid = stats.invalid,
+        "Legacy database imported"
+    );
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An empty in-memory database with the current schema.
+    async fn test_pool() -> SqlitePool {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        create_schema(&pool).await.unwrap();
+        pool
+    }
+
+    /// Inserts a cache row as an older version might have stored it.
+    async fn insert_row(pool: &SqlitePool, key: (&str, &str, &str), format: &str, preferred: bool) -> i64 {
+        sqlx::query("INSERT INTO lyrics (artist, title, album, format, raw_lyrics, preferred) VALUES (?, ?, ?, ?, '', ?)")
+            .bind(key.0)
+            .bind(key.1)
+            .bind(key.2)
+            .bind(format)
+            .bind(preferred)
+            .execute(pool)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    #[tokio::test]
+    async fn test_dedupe_pool() {
+        let pool = test_pool().await;
+        let richsync = insert_row(&pool, ("DAFT PUNK", " One More Time", ""), "richsync", true).await;
+        let old = insert_row(&pool, ("Daft  Punk", "One More Time", ""), "lrclib", true).await;
+        let new = insert_row(&pool, ("daft punk", "one more time", ""), "lrclib", true).await;
+        let single = insert_row(&pool, ("justice", "genesis", ""), "ttml", false).await;
+
+        let stats = dedupe_pool(&pool).await.unwrap();
+        assert_eq!(stats, DedupeStats { duplicate_groups: 1, removed: 1, upgraded: 1, renormalized: 1 });
+
+        let rows: Vec<(i64, String, String, bool)> = sqlx::query("SELECT id, artist, title, preferred FROM lyrics ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| (row.get("id"), row.get("artist"), row.get("title"), row.get("preferred")))
+            .collect();
+        let track = |id, preferred| (id, "daft punk".to_string(), "one more time".to_string(), preferred);
+        assert_eq!(
+            rows,
+            [
+                // Two rows claimed to be preferred: the word-level one wins
+                track(richsync, true),
+                track(new, false),
+                (single, "justice".to_string(), "genesis".to_string(), true),
+            ]
+        );
+        assert!(!rows.iter().any(|row| row.0 == old));
+
+        assert_eq!(dedupe_pool(&pool).await.unwrap(), DedupeStats::default());
+    }
+
+    #[tokio::test]
+    async fn test_renormalize_keys() {
+        let pool = test_pool().await;
+        insert_row(&pool, ("Daft  Punk", "Da  Funk", ""), "lrclib", true).await;
+        for (artist, provider) in [("daft  punk", "lrclib"), ("daft punk", "amll")] {
+            sqlx::query("INSERT INTO pinned (artist, title, album, provider) VALUES (?, 'da funk', '', ?)")
+                .bind(artist)
+                .bind(provider)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        sqlx::query("PRAGMA user_version = 0").execute(&pool).await.unwrap();
+        create_schema(&pool).await.unwrap();
+
+        let key = cache_key("Daft Punk", "Da Funk", "");
+        let cached: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM lyrics WHERE artist = ? AND title = ?")
+            .bind(&key.0)
+            .bind(&key.1)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(cached, 1);
+        // The pin already stored under the new key is kept
+        assert_eq!(pinned_provider_pool(&pool, &key).await.as_deref(), Some("amll"));
+        let pins: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pinned").fetch_one(&pool).await.unwrap();
+        assert_eq!(pins, 1);
+    }
+
+    #[test]
+    fn every_provider_stores_a_format() {
+        for &name in crate::event::PROVIDERS {
+            assert!(!LyricsFormat::for_provider(name).is_empty(), "{name}");
+        }
+        assert_eq!(LyricsFormat::for_provider("exec:/bin/true"), &[LyricsFormat::Exec]);
+    }
+
+    fn row(id: i64, format: Option<LyricsFormat>) -> DedupeRow {
+        DedupeRow {
+            id,
+            artist: String::new(),
+            title: String::new(),
+            album: String::new(),
+            format,
+            preferred: true,
+        }
+    }
+
+    #[test]
+    fn test_choose_keeper_prefers_word_level() {
+        // Newer line-level rows lose to an older richsync one
+        let group = [
+            row(1, Some(LyricsFormat::Richsync)),
+            row(2, Some(LyricsFormat::Lrclib)),
+            row(3, Some(LyricsFormat::Subtitles)),
+        ];
+        assert_eq!(choose_keeper(&group.each_ref()), 0);
+
+        // Same quality: newest wins; unknown formats lose
+        let group = [row(4, Some(LyricsFormat::Lrclib)), row(5, Some(LyricsFormat::Subtitles)), row(6, None)];
+        assert_eq!(choose_keeper(&group.each_ref()), 1);
+
+        assert_eq!(normalize("  Arctic   Monkeys "), "arctic monkeys");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_stats() {
+        let pool = test_pool().await;
+
+        let ms = std::time::Duration::from_millis;
+        for (provider, outcome, latency) in [
+            ("lrclib", FetchOutcome::Missed, ms(300)),
+            ("musixmatch", FetchOutcome::Found, ms(800)),
+            ("lrclib", FetchOutcome::Found, ms(100)),
+            ("musixmatch", FetchOutcome::Found, ms(400)),
+            ("amll", FetchOutcome::Failed, ms(50)),
+        ] {
+            record_fetch_pool(&pool, "Artist", "Title", "", provider, outcome, latency).await.unwrap();
+        }
+
+        let stats = fetch_stats_pool(&pool).await.unwrap();
+        let names: Vec<&str> = stats.iter().map(|s| s.provider.as_str()).collect();
+        assert_eq!(names, ["musixmatch", "lrclib", "amll"]);
+        assert_eq!((stats[1].fetches, stats[1].found, stats[1].missed), (2, 1, 1));
+        assert_eq!(stats[0].avg_latency_ms, 600.0);
+        assert_eq!(stats[2].failed, 1);
+
+        // Everything above was recorded just now; age two rows past retention
+        sqlx::query("UPDATE events SET at = 0 WHERE provider = 'lrclib'").execute(&pool).await.unwrap();
+        prune_events(&pool, chrono::Utc::now().timestamp()).await.unwrap();
+        let names: Vec<String> = fetch_stats_pool(&pool).await.unwrap().into_iter().map(|s| s.provider).collect();
+        assert_eq!(names, ["musixmatch", "amll"]);
+    }
+
+    #[tokio::test]
+    async fn test_import_legacy() {
+        let pool = test_pool().await;
+        sqlx::query("INSERT INTO lyrics (artist, title, album, format, raw_lyrics) VALUES ('a', 'cached', '', 'ttml', '<tt/>')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let db = parse_legacy(
+            r#"{"entries": {
+                "a|lines|": {"artist": "A", "title": "Lines", "length": 180.0,
+                             "lyrics": [{"time": 1.5, "text": "one"}, {"time": 62.0, "text": "two"}]},
+                "a|lrc|x": {"artist": "A", "title": "LRC", "album": "X", "lyrics": "[00:01.00]hi\n"},
+                "a|cached|": {"artist": "A", "title": "Cached", "lyrics": "[00:01.00]old\n"},
+                "a|empty|": {"artist": "A", "title": "Empty", "lyrics": []},
+                "broken": {"title": "No artist", "lyrics": "[00:01.00]x"}
+            }}"#,
+        )
+        .unwrap();
+
+        let stats = import_legacy_pool(&pool, &db).await.unwrap();
+        assert_eq!(stats, ImportStats { imported: 2, existing: 1, invalid: 2 });
+
+        let row = sqlx::query("SELECT duration, format, raw_lyrics FROM lyrics WHERE title = 'lines'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(row.get::<Option<f64>, _>("duration"), Some(180.0));
+        assert_eq!(row.get::<String, _>("format"), "lrclib");
+        assert_eq!(row.get::<String, _>("raw_lyrics"), "[00:01.50]one\n[01:02.00]two\n");
+
+        let cached: String = sqlx::query("SELECT raw_lyrics FROM lyrics WHERE title = 'cached'")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get("raw_lyrics");
+        assert_eq!(cached, "<tt/>");
+
+        // A bare list works too
+        let db = parse_legacy(r#"[{"artist": "B", "title": "T", "lyrics": "[00:00.00]x"}]"#).unwrap();
+        assert_eq!(db.entries.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_columns_are_added_to_old_databases() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::query(
+            "CREATE TABLE lyrics (id INTEGER PRIMARY KEY AUTOINCREMENT, artist TEXT NOT NULL, title TEXT NOT NULL, \
+             album TEXT NOT NULL, duration REAL, format TEXT NOT NULL, raw_lyrics TEXT NOT NULL)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO lyrics (artist, title, album, duration, format, raw_lyrics) VALUES ('a', 't', '', 200.0, 'lrclib', '')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        create_schema(&pool).await.unwrap();
+        create_schema(&pool).await.unwrap();
+        sqlx::query(
+            "INSERT INTO lyrics (artist, title, album, duration, provider_duration, etag, format, raw_lyrics) \
+             VALUES ('b', 't', '', 200.0, 193.0, '\"v1\"', 'lrclib', '')",
+        )
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let durations: Vec<f64> = sqlx::query("SELECT COALESCE(provider_duration, duration) AS duration FROM lyrics ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| row.get("duration"))
+            .collect();
+        assert_eq!(durations, [200.0, 193.0]);
+
+        assert_eq!(fetch_validators_pool(&pool, "A", "T", "").await, None);
+        let validators = fetch_validators_pool(&pool, "B", "T", "").await.unwrap();
+        assert_eq!(validators.etag.as_deref(), Some("\"v1\""));
+
+        // The old row has no `stored_at` and counts as stale
+        let day = std::time::Duration::from_secs(86_400);
+        assert!(is_stale_pool(&pool, "a", "t", "", day, 0).await);
+        sqlx::query("UPDATE lyrics SET stored_at = 1000 WHERE artist = 'a'").execute(&pool).await.unwrap();
+        assert!(!is_stale_pool(&pool, "a", "t", "", day, 1000 + 3600).await);
+        assert!(is_stale_pool(&pool, "a", "t", "", day, 1000 + 2 * 86_400).await);
+        assert!(!is_stale_pool(&pool, "missing", "t", "", day, 0).await);
+
+        assert!(!upgrade_failed_recently_pool(&pool, "a", "t", "", day, 1000).await);
+        sqlx::query("UPDATE lyrics SET upgrade_failed_at = 1000 WHERE artist = 'a'").execute(&pool).await.unwrap();
+        assert!(upgrade_failed_recently_pool(&pool, "a", "t", "", day, 1000 + 3600).await);
+        assert!(!upgrade_failed_recently_pool(&pool, "a", "t", "", day, 1000 + 2 * 86_400).await);
+    }
+
+    #[tokio::test]
+    async fn test_reject_provider() {
+        let pool = test_pool().await;
+        sqlx::query("INSERT INTO lyrics (artist, title, album, format, raw_lyrics) VALUES ('a', 't', '', 'lrclib', 'x')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let key = ("a".to_string(), "t".to_string(), String::new(), "lrclib".to_string());
+        reject_provider_pool(&pool, &key).await.unwrap();
+        reject_provider_pool(&pool, &key).await.unwrap();
+
+        assert_eq!(rejected_providers_pool(&pool, "a", "t", "").await, ["lrclib"]);
+        assert!(rejected_providers_pool(&pool, "a", "other", "").await.is_empty());
+        let cached: i64 = sqlx::query("SELECT COUNT(*) AS n FROM lyrics").fetch_one(&pool).await.unwrap().get("n");
+        assert_eq!(cached, 0);
+
+        assert!(forget_rejections_pool(&pool, &("a".to_string(), "t".to_string(), String::new())).await.unwrap());
+        assert!(rejected_providers_pool(&pool, "a", "t", "").await.is_empty());
+        assert!(!forget_rejections_pool(&pool, &("a".to_string(), "t".to_string(), String::new())).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_session_choices() {
+        let mut session = SessionChoices::default();
+        reject_provider(&mut session, "A", "T", "", "lrclib").await;
+        assert_eq!(rejected_providers(&session, "a", "t", "").await, ["lrclib"]);
+        assert!(rejected_providers(&SessionChoices::default(), "a", "t", "").await.is_empty());
+        assert!(forget_rejections(&mut session, "a", "t", "").await);
+        assert!(rejected_providers(&session, "a", "t", "").await.is_empty());
+
+        pin_provider(&mut session, "A", "T", "", Some("amll")).await;
+        assert_eq!(pinned_provider(&session, "a", "t", "").await.as_deref(), Some("amll"));
+        pin_provider(&mut session, "A", "T", "", None).await;
+        assert_eq!(pinned_provider(&session, "a", "t", "").await, None);
+    }
+
+    #[tokio::test]
+    async fn test_lyrics_versions() {
+        let pool = test_pool().await;
+        let key = ("a".to_string(), "t".to_string(), String::new());
+        let entry = |format, raw: &str| LyricsEntry { duration: None, format, raw_lyrics: raw.to_string() };
+        let store = |entry| {
+            let (pool, key) = (&pool, &key);
+            async move { store_in_database_pool(pool, key, None, &HttpValidators::default(), &entry).await.unwrap() }
+        };
+        let preferred = || async {
+            let row = sqlx::query("SELECT format FROM lyrics WHERE preferred = 1").fetch_one(&pool).await.unwrap();
+            row.get::<String, _>("format")
+        };
+
+        store(entry(LyricsFormat::Lrclib, "old")).await;
+        store(entry(LyricsFormat::Richsync, "{}")).await;
+        store(entry(LyricsFormat::Lrclib, "new")).await;
+        let count: i64 = sqlx::query("SELECT COUNT(*) AS n FROM lyrics").fetch_one(&pool).await.unwrap().get("n");
+        assert_eq!(count, 2);
+        assert_eq!(preferred().await, "lrclib");
+
+        assert!(prefer_version_pool(&pool, &key, LyricsFormat::for_provider("musixmatch")).await.unwrap());
+        assert_eq!(preferred().await, "richsync");
+        assert!(!prefer_version_pool(&pool, &key, LyricsFormat::for_provider("amll")).await.unwrap());
+        assert_eq!(preferred().await, "richsync");
+
+        // Rejecting the preferred version's provider falls back to the other one
+        let rejection = ("a".to_string(), "t".to_string(), String::new(), "musixmatch".to_string());
+        reject_provider_pool(&pool, &rejection).await.unwrap();
+        assert_eq!(preferred().await, "lrclib");
+    }
+
+    #[tokio::test]
+    async fn test_exec_is_stored_apart_from_lrclib() {
+        let pool = test_pool().await;
+        let key = ("a".to_string(), "t".to_string(), String::new());
+        for (format, raw) in [(LyricsFormat::Lrclib, "lrclib"), (LyricsFormat::Exec, "exec")] {
+            let entry = LyricsEntry { duration: None, format, raw_lyrics: raw.to_string() };
+            store_in_database_pool(&pool, &key, None, &HttpValidators::default(), &entry).await.unwrap();
+        }
+
+        let rejection = ("a".to_string(), "t".to_string(), String::new(), "exec:/bin/lyrics".to_string());
+        reject_provider_pool(&pool, &rejection).await.unwrap();
+        let formats: Vec<String> = sqlx::query("SELECT format FROM lyrics")
+            .fetch_all(&pool)
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| row.get("format"))
+            .collect();
+        assert_eq!(formats, ["lrclib"]);
+        assert_eq!(LyricsFormat::Exec.provider(), Provider::Exec);
+    }
+
+    #[tokio::test]
+    async fn test_pin_provider() {
+        let pool = test_pool().await;
+        let key = ("a".to_string(), "t".to_string(), String::new());
+
+        assert_eq!(pinned_provider_pool(&pool, &key).await, None);
+        pin_provider_pool(&pool, &key, Some("lrclib")).await.unwrap();
+        pin_provider_pool(&pool, &key, Some("musixmatch")).await.unwrap();
+        assert_eq!(pinned_provider_pool(&pool, &key).await.as_deref(), Some("musixmatch"));
+        pin_provider_pool(&pool, &key, None).await.unwrap();
+        assert_eq!(pinned_provider_pool(&pool, &key).await, None);
+    }
+}
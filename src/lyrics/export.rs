@@ -0,0 +1,137 @@
+//! Serialization of parsed lyrics back into text formats.
+
+use crate::lyrics::types::LyricLine;
+
+/// Format a timestamp in seconds as LRC `MM:SS.CC`.
+pub fn format_lrc_time(seconds: f64) -> String {
+    let centis = (seconds.max(0.0) * 100.0).round() as u64;
+    format!("{:02}:{:02}.{:02}", centis / 6000, (centis / 100) % 60, centis % 100)
+}
+
+/// Serialize lines as standard LRC (`[MM:SS.CC]text`), dropping word timing.
+pub fn to_lrc(lines: &[LyricLine]) -> String {
+    lines
+        .iter()
+        .map(|line| {
+            let cue = if line.cue { "# cue\n" } else { "" };
+            format!("{}[{}]{}\n", cue, format_lrc_time(line.time), line.text)
+        })
+        .collect()
+}
+
+/// Plain text of all lines, one per line.
+pub fn to_plain(lines: &[LyricLine]) -> String {
+    lines.iter().map(|line| format!("{}\n", line.text)).collect()
+}
+
+/// How long the last line stays on screen (in seconds) when nothing ends it.
+const ASS_LAST_LINE_DURATION: f64 = 5.0;
+
+/// Format a timestamp in seconds as ASS `H:MM:SS.CC`.
+fn format_ass_time(seconds: f64) -> String {
+    let centis = (seconds.max(0.0) * 100.0).round() as u64;
+    format!("{}:{:02}:{:02}.{:02}", centis / 360_000, (centis / 6000) % 60, (centis / 100) % 60, centis % 100)
+}
+
+/// Duration in seconds as whole centiseconds, for `\k` tags.
+fn centis(seconds: f64) -> u64 {
+    (seconds.max(0.0) * 100.0).round() as u64
+}
+
+/// Text with ASS override braces and newlines neutralized.
+fn ass_text(text: &str) -> String {
+    text.replace('{', "(").replace('}', ")").replace('\n', "\\N")
+}
+
+/// Serialize lines as ASS karaoke subtitles.
+///
+/// Word-synced lines get one `{\k}` tag per word (gaps between words become
+/// empty syllables), so players sweep the highlight word by word. Other
+/// lines are shown plainly from their start to the next line.
+pub fn to_ass(lines: &[LyricLine], title: &str) -> String {
+    let mut out = format!(
+        "[Script Info]\n\
+         Title: {}\n\
+         ScriptType: v4.00+\n\
+         PlayResX: 1920\n\
+         PlayResY: 1080\n\
+         WrapStyle: 0\n\
+         \n\
+         [V4+ Styles]\n\
+         Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, \
+         Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, \
+         Alignment, MarginL, MarginR, MarginV, Encoding\n\
+         Style: Default,Sans,64,&H0000D7FF,&H00FFFFFF,&H00000000,&H80000000,-1,0,0,0,100,100,0,0,1,3,1,2,60,60,80,1\n\
+         \n\
+         [Events]\n\
+         Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n",
+        ass_text(title)
+    );
+
+    for (i, line) in lines.iter().enumerate() {
+        let next_start = lines.get(i + 1).map(|next| next.time);
+        let words = line.words.as_deref().filter(|words| !words.is_empty());
+        let end = words
+            .and_then(|words| words.last())
+            .map(|last| last.end)
+            .or(next_start)
+            .unwrap_or(line.time + ASS_LAST_LINE_DURATION)
+            .max(line.time);
+
+        let text = match words {
+            Some(words) => {
+                let mut text = String::new();
+                let mut cursor = line.time;
+                for (j, word) in words.iter().enumerate() {
+                    if word.start > cursor && centis(word.start - cursor) > 0 {
+                        text.push_str(&format!("{{\\k{}}}", centis(word.start - cursor)));
+                    }
+                    let separator = if j + 1 < words.len() { " " } else { "" };
+                    text.push_str(&format!(
+                        "{{\\k{}}}{}{}",
+                        centis(word.end - word.start.max(cursor)),
+                        ass_text(&word.text),
+                        separator
+                    ));
+                    cursor = word.end.max(cursor);
+                }
+                text
+            }
+            None => ass_text(&line.text),
+        };
+        out.push_str(&format!(
+            "Dialogue: 0,{},{},Default,,0,0,0,,{}\n",
+            format_ass_time(line.time),
+            format_ass_time(end),
+            text
+        ));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_lrc_round_trip() {
+        let lrc = "[00:29.26]Have you got colour in your cheeks?\n# cue\n[01:04.05]Do you ever get that fear\n";
+        let lines = crate::lyrics::parse::parse_synced_lyrics(lrc);
+        assert!(!lines[0].cue && lines[1].cue);
+        assert_eq!(to_lrc(&lines), lrc);
+        assert_eq!(format_lrc_time(3599.999), "60:00.00");
+    }
+
+    #[test]
+    fn test_to_ass_karaoke() {
+        let mut lines = crate::lyrics::parse::parse_yrc("[10000,4000](10000,1500,0)Two (12000,2000,0)words").unwrap();
+        lines.push(LyricLine { time: 20.0, text: "Plain {line}".into(), ..Default::default() });
+
+        let ass = to_ass(&lines, "Artist - Song");
+        assert!(ass.starts_with("[Script Info]\nTitle: Artist - Song\n"));
+        assert!(ass.contains("Dialogue: 0,0:00:10.00,0:00:14.00,Default,,0,0,0,,{\\k150}Two {\\k50}{\\k200}words\n"));
+        assert!(ass.contains("Dialogue: 0,0:00:20.00,0:00:25.00,Default,,0,0,0,,Plain (line)\n"));
+        assert_eq!(format_ass_time(3725.5), "1:02:05.50");
+    }
+}
@@ -0,0 +1,261 @@
+//! MusicBrainz metadata normalization.
+//!
+//! With `--musicbrainz`, tracks that miss the local cache are first resolved
+//! against the MusicBrainz recording search so providers are queried with
+//! canonical artist/title names and the recording's length. Resolutions
+//! (including the recording and artist MBIDs) are cached in the database,
+//! so each track is looked up at most once.
+
+use crate::lyrics::types::{http_client, LyricsError};
+use crate::mpris::TrackMetadata;
+use serde_json::{json, Value};
+
+const SEARCH_URL: &str = "https://musicbrainz.org/ws/2/recording";
+
+/// A track resolved against MusicBrainz.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MusicBrainzMatch {
+    /// Recording MBID
+    pub recording_id: String,
+    /// MBID of the first credited artist
+    pub artist_id: Option<String>,
+    /// Canonical artist credit (e.g. "Simon & Garfunkel")
+    pub artist: String,
+    /// Canonical recording title
+    pub title: String,
+    /// Recording length in seconds (if known)
+    pub length: Option<f64>,
+}
+
+impl MusicBrainzMatch {
+    /// Returns `meta` with canonical names and length substituted.
+    pub fn apply(&self, meta: &TrackMetadata) -> TrackMetadata {
+        TrackMetadata {
+            artist: self.artist.clone(),
+            title: self.title.clone(),
+            length: self.length.or(meta.length),
+            ..meta.clone()
+        }
+    }
+}
+
+/// Resolves `meta` to canonical MusicBrainz metadata.
+///
+/// Uses the database cache when possible. On any failure (no match, network
+/// error, rate limiting) the input metadata is returned unchanged.
+pub async fn normalize(meta: &TrackMetadata) -> TrackMetadata {
+    if meta.title.is_empty() || meta.artist.is_empty() {
+        return meta.clone();
+    }
+
+    if let Some(cached) =
+        crate::lyrics::database::fetch_musicbrainz_match(&meta.artist, &meta.title, &meta.album).await
+    {
+        tracing::debug!(recording = %cached.recording_id, "MusicBrainz match from cache");
+        return cached.apply(meta);
+    }
+
+    match search_recording(meta).await {
+        Ok(Some(mb)) => {
+            tracing::debug!(
+                recording = %mb.recording_id,
+                artist = %mb.artist,
+                title = %mb.title,
+                "Resolved track via MusicBrainz"
+            );
+            crate::lyrics::database::store_musicbrainz_match(&meta.artist, &meta.title, &meta.album, &mb)
+                .await;
+            mb.apply(meta)
+        }
+        Ok(None) => {
+            tracing::debug!(title = %meta.title, artist = %meta.artist, "No MusicBrainz match");
+            meta.clone()
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "MusicBrainz lookup failed");
+            meta.clone()
+        }
+    }
+}
+
+/// Searches MusicBrainz recordings and picks the best candidate using the
+/// same similarity scoring as the providers.
+async fn search_recording(meta: &TrackMetadata) -> Result<Option<MusicBrainzMatch>, LyricsError> {
+    let query = search_query(meta);
+    let resp = http_client()
+        .get(SEARCH_URL)
+        .query(&[("query", query.as_str()), ("fmt", "json"), ("limit", "10")])
+        // MusicBrainz asks clients to identify themselves with a contact
+        .header(reqwest::header::USER_AGENT, crate::lyrics::providers::musicbrainz_user_agent())
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        return Err(LyricsError::Api(format!("musicbrainz: HTTP {}", resp.status())));
+    }
+
+    let body: Value = resp.json().await?;
+    Ok(best_match(&body, meta))
+}
+
+/// Lucene query of the recording search for `meta`.
+fn search_query(meta: &TrackMetadata) -> String {
+    format!(
+        "recording:\"{}\" AND artist:\"{}\"",
+        escape_lucene(&meta.title),
+        escape_lucene(&meta.artist)
+    )
+}
+
+/// The recording in a search response `body` that best matches `meta`.
+fn best_match(body: &Value, meta: &TrackMetadata) -> Option<MusicBrainzMatch> {
+    let recordings = body
+        .get("recordings")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    // Flatten into the generic candidate shape understood by `similarity`
+    let candidates: Vec<Value> = recordings.iter().map(to_candidate).collect();
+
+    let album = (!meta.album.is_empty()).then_some(meta.album.as_str());
+    let (idx, _score) =
+        crate::lyrics::similarity::find_best_song_match(&candidates, &meta.title, &meta.artist, album, meta.length)?;
+
+    let recording = &recordings[idx];
+    let candidate = &candidates[idx];
+
+    recording.get("id").and_then(|v| v.as_str()).map(|id| MusicBrainzMatch {
+        recording_id: id.to_string(),
+        artist_id: recording
+            .pointer("/artist-credit/0/artist/id")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        artist: candidate["artist"].as_str().unwrap_or_default().to_string(),
+        title: candidate["title"].as_str().unwrap_or_default().to_string(),
+        length: recording.get("length").and_then(|v| v.as_f64()).map(|ms| ms / 1000.0),
+    })
+}
+
+/// Converts a MusicBrainz recording into a similarity candidate.
+fn to_candidate(recording: &Value) -> Value {
+    // The full credit is the concatenation of names and join phrases
+    let artist: String = recording
+        .get("artist-credit")
+        .and_then(|v| v.as_array())
+        .map(|credits| {
+            credits
+                .iter()
+                .map(|c| {
+                    let name = c.get("name").and_then(|v| v.as_str()).unwrap_or("");
+                    let join = c.get("joinphrase").and_then(|v| v.as_str()).unwrap_or("");
+                    format!("{}{}", name, join)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    json!({
+        "title": recording.get("title").and_then(|v| v.as_str()).unwrap_or(""),
+        "artist": artist,
+        "album": recording.pointer("/releases/0/title").and_then(|v| v.as_str()),
+        "durationMs": recording.get("length").and_then(|v| v.as_f64()),
+    })
+}
+
+/// Escapes Lucene query syntax characters in a search term.
+fn escape_lucene(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if "+-&|!(){}[]^\"~*?:\\/".contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(artist: &str, title: &str, length: Option<f64>) -> TrackMetadata {
+        TrackMetadata { artist: artist.into(), title: title.into(), length, ..Default::default() }
+    }
+
+    /// A trimmed `/ws/2/recording?fmt=json` response.
+    fn response() -> Value {
+        json!({
+            "recordings": [
+                {
+                    "id": "rec-cover",
+                    "title": "The Boxer",
+                    "length": 262000,
+                    "artist-credit": [
+                        { "name": "Mumford & Sons", "joinphrase": " feat. ", "artist": { "id": "art-mumford" } },
+                        { "name": "Jerry Douglas", "artist": { "id": "art-douglas" } }
+                    ]
+                },
+                {
+                    "id": "rec-live",
+                    "title": "The Boxer",
+                    "length": 345000,
+                    "artist-credit": [{ "name": "Simon & Garfunkel", "artist": { "id": "art-sg" } }],
+                    "releases": [{ "title": "The Concert in Central Park" }]
+                },
+                {
+                    "id": "rec-studio",
+                    "title": "The Boxer",
+                    "length": 308000,
+                    "artist-credit": [{ "name": "Simon & Garfunkel", "artist": { "id": "art-sg" } }],
+                    "releases": [{ "title": "Bridge Over Troubled Water" }]
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_search_query() {
+        let meta = track("AC/DC", "Whole Lotta Rosie (Live)", None);
+        assert_eq!(search_query(&meta), r#"recording:"Whole Lotta Rosie \(Live\)" AND artist:"AC\/DC""#);
+    }
+
+    #[test]
+    fn test_best_match() {
+        let meta = track("simon & garfunkel", "the boxer", Some(309.0));
+        let best = best_match(&response(), &meta).unwrap();
+        assert_eq!(
+            best,
+            MusicBrainzMatch {
+                recording_id: "rec-studio".into(),
+                artist_id: Some("art-sg".into()),
+                artist: "Simon & Garfunkel".into(),
+                title: "The Boxer".into(),
+                length: Some(308.0),
+            }
+        );
+
+        assert_eq!(to_candidate(&response()["recordings"][0])["artist"], "Mumford & Sons feat. Jerry Douglas");
+        assert!(best_match(&json!({ "recordings": [] }), &meta).is_none());
+        assert!(best_match(&response(), &track("Justice", "Genesis", None)).is_none());
+    }
+
+    #[test]
+    fn test_apply() {
+        let mb = MusicBrainzMatch {
+            recording_id: "rec".into(),
+            artist_id: None,
+            artist: "Simon & Garfunkel".into(),
+            title: "The Boxer".into(),
+            length: None,
+        };
+        let meta = TrackMetadata { album: "Greatest Hits".into(), ..track("simon and garfunkel", "boxer", Some(309.0)) };
+        let applied = mb.apply(&meta);
+        assert_eq!((applied.artist.as_str(), applied.title.as_str()), ("Simon & Garfunkel", "The Boxer"));
+        // The player's length and album stay when MusicBrainz has none to offer
+        assert_eq!((applied.length, applied.album.as_str()), (Some(309.0), "Greatest Hits"));
+
+        let applied = MusicBrainzMatch { length: Some(308.0), ..mb }.apply(&meta);
+        assert_eq!(applied.length, Some(308.0));
+    }
+}
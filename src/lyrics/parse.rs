@@ -1,4 +1,4 @@
-use crate::lyrics::types::LyricLine;
+use crate::lyrics::types::{LyricLine, WordTiming};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde_json::Value;
@@ -17,12 +17,22 @@ static SYNCED_LYRICS_RE: Lazy<Regex> =
 /// Example input:
 /// ```text
 /// [00:29.26]Have you got colour in your cheeks?
+/// # cue
 /// [00:34.27]Do you ever get that fear
 /// ```
+///
+/// A `# cue` comment marks the next timed line as a cue; other comments
+/// are skipped.
 pub fn parse_synced_lyrics(synced: &str) -> Vec<LyricLine> {
+    let mut cue = false;
     synced
         .lines()
         .flat_map(|line| {
+            if let Some(comment) = line.trim().strip_prefix('#') {
+                cue |= comment.trim().eq_ignore_ascii_case("cue");
+                return Vec::new();
+            }
+
             let matches: Vec<_> = SYNCED_LYRICS_RE.captures_iter(line).collect();
             if matches.is_empty() {
                 return Vec::new();
@@ -32,6 +42,7 @@ pub fn parse_synced_lyrics(synced: &str) -> Vec<LyricLine> {
             if text.is_empty() {
                 return Vec::new();
             }
+            let cue = std::mem::take(&mut cue);
 
             matches
                 .into_iter()
@@ -46,6 +57,9 @@ pub fn parse_synced_lyrics(synced: &str) -> Vec<LyricLine> {
                         time,
                         text: text.clone(),
                         words: None,
+                        translation: None,
+                        agent: None,
+                        cue,
                     }
                 })
                 .collect()
@@ -53,6 +67,39 @@ pub fn parse_synced_lyrics(synced: &str) -> Vec<LyricLine> {
         .collect()
 }
 
+/// Seconds per line of unsynced lyrics when the track length is unknown.
+pub const PLAIN_LINE_SECONDS: f64 = 3.0;
+
+/// Time unsynced (plain) lyrics for teleprompter mode (`--teleprompter`).
+///
+/// Non-empty lines are spread evenly over `duration`, so the lyrics scroll
+/// at a steady speed from the start of the track to its end; without a
+/// duration every line gets [`PLAIN_LINE_SECONDS`].
+pub fn time_plain_lyrics(plain: &str, duration: Option<f64>) -> Vec<LyricLine> {
+    let texts: Vec<&str> = plain
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .take(MAX_LYRIC_LINES)
+        .collect();
+    let step = duration
+        .filter(|d| d.is_finite() && *d > 0.0)
+        .map_or(PLAIN_LINE_SECONDS, |d| d / texts.len().max(1) as f64);
+
+    texts
+        .into_iter()
+        .enumerate()
+        .map(|(i, text)| LyricLine {
+            time: i as f64 * step,
+            text: text.to_string(),
+            words: None,
+            translation: None,
+            agent: None,
+            cue: false,
+        })
+        .collect()
+}
+
 /// Parse Musixmatch subtitle_body JSON into lyric lines (line-level timing only).
 ///
 /// Format: `[{"text": "lyrics", "time": {"total": 29.26, ...}}, ...]`
@@ -72,6 +119,9 @@ pub fn parse_subtitle_body(subtitle_body: &str) -> Option<Vec<LyricLine>> {
             time,
             text: text.to_string(),
             words: None, // No word-level timing in subtitle format
+            translation: None,
+            agent: None,
+            cue: false,
         });
     }
 
@@ -84,6 +134,9 @@ pub fn parse_subtitle_body(subtitle_body: &str) -> Option<Vec<LyricLine>> {
 /// 1. Word array: `{"ts": 29.26, "te": 31.59, "x": "text", "words": [{start, end, text}]}`
 /// 2. Character array: `{"ts": 29.26, "te": 31.59, "x": "text", "l": [{c, o}]}`
 ///
+/// Lines that come without either array get synthesized word timings (see
+/// [`synthesize_word_timings`]) so karaoke doesn't drop to line level for them.
+///
 /// Returns parsed lines or None if parsing fails.
 pub fn parse_richsync_body(richsync_body: &str) -> Option<Vec<LyricLine>> {
     let lines_val = serde_json::from_str::<Value>(richsync_body).ok()?;
@@ -110,12 +163,16 @@ pub fn parse_richsync_body(richsync_body: &str) -> Option<Vec<LyricLine>> {
             .unwrap_or("♪");
 
         // Parse word-level timings (if available)
-        let words = parse_word_timings(line, line_start, line_end);
+        let words = parse_word_timings(line, line_start, line_end)
+            .or_else(|| synthesize_word_timings(text, line_start, line_end));
 
         parsed.push(LyricLine {
             time: line_start,
             text: text.to_string(),
             words,
+            translation: None,
+            agent: None,
+            cue: false,
         });
     }
 
@@ -158,7 +215,7 @@ fn parse_word_timings(line: &Value, line_start: f64, line_end: f64) -> Option<Ve
 fn parse_explicit_word_array(words_arr: &[Value], line_start: f64, line_end: f64) -> Option<Vec<crate::lyrics::types::WordTiming>> {
     let word_timings: Vec<crate::lyrics::types::WordTiming> = words_arr
         .iter()
-        .filter_map(|w| {
+        .map(|w| {
             let start = w.get("start").and_then(|v| v.as_f64()).unwrap_or(line_start);
             let end = w.get("end").and_then(|v| v.as_f64()).unwrap_or(start);
             let text = w.get("text").and_then(|v| v.as_str()).unwrap_or("");
@@ -166,7 +223,7 @@ fn parse_explicit_word_array(words_arr: &[Value], line_start: f64, line_end: f64
             // Validate and fix timing
             let final_end = if end <= start { line_end } else { end };
 
-            Some(create_word_timing(start, final_end, text))
+            WordTiming::new(start, final_end, text)
         })
         .collect();
 
@@ -203,7 +260,7 @@ fn parse_character_array(char_arr: &[Value], line_start: f64, line_end: f64) ->
             // Validate timing
             let final_end = if end <= start { line_end } else { end };
 
-            Some(create_word_timing(start, final_end, text))
+            Some(WordTiming::new(start, final_end, text))
         })
         .collect();
 
@@ -214,26 +271,544 @@ fn parse_character_array(char_arr: &[Value], line_start: f64, line_end: f64) ->
     }
 }
 
-/// Create a WordTiming struct with precomputed grapheme boundary data.
-fn create_word_timing(start: f64, end: f64, text: &str) -> crate::lyrics::types::WordTiming {
-    // Precompute grapheme cluster boundaries for efficient Unicode-aware rendering
-    // This avoids storing each grapheme as a separate String (24 bytes overhead each)
-    let mut grapheme_boundaries: Vec<usize> = Vec::new();
-    grapheme_boundaries.push(0);
-    
-    for (byte_offset, _grapheme) in text.grapheme_indices(true) {
-        if byte_offset > 0 {
-            grapheme_boundaries.push(byte_offset);
+/// Spread a line's duration over its words, proportionally to their
+/// grapheme counts (longer words take longer to sing).
+///
+/// Returns None for lines without words (e.g. an instrumental "♪").
+fn synthesize_word_timings(text: &str, line_start: f64, line_end: f64) -> Option<Vec<crate::lyrics::types::WordTiming>> {
+    let words: Vec<&str> = text.split_whitespace().take(MAX_WORDS_PER_LINE).collect();
+    let total: usize = words.iter().map(|w| w.graphemes(true).count()).sum();
+    if words.is_empty() || text.trim() == "♪" || line_end <= line_start {
+        return None;
+    }
+
+    let duration = line_end - line_start;
+    let mut elapsed = 0usize;
+    let timings = words
+        .into_iter()
+        .map(|word| {
+            let start = line_start + duration * elapsed as f64 / total as f64;
+            elapsed += word.graphemes(true).count();
+            let end = line_start + duration * elapsed as f64 / total as f64;
+            WordTiming::new(start, end, word)
+        })
+        .collect();
+    Some(timings)
+}
+
+// ============================================================================
+// TTML (Apple Music / AMLL)
+// ============================================================================
+
+/// XML comments, stripped before tokenizing.
+static XML_COMMENT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<!--.*?-->").unwrap());
+
+/// A single XML tag: `<name attrs>`, `</name>` or `<name attrs/>`.
+static XML_TAG_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"<(/?)([A-Za-z_][\w:.-]*)((?:[^>"']|"[^"]*"|'[^']*')*?)(/?)>"#).unwrap()
+});
+
+/// A tag attribute: `name="value"` or `name='value'`.
+static XML_ATTR_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"([\w:.-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap());
+
+/// XML entity references.
+static XML_ENTITY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"&(#x[0-9a-fA-F]+|#[0-9]+|[a-zA-Z]+);").unwrap());
+
+/// Span roles carrying text that isn't sung (translations, romanization).
+const TTML_SKIPPED_ROLES: &[&str] = &["x-translation", "x-roman"];
+
+/// Parse TTML lyrics (Apple Music / AMLL) into lyric lines.
+///
+/// Each `<p begin=…>` becomes a line. Timed `<span begin=… end=…>` children
+/// become word timings; spans not separated by whitespace (syllables) are
+/// merged into a single word. Translation spans become the line's
+/// translation; romanization spans are skipped. The `ttm:agent` of each
+/// `<p>` (the vocal part in duets) is kept as the line's agent.
+///
+/// Example input:
+/// ```text
+/// <tt><body><div>
+///   <p begin="00:29.260" end="00:31.597">
+///     <span begin="00:29.260" end="00:29.700">Have</span> <span begin="00:29.700" end="00:30.100">you</span>
+///   </p>
+/// </div></body></tt>
+/// ```
+///
+/// Returns parsed lines or None if the input isn't TTML.
+pub fn parse_ttml(ttml: &str) -> Option<Vec<LyricLine>> {
+    if !ttml.contains("<tt") {
+        return None;
+    }
+
+    let source = XML_COMMENT_RE.replace_all(ttml, "");
+    let mut parsed = Vec::new();
+    let mut line: Option<TimedLineBuilder> = None;
+    let mut agent: Option<String> = None;
+    let mut spans: Vec<TtmlSpan> = Vec::new();
+    let mut last_end = 0;
+
+    for tag in XML_TAG_RE.captures_iter(&source) {
+        let whole = tag.get(0)?;
+        let span = spans.last();
+        if let Some(current) = line.as_mut() {
+            let text = decode_xml_entities(&source[last_end..whole.start()]);
+            match span {
+                Some(s) if s.translation => current.translation.push_str(&text),
+                Some(s) if s.skipped => {}
+                _ => current.push_text(&text, span.and_then(|s| Some((s.begin?, s.end?)))),
+            }
+        }
+        last_end = whole.end();
+
+        let closing = !tag[1].is_empty();
+        let self_closing = !tag[4].is_empty();
+        // Ignore namespace prefixes (`tt:p`, `tt:span`)
+        let name = tag[2].rsplit(':').next().unwrap_or("");
+        let attrs = &tag[3];
+
+        match (name, closing) {
+            ("p", false) if !self_closing => {
+                line = Some(TimedLineBuilder::new(ttml_attr(attrs, "begin").and_then(|t| parse_ttml_time(&t))));
+                agent = ttml_attr(attrs, "agent").filter(|a| !a.is_empty());
+                spans.clear();
+            }
+            ("p", true) => {
+                if let Some(mut finished) = line.take().and_then(TimedLineBuilder::finish) {
+                    finished.agent = agent.take();
+                    parsed.push(finished);
+                    if parsed.len() >= MAX_LYRIC_LINES {
+                        tracing::warn!("TTML has more than {} lines, truncating", MAX_LYRIC_LINES);
+                        break;
+                    }
+                }
+            }
+            ("span", false) if line.is_some() && !self_closing => {
+                let parent = spans.last();
+                let role = ttml_attr(attrs, "role").unwrap_or_default();
+                spans.push(TtmlSpan {
+                    begin: ttml_attr(attrs, "begin").and_then(|t| parse_ttml_time(&t)),
+                    end: ttml_attr(attrs, "end").and_then(|t| parse_ttml_time(&t)),
+                    skipped: parent.is_some_and(|s| s.skipped) || TTML_SKIPPED_ROLES.contains(&role.as_str()),
+                    translation: parent.is_some_and(|s| s.translation) || role == "x-translation",
+                });
+            }
+            ("span", true) => {
+                spans.pop();
+            }
+            ("br", _) => {
+                if let Some(current) = line.as_mut() {
+                    current.push_text(" ", None);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    parsed.sort_by(|a, b| a.time.total_cmp(&b.time));
+    Some(parsed)
+}
+
+/// A `<span>` on the open-element stack while parsing TTML.
+struct TtmlSpan {
+    begin: Option<f64>,
+    end: Option<f64>,
+    skipped: bool,
+    /// Inside an `x-translation` span
+    translation: bool,
+}
+
+/// A line being assembled from text fragments, some of them timed.
+///
+/// Shared by the TTML and YRC parsers. Timed fragments not separated by
+/// whitespace (syllables, CJK characters) are merged into one word, since
+/// the karaoke renderer puts a space between words.
+struct TimedLineBuilder {
+    begin: Option<f64>,
+    text: String,
+    /// (start, end, text) of each word so far
+    words: Vec<(f64, f64, String)>,
+    /// Whether whitespace was seen since the last word fragment
+    word_break: bool,
+    /// Translation text (TTML `x-translation` spans)
+    translation: String,
+}
+
+impl TimedLineBuilder {
+    fn new(begin: Option<f64>) -> Self {
+        Self {
+            begin,
+            text: String::new(),
+            words: Vec::new(),
+            word_break: true,
+            translation: String::new(),
         }
     }
-    
-    // Add final boundary for convenience (allows simple slicing: text[boundaries[i]..boundaries[i+1]])
-    grapheme_boundaries.push(text.len());
 
-    crate::lyrics::types::WordTiming {
-        start,
-        end,
+    /// Appends a text fragment, timed as `(start, end)` if known.
+    fn push_text(&mut self, text: &str, timing: Option<(f64, f64)>) {
+        if text.is_empty() {
+            return;
+        }
+
+        self.text.push_str(text);
+
+        let core = text.trim();
+        if text.starts_with(char::is_whitespace) {
+            self.word_break = true;
+        }
+
+        if !core.is_empty() {
+            match timing {
+                Some((start, end)) => {
+                    let joins_previous = !self.word_break && !self.words.is_empty();
+                    match self.words.last_mut() {
+                        Some(last) if joins_previous => {
+                            last.1 = end.max(last.1);
+                            last.2.push_str(core);
+                        }
+                        _ => self.words.push((start, end, core.to_string())),
+                    }
+                    self.word_break = false;
+                }
+                // Untimed text can't be highlighted; just separate the words around it
+                None => self.word_break = true,
+            }
+        }
+
+        if text.ends_with(char::is_whitespace) {
+            self.word_break = true;
+        }
+    }
+
+    fn finish(self) -> Option<LyricLine> {
+        let text = self.text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.is_empty() {
+            return None;
+        }
+
+        let time = self
+            .begin
+            .or_else(|| self.words.first().map(|w| w.0))
+            .unwrap_or(0.0);
+
+        if self.words.len() > MAX_WORDS_PER_LINE {
+            tracing::warn!(
+                "Line has {} words, exceeds limit of {}, truncating",
+                self.words.len(),
+                MAX_WORDS_PER_LINE
+            );
+        }
+
+        let words: Vec<_> = self
+            .words
+            .iter()
+            .take(MAX_WORDS_PER_LINE)
+            .map(|(start, end, word)| {
+                let end = if end <= start { *start } else { *end };
+                WordTiming::new(*start, end, word)
+            })
+            .collect();
+
+        Some(LyricLine {
+            time,
+            text,
+            words: (!words.is_empty()).then_some(words),
+            translation: normalize_whitespace(&self.translation),
+            agent: None,
+            cue: false,
+        })
+    }
+}
+
+/// Collapses runs of whitespace; `None` if nothing is left.
+fn normalize_whitespace(text: &str) -> Option<String> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+/// Looks up an attribute by local name (ignoring any namespace prefix).
+fn ttml_attr(attrs: &str, name: &str) -> Option<String> {
+    XML_ATTR_RE.captures_iter(attrs).find_map(|cap| {
+        let key = cap[1].rsplit(':').next().unwrap_or("");
+        if key != name {
+            return None;
+        }
+        let value = cap.get(2).or_else(|| cap.get(3)).map_or("", |m| m.as_str());
+        Some(decode_xml_entities(value))
+    })
+}
+
+/// Parse a TTML time expression into seconds.
+///
+/// Supports clock times (`HH:MM:SS.mmm`, `MM:SS.mmm`, `SS.mmm`) and offset
+/// times with `h`, `m`, `s` or `ms` units.
+fn parse_ttml_time(value: &str) -> Option<f64> {
+    let value = value.trim();
+
+    let offset = if let Some(ms) = value.strip_suffix("ms") {
+        ms.parse::<f64>().ok().map(|v| v / 1000.0)
+    } else if let Some(s) = value.strip_suffix('s') {
+        s.parse::<f64>().ok()
+    } else if let Some(m) = value.strip_suffix('m') {
+        m.parse::<f64>().ok().map(|v| v * 60.0)
+    } else if let Some(h) = value.strip_suffix('h') {
+        h.parse::<f64>().ok().map(|v| v * 3600.0)
+    } else {
+        None
+    };
+    if offset.is_some() {
+        return offset.filter(|t| t.is_finite() && *t >= 0.0);
+    }
+
+    let parts: Vec<&str> = value.split(':').collect();
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+
+    let mut seconds = 0.0;
+    for part in &parts {
+        seconds = seconds * 60.0 + part.parse::<f64>().ok()?;
+    }
+
+    (seconds.is_finite() && seconds >= 0.0).then_some(seconds)
+}
+
+/// Decode the predefined XML entities and numeric character references.
+fn decode_xml_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+
+    XML_ENTITY_RE
+        .replace_all(text, |cap: &regex::Captures| {
+            let entity = &cap[1];
+            let decoded = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse::<u32>))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            };
+            decoded.map_or_else(|| cap[0].to_string(), String::from)
+        })
+        .into_owned()
+}
+
+// ============================================================================
+// YRC (NetEase)
+// ============================================================================
+
+/// YRC line header: `[start_ms,duration_ms]`
+static YRC_LINE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[(\d+),(\d+)\]").unwrap());
+
+/// YRC word header: `(start_ms,duration_ms,0)`, followed by the word text.
+static YRC_WORD_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\((\d+),(\d+),-?\d+\)").unwrap());
+
+/// Returns `true` if the text looks like NetEase YRC rather than LRC.
+///
+/// Checks the first non-metadata line for a `[start_ms,duration_ms]` header.
+pub fn is_yrc(text: &str) -> bool {
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('{'))
+        .is_some_and(|line| YRC_LINE_RE.is_match(line))
+}
+
+/// Parse NetEase YRC karaoke lyrics into lyric lines with word-level timing.
+///
+/// Example input:
+/// ```text
+/// {"t":0,"c":[{"tx":"作词: "},{"tx":"Someone"}]}
+/// [16210,3460](16210,670,0)Have (16880,410,0)you (17290,380,0)got
+/// ```
+///
+/// Times are in milliseconds. JSON metadata lines (credits) become plain
+/// lines without word timing.
+///
+/// Returns parsed lines or None if no YRC lines were found.
+pub fn parse_yrc(yrc: &str) -> Option<Vec<LyricLine>> {
+    let mut parsed = Vec::new();
+    let mut found_timed = false;
+
+    for raw_line in yrc.lines().map(str::trim) {
+        if parsed.len() >= MAX_LYRIC_LINES {
+            tracing::warn!("YRC has more than {} lines, truncating", MAX_LYRIC_LINES);
+            break;
+        }
+
+        if raw_line.starts_with('{') {
+            if let Some(line) = parse_yrc_metadata_line(raw_line) {
+                parsed.push(line);
+            }
+            continue;
+        }
+
+        let Some(header) = YRC_LINE_RE.captures(raw_line) else {
+            continue;
+        };
+        found_timed = true;
+
+        let start_ms = header[1].parse::<f64>().unwrap_or(0.0);
+        let body = &raw_line[header.get(0)?.end()..];
+        let mut builder = TimedLineBuilder::new(Some(start_ms / 1000.0));
+
+        let word_headers: Vec<_> = YRC_WORD_RE.captures_iter(body).collect();
+        if word_headers.is_empty() {
+            builder.push_text(body, None);
+        }
+
+        for (i, cap) in word_headers.iter().enumerate() {
+            let whole = cap.get(0)?;
+            let text_end = word_headers
+                .get(i + 1)
+                .and_then(|next| next.get(0))
+                .map_or(body.len(), |m| m.start());
+
+            let start = cap[1].parse::<f64>().unwrap_or(start_ms) / 1000.0;
+            let duration = cap[2].parse::<f64>().unwrap_or(0.0) / 1000.0;
+            builder.push_text(&body[whole.end()..text_end], Some((start, start + duration)));
+        }
+
+        if let Some(line) = builder.finish() {
+            parsed.push(line);
+        }
+    }
+
+    if !found_timed {
+        return None;
+    }
+
+    parsed.sort_by(|a, b| a.time.total_cmp(&b.time));
+    Some(parsed)
+}
+
+/// Parse a lyrics file from disk: NetEase YRC when [`is_yrc`] recognizes it,
+/// LRC otherwise.
+pub fn parse_lyrics_file(text: &str) -> Vec<LyricLine> {
+    if is_yrc(text) {
+        parse_yrc(text).unwrap_or_default()
+    } else {
+        parse_synced_lyrics(text)
+    }
+}
+
+/// Parse a YRC JSON metadata line: `{"t":0,"c":[{"tx":"作词: "},{"tx":"Someone"}]}`
+fn parse_yrc_metadata_line(line: &str) -> Option<LyricLine> {
+    let value = serde_json::from_str::<Value>(line).ok()?;
+    let time = value.get("t").and_then(|v| v.as_f64()).unwrap_or(0.0) / 1000.0;
+    let text: String = value
+        .get("c")?
+        .as_array()?
+        .iter()
+        .filter_map(|c| c.get("tx").and_then(|v| v.as_str()))
+        .collect();
+
+    let text = text.trim();
+    (!text.is_empty()).then(|| LyricLine {
+        time,
         text: text.to_string(),
-        grapheme_boundaries,
+        words: None,
+        translation: None,
+        agent: None,
+        cue: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_plain_lyrics() {
+        let plain = "First line\n\n  Second line  \nThird\nFourth\n";
+        let lines = time_plain_lyrics(plain, Some(200.0));
+        let times: Vec<f64> = lines.iter().map(|l| l.time).collect();
+        assert_eq!(times, vec![0.0, 50.0, 100.0, 150.0]);
+        assert_eq!(lines[1].text, "Second line");
+
+        let unknown = time_plain_lyrics(plain, None);
+        assert_eq!(unknown[3].time, 3.0 * PLAIN_LINE_SECONDS);
+        assert!(time_plain_lyrics("\n  \n", Some(100.0)).is_empty());
+    }
+
+    #[test]
+    fn test_parse_ttml() {
+        let ttml = r#"<?xml version="1.0" encoding="utf-8"?>
+<tt xmlns="http://www.w3.org/ns/ttml" xmlns:ttm="http://www.w3.org/ns/ttml#metadata">
+  <body dur="01:00.000"><div>
+    <p begin="00:01.500" end="00:04.000" ttm:agent="v1"><span begin="00:01.500" end="00:02.000">Beau</span><span begin="00:02.000" end="00:02.600">tiful</span> <span begin="00:02.600" end="00:04.000">day&apos;s</span><span ttm:role="x-translation" xml:lang="de">Schöner Tag</span></p>
+    <p begin="1:02:03.25">Line only &amp; no spans</p>
+  </div></body>
+</tt>"#;
+
+        let lines = parse_ttml(ttml).unwrap();
+        assert_eq!(lines.len(), 2);
+
+        assert_eq!(lines[0].time, 1.5);
+        assert_eq!(lines[0].text, "Beautiful day's");
+        let words = lines[0].words.as_ref().unwrap();
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].text, "Beautiful");
+        assert_eq!((words[0].start, words[0].end), (1.5, 2.6));
+        assert_eq!(words[1].text, "day's");
+        assert_eq!(lines[0].translation.as_deref(), Some("Schöner Tag"));
+        assert_eq!(lines[0].agent.as_deref(), Some("v1"));
+        assert_eq!(lines[1].agent, None);
+
+        assert_eq!(lines[1].time, 3723.25);
+        assert_eq!(lines[1].text, "Line only & no spans");
+        assert!(lines[1].words.is_none());
+
+        assert_eq!(parse_ttml_time("12.5s"), Some(12.5));
+        assert_eq!(parse_ttml_time("250ms"), Some(0.25));
+        assert!(parse_ttml("[00:01.00]not ttml").is_none());
+    }
+
+    #[test]
+    fn test_richsync_lines_without_words_get_synthesized_timing() {
+        let body = r#"[{"ts":10.0,"te":14.0,"x":"Hey there"},{"ts":14.0,"te":16.0,"x":"♪"}]"#;
+        let lines = parse_richsync_body(body).unwrap();
+
+        let words = lines[0].words.as_ref().unwrap();
+        assert_eq!(words.len(), 2);
+        assert_eq!((words[0].start, words[0].end), (10.0, 11.5));
+        assert_eq!((words[1].start, words[1].end), (11.5, 14.0));
+        assert!(lines[1].words.is_none());
+    }
+
+    #[test]
+    fn test_parse_yrc() {
+        let yrc = r#"{"t":0,"c":[{"tx":"作词: "},{"tx":"Someone"}]}
+[16210,3460](16210,670,0)Have (16880,410,0)you (17290,380,0)go(17670,200,0)t
+[20000,1000](20000,500,0)夜(20500,500,0)に"#;
+
+        assert!(is_yrc(yrc));
+        assert!(!is_yrc("[00:16.21]Have you got"));
+
+        let lines = parse_yrc(yrc).unwrap();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].text, "作词: Someone");
+        assert!(lines[0].words.is_none());
+
+        assert_eq!(lines[1].time, 16.21);
+        assert_eq!(lines[1].text, "Have you got");
+        let words = lines[1].words.as_ref().unwrap();
+        assert_eq!(words.len(), 3);
+        assert_eq!(words[2].text, "got");
+        assert_eq!(words[2].start, 17.29);
+        assert!((words[2].end - 17.87).abs() < 1e-9);
+
+        // Unspaced CJK characters form a single word
+        assert_eq!(lines[2].words.as_ref().unwrap().len(), 1);
+        assert!(parse_yrc("[00:16.21]Have you got").is_none());
     }
 }
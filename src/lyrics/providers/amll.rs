@@ -0,0 +1,60 @@
+use crate::lyrics::parse::parse_ttml;
+use crate::lyrics::types::{http_client, LyricsError, ProviderResult};
+
+/// Default AMLL TTML DB location (raw GitHub content).
+const DEFAULT_AMLL_DB_URL: &str = "https://raw.githubusercontent.com/Steve-xmh/amll-ttml-db/main";
+
+/// The request [`fetch_lyrics_from_amll`] would send (`--explain`).
+pub fn explain(spotify_id: Option<&str>) -> Vec<String> {
+    match spotify_id.filter(|id| !id.is_empty()) {
+        Some(id) => vec![format!("GET {}", ttml_url(id))],
+        None => vec!["skipped: the player reports no Spotify track ID".to_string()],
+    }
+}
+
+/// URL of the TTML document for a Spotify track.
+fn ttml_url(spotify_id: &str) -> String {
+    let base = super::setting("AMLL_DB_URL", "amll", |s| s.url.as_ref())
+        .unwrap_or_else(|| DEFAULT_AMLL_DB_URL.to_string());
+    format!(
+        "{}/spotify-lyrics/{}.ttml",
+        base.trim_end_matches('/'),
+        urlencoding::encode(spotify_id)
+    )
+}
+
+/// Fetch word-synced TTML lyrics from the AMLL TTML database.
+///
+/// The database is keyed by platform track IDs, so only tracks with a
+/// Spotify ID can be looked up. Set `AMLL_DB_URL` (or `[providers.amll] url`)
+/// to use a mirror.
+pub async fn fetch_lyrics_from_amll(spotify_id: Option<&str>) -> ProviderResult {
+    let Some(id) = spotify_id.filter(|id| !id.is_empty()) else {
+        return Ok((Vec::new(), None));
+    };
+
+    let url = ttml_url(id);
+    let resp = http_client()
+        .get(&url)
+        .send()
+        .await?;
+
+    // 404 means the track isn't in the database - not an error
+    if resp.status().as_u16() == 404 {
+        return Ok((Vec::new(), None));
+    }
+
+    if !resp.status().is_success() {
+        return Err(LyricsError::Api(format!(
+            "amll: HTTP {}",
+            resp.status()
+        )));
+    }
+
+    let body = resp.text().await?;
+    match parse_ttml(&body) {
+        Some(parsed) if !parsed.is_empty() => Ok((parsed, Some(body))),
+        Some(_) => Ok((Vec::new(), None)),
+        None => Err(LyricsError::Api("amll: response is not TTML".to_string())),
+    }
+}
@@ -0,0 +1,113 @@
+//! User scripts as lyrics providers (`--providers exec:/path/to/script`).
+//!
+//! The script is run with the track as arguments:
+//!
+//! ```text
+//! script ARTIST TITLE ALBUM DURATION
+//! ```
+//!
+//! where `DURATION` is in seconds (empty if unknown). The same data is
+//! written to its stdin as JSON: `{"artist","title","album","duration"}`.
+//! The script prints LRC lyrics on stdout; printing nothing or exiting
+//! non-zero means "not found", so the next provider is tried.
+
+use crate::lyrics::parse::parse_synced_lyrics;
+use crate::lyrics::types::{LyricsError, ProviderResult};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+/// Prefix marking an executable provider in the provider list.
+pub const EXEC_PREFIX: &str = "exec:";
+
+/// Scripts taking longer than this are killed.
+const EXEC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The command [`fetch_lyrics_from_exec`] would run (`--explain`).
+pub fn explain(path: &str, artist: &str, title: &str, album: &str, duration: Option<f64>) -> Vec<String> {
+    let duration_arg = duration.map(|d| format!("{:.0}", d)).unwrap_or_default();
+    vec![format!("run {:?} {:?} {:?} {:?} {:?}", path, artist, title, album, duration_arg)]
+}
+
+/// Runs the script at `path` and parses its output.
+///
+/// Failures to start the script are API errors; a timeout, non-zero exit
+/// or output without timestamps yields no lyrics.
+pub async fn fetch_lyrics_from_exec(
+    path: &str,
+    artist: &str,
+    title: &str,
+    album: &str,
+    duration: Option<f64>,
+) -> ProviderResult {
+    let duration_arg = duration.map(|d| format!("{:.0}", d)).unwrap_or_default();
+    let mut child = tokio::process::Command::new(path)
+        .args([artist, title, album, &duration_arg])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| LyricsError::Api(format!("failed to run {}: {}", path, e)))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        let input = serde_json::json!({
+            "artist": artist,
+            "title": title,
+            "album": album,
+            "duration": duration,
+        });
+        // Scripts that ignore stdin may exit before reading it
+        let _ = stdin.write_all(input.to_string().as_bytes()).await;
+    }
+
+    let output = match tokio::time::timeout(EXEC_TIMEOUT, child.wait_with_output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => return Err(LyricsError::Api(format!("{}: {}", path, e))),
+        Err(_) => {
+            tracing::warn!(path = %path, "Lyrics script timed out");
+            return Ok((Vec::new(), None));
+        }
+    };
+
+    if !output.status.success() {
+        tracing::debug!(path = %path, status = %output.status, "Lyrics script found nothing");
+        return Ok((Vec::new(), None));
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout).into_owned();
+    let lines = parse_synced_lyrics(&text);
+    if lines.is_empty() {
+        return Ok((Vec::new(), None));
+    }
+    Ok((lines, Some(text)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_exec_provider_reads_lrc_from_script() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("lyricsmpris-exec-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("provider.sh");
+        // Written and closed before running: exec fails with ETXTBSY while
+        // a write handle is open
+        std::fs::write(&script, "#!/bin/sh\nprintf '[00:01.00]%s by %s\\n' \"$2\" \"$1\"\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let (lines, raw) = fetch_lyrics_from_exec(script.to_str().unwrap(), "Artist", "Song", "", Some(180.0))
+            .await
+            .unwrap();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].text, "Song by Artist");
+        assert!(raw.is_some());
+
+        let missing = fetch_lyrics_from_exec("/nonexistent/script", "a", "b", "", None).await;
+        assert!(missing.is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,122 @@
+use std::path::{Path, PathBuf};
+
+use crate::lyrics::parse::parse_lyrics_file;
+use crate::lyrics::types::ProviderResult;
+
+/// Read lyrics from the standard local lyrics directories.
+///
+/// Looks for `Artist - Title.lrc` (the naming convention shared by many
+/// players and download scripts) in `~/.lyrics` and `$XDG_DATA_HOME/lyrics`
+/// (default `~/.local/share/lyrics`), then for a NetEase `Artist - Title.yrc`.
+/// File names are matched case-insensitively. Unreadable files are logged
+/// and skipped.
+pub async fn fetch_lyrics_from_local_dirs(artist: &str, title: &str) -> ProviderResult {
+    if artist.is_empty() || title.is_empty() {
+        return Ok((Vec::new(), None));
+    }
+
+    let names = local_file_names(artist, title);
+
+    for dir in lyrics_dirs() {
+        for name in &names {
+            let Some(path) = find_case_insensitive(&dir, name).await else {
+                continue;
+            };
+
+            match tokio::fs::read_to_string(&path).await {
+                Ok(text) => {
+                    let parsed = parse_lyrics_file(&text);
+                    if !parsed.is_empty() {
+                        tracing::debug!(path = %path.display(), "Loaded local lyrics file");
+                        return Ok((parsed, Some(text)));
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "Failed to read local lyrics file");
+                }
+            }
+        }
+    }
+
+    Ok((Vec::new(), None))
+}
+
+/// Standard directories searched for local lyrics files, in priority order.
+pub fn lyrics_dirs() -> Vec<PathBuf> {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|h| h.join(".local").join("share")));
+
+    home.map(|h| h.join(".lyrics"))
+        .into_iter()
+        .chain(data_home.map(|d| d.join("lyrics")))
+        .collect()
+}
+
+/// Extensions of local lyrics files, in lookup order: LRC, then NetEase YRC.
+pub const EXTENSIONS: [&str; 2] = ["lrc", "yrc"];
+
+/// Builds the conventional `Artist - Title.lrc` file name.
+///
+/// Path separators can't appear in file names, so they are replaced.
+pub fn local_file_name(artist: &str, title: &str) -> String {
+    let clean = |s: &str| s.trim().replace(['/', '\0'], "_");
+    format!("{} - {}.lrc", clean(artist), clean(title))
+}
+
+/// [`local_file_name`] with each of the [`EXTENSIONS`].
+pub fn local_file_names(artist: &str, title: &str) -> Vec<String> {
+    let name = local_file_name(artist, title);
+    let stem = name.strip_suffix(".lrc").unwrap_or(&name);
+    EXTENSIONS.iter().map(|ext| format!("{stem}.{ext}")).collect()
+}
+
+/// Finds `name` in `dir`, trying the exact name first and then a
+/// case-insensitive scan of the directory.
+async fn find_case_insensitive(dir: &Path, name: &str) -> Option<PathBuf> {
+    let exact = dir.join(name);
+    if tokio::fs::metadata(&exact).await.is_ok_and(|m| m.is_file()) {
+        return Some(exact);
+    }
+
+    let wanted = name.to_lowercase();
+    let mut entries = tokio::fs::read_dir(dir).await.ok()?;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        if entry.file_name().to_string_lossy().to_lowercase() == wanted {
+            return Some(entry.path());
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_file_name() {
+        assert_eq!(local_file_name("Arctic Monkeys", "Do I Wanna Know?"), "Arctic Monkeys - Do I Wanna Know?.lrc");
+        assert_eq!(local_file_name(" AC/DC ", "T.N.T."), "AC_DC - T.N.T..lrc");
+        assert_eq!(local_file_names("Muse", "Uprising"), ["Muse - Uprising.lrc", "Muse - Uprising.yrc"]);
+    }
+
+    #[tokio::test]
+    async fn test_yrc_file_is_loaded() {
+        let dir = std::env::temp_dir().join(format!("lyricsmpris-local-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("muse - uprising.yrc"), "[1000,1500](1000,700,0)Paranoia (1700,800,0)is").unwrap();
+
+        let found = find_case_insensitive(&dir, &local_file_names("Muse", "Uprising")[1]).await;
+        let text = std::fs::read_to_string(found.unwrap()).unwrap();
+        let lines = parse_lyrics_file(&text);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].text, "Paranoia is");
+        assert_eq!(lines[0].words.as_ref().unwrap().len(), 2);
+    }
+}
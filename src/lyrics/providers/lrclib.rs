@@ -1,35 +1,76 @@
 use serde::Deserialize;
 
 use crate::lyrics::parse::parse_synced_lyrics;
-use crate::lyrics::types::{http_client, LyricsError, ProviderResult};
+use crate::lyrics::types::{http_client, HttpValidators, LyricLine, LyricsError};
 
 #[derive(Deserialize)]
 #[allow(non_snake_case)]
 struct LrcLibResponse {
     syncedLyrics: Option<String>,
+    plainLyrics: Option<String>,
+    duration: Option<f64>,
+}
+
+/// Lyrics found on LRCLIB.
+#[derive(Debug, Default)]
+pub struct LrclibLyrics {
+    /// Parsed lines; empty if LRCLIB has no synced lyrics for the track
+    pub lines: Vec<LyricLine>,
+    pub raw: Option<String>,
+    /// Unsynced text, when LRCLIB has only that (`--teleprompter`)
+    pub plain: Option<String>,
+    /// Duration of the matched track in seconds
+    pub duration: Option<f64>,
+    pub validators: HttpValidators,
+}
+
+/// Response to an LRCLIB lookup.
+#[derive(Debug)]
+pub enum LrclibFetch {
+    Lyrics(LrclibLyrics),
+    /// The lyrics the given validators came from are still current
+    NotModified,
 }
 
 /// Fetch synced lyrics from lrclib.net API.
 ///
 /// The lrclib API provides high-quality community-sourced time-synced lyrics.
 /// Matching is improved by including album and duration when available.
+/// LRCLIB only matches durations within 2 seconds, which browser players
+/// often miss, so a lookup without a match is retried once without the
+/// duration.
+/// With `validators` from an earlier response the request is conditional,
+/// and unchanged lyrics come back as [`LrclibFetch::NotModified`].
 pub async fn fetch_lyrics_from_lrclib(
     artist: &str,
     title: &str,
     album: &str,
     duration: Option<f64>,
-) -> ProviderResult {
-    let url = build_lrclib_url(artist, title, album, duration);
-    
-    let resp = http_client()
-        .get(&url)
-        .header("User-Agent", "LyricsMPRIS/1.0")
-        .send()
-        .await?;
+    validators: Option<&HttpValidators>,
+) -> Result<LrclibFetch, LyricsError> {
+    let mut fetch = get(&build_lrclib_url(artist, title, album, duration), validators).await?;
+    if fetch.is_none() && duration.is_some() {
+        tracing::debug!(artist = %artist, title = %title, "No LRCLIB match at this duration, retrying without it");
+        fetch = get(&build_lrclib_url(artist, title, album, None), validators).await?;
+    }
+    Ok(fetch.unwrap_or_else(|| LrclibFetch::Lyrics(LrclibLyrics::default())))
+}
+
+/// Sends one `/api/get` request; `None` if LRCLIB has no such track.
+async fn get(url: &str, validators: Option<&HttpValidators>) -> Result<Option<LrclibFetch>, LyricsError> {
+    let mut request = http_client().get(url);
+    if let Some(validators) = validators {
+        request = validators.apply(request);
+    }
+    let resp = request.send().await?;
+
+    if resp.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(Some(LrclibFetch::NotModified));
+    }
 
     // 404 means no lyrics found - not an error
     if resp.status().as_u16() == 404 {
-        return Ok((Vec::new(), None));
+        return Ok(None);
     }
 
     if !resp.status().is_success() {
@@ -39,17 +80,51 @@ pub async fn fetch_lyrics_from_lrclib(
         )));
     }
 
+    let validators = HttpValidators::from_headers(resp.headers());
     let response: LrcLibResponse = resp.json().await?;
     
     match response.syncedLyrics {
-        Some(synced) if !synced.is_empty() => {
-            let parsed = parse_synced_lyrics(&synced);
-            Ok((parsed, Some(synced)))
-        }
-        _ => Ok((Vec::new(), None)),
+        Some(synced) if !synced.is_empty() => Ok(Some(LrclibFetch::Lyrics(LrclibLyrics {
+            lines: parse_synced_lyrics(&synced),
+            raw: Some(synced),
+            plain: None,
+            duration: response.duration,
+            validators,
+        }))),
+        _ => Ok(Some(LrclibFetch::Lyrics(LrclibLyrics {
+            plain: response.plainLyrics.filter(|plain| !plain.trim().is_empty()),
+            duration: response.duration,
+            ..LrclibLyrics::default()
+        }))),
+    }
+}
+
+/// The request [`fetch_lyrics_from_lrclib`] would send (`--explain`).
+pub fn explain(
+    artist: &str,
+    title: &str,
+    album: &str,
+    duration: Option<f64>,
+    validators: Option<&HttpValidators>,
+) -> Vec<String> {
+    let headers: Vec<String> = validators.map_or_else(Vec::new, |validators| {
+        let etag = validators.etag.iter().map(|etag| format!("  If-None-Match: {}", etag));
+        let date = validators.last_modified.iter().map(|date| format!("  If-Modified-Since: {}", date));
+        etag.chain(date).collect()
+    });
+    let mut requests = vec![format!("GET {}", build_lrclib_url(artist, title, album, duration))];
+    requests.extend(headers.iter().cloned());
+    if duration.is_some() {
+        requests.push(format!("if not found: GET {}", build_lrclib_url(artist, title, album, None)));
+        requests.extend(headers);
     }
+    requests
 }
 
+/// Default LRCLIB instance; override with `LRCLIB_URL` or `[providers.lrclib] url`
+/// (e.g. a `lyricsmpris serve` host).
+const DEFAULT_LRCLIB_URL: &str = "https://lrclib.net";
+
 /// Build lrclib API URL with query parameters.
 fn build_lrclib_url(artist: &str, title: &str, album: &str, duration: Option<f64>) -> String {
     let mut params = vec![
@@ -66,5 +141,7 @@ fn build_lrclib_url(artist: &str, title: &str, album: &str, duration: Option<f64
         params.push(format!("duration={}", d.round() as i64));
     }
 
-    format!("https://lrclib.net/api/get?{}", params.join("&"))
+    let base = super::setting("LRCLIB_URL", "lrclib", |s| s.url.as_ref())
+        .unwrap_or_else(|| DEFAULT_LRCLIB_URL.to_string());
+    format!("{}/api/get?{}", base.trim_end_matches('/'), params.join("&"))
 }
@@ -1,5 +1,50 @@
+pub mod amll;
+pub mod exec;
+pub mod local;
 pub mod lrclib;
 pub mod musixmatch;
 
+pub use amll::fetch_lyrics_from_amll;
+pub use exec::fetch_lyrics_from_exec;
+pub use local::fetch_lyrics_from_local_dirs;
 pub use lrclib::fetch_lyrics_from_lrclib;
 pub use musixmatch::fetch_lyrics_from_musixmatch_usertoken;
+
+use crate::config::{ProviderSettings, ProvidersConfig};
+use std::sync::OnceLock;
+
+/// Stands in for tokens in requests printed by `--explain`.
+const REDACTED: &str = "REDACTED";
+
+/// Provider settings from the config file, set once at startup.
+static SETTINGS: OnceLock<ProvidersConfig> = OnceLock::new();
+
+/// Installs the `[providers]` config section. Later calls are ignored.
+pub fn configure(config: ProvidersConfig) {
+    let _ = SETTINGS.set(config);
+}
+
+/// `User-Agent` of provider requests (see [`ProvidersConfig::user_agent`]).
+pub(crate) fn user_agent() -> String {
+    SETTINGS.get().map_or_else(|| ProvidersConfig::default().user_agent(), ProvidersConfig::user_agent)
+}
+
+/// `User-Agent` of MusicBrainz requests (see [`ProvidersConfig::musicbrainz_user_agent`]).
+pub(crate) fn musicbrainz_user_agent() -> String {
+    SETTINGS
+        .get()
+        .map_or_else(|| ProvidersConfig::default().musicbrainz_user_agent(), ProvidersConfig::musicbrainz_user_agent)
+}
+
+/// Resolves a provider option: the environment variable `env` if set and
+/// non-empty, else the config-file value picked by `field`.
+pub(crate) fn setting(
+    env: &str,
+    provider: &str,
+    field: impl FnOnce(&ProviderSettings) -> Option<&String>,
+) -> Option<String> {
+    std::env::var(env)
+        .ok()
+        .filter(|v| !v.is_empty())
+        .or_else(|| SETTINGS.get()?.settings(provider).and_then(field).cloned())
+}
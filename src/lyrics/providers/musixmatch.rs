@@ -1,8 +1,25 @@
 use serde_json::Value;
-use std::env;
 use reqwest::Client;
 
-use crate::lyrics::types::{http_client, LyricLine, ProviderResult};
+use crate::lyrics::types::{http_client, LyricLine, LyricsError, ProviderResult};
+
+/// Recognizes Musixmatch's authentication failures.
+///
+/// They arrive as HTTP 200 with `status_code: 401` in the message header;
+/// the `hint` tells an expired token (`renew`) from rate limiting
+/// (`captcha`). Both are turned into a one-line message for the user.
+fn auth_error(json: &Value) -> Option<LyricsError> {
+    let header = json.pointer("/message/header")?;
+    if header.get("status_code").and_then(|v| v.as_i64()) != Some(401) {
+        return None;
+    }
+    let message = match header.get("hint").and_then(|v| v.as_str()) {
+        Some("captcha") => "Musixmatch wants a captcha (too many requests) — wait a while or get a new MUSIXMATCH_USERTOKEN",
+        Some("renew") => "Musixmatch token expired — get a new MUSIXMATCH_USERTOKEN",
+        _ => "Musixmatch rejected the token — check MUSIXMATCH_USERTOKEN",
+    };
+    Some(LyricsError::Api(message.to_string()))
+}
 
 /// Fetch lyrics using Musixmatch desktop "usertoken" (apic-desktop.musixmatch.com).
 pub async fn fetch_lyrics_from_musixmatch_usertoken(
@@ -13,9 +30,9 @@ pub async fn fetch_lyrics_from_musixmatch_usertoken(
     track_spotify_id: Option<&str>,
 ) -> ProviderResult {
     // Requirements: a usertoken must be present.
-    let token = match env::var("MUSIXMATCH_USERTOKEN").ok() {
-        Some(t) if !t.is_empty() => t,
-        _ => return Ok((Vec::new(), None)),
+    let token = match super::setting("MUSIXMATCH_USERTOKEN", "musixmatch", |s| s.token.as_ref()) {
+        Some(t) => t,
+        None => return Ok((Vec::new(), None)),
     };
 
     let client = http_client();
@@ -36,15 +53,8 @@ pub async fn fetch_lyrics_from_musixmatch_usertoken(
     async fn try_macro_for_lyrics(
         client: &Client,
         params: &[(String, String)],
-    ) -> Result<Option<(Vec<LyricLine>, String)>, reqwest::Error> {
-        let macro_base = "https://apic-desktop.musixmatch.com/ws/1.1/macro.subtitles.get?format=json&namespace=lyrics_richsynched&subtitle_format=mxm&optional_calls=track.richsync&app_id=web-desktop-app-v1.0&";
-        let macro_url = macro_base.to_string()
-            + &params
-                .iter()
-                .map(|(k, v)| format!("{}={}", k, urlencoding::encode(v)))
-                .collect::<Vec<_>>()
-                .join("&");
-
+    ) -> Result<Option<(Vec<LyricLine>, String)>, LyricsError> {
+        let macro_url = macro_url(params);
         let macro_resp = client
             .get(&macro_url)
             .header("Cookie", "x-mxm-token-guid=")
@@ -56,33 +66,32 @@ pub async fn fetch_lyrics_from_musixmatch_usertoken(
         }
 
         let macro_json: Value = macro_resp.json().await?;
+        if let Some(err) = auth_error(&macro_json) {
+            return Err(err);
+        }
         let macro_calls = macro_json.pointer("/message/body/macro_calls");
         
         if let Some(calls) = macro_calls {
             // Prefer richsync (word-level timing) if available
-            if is_success(calls, "track.richsync.get") {
-                if let Some(richsync_body) = calls
+            if is_success(calls, "track.richsync.get")
+                && let Some(richsync_body) = calls
                     .pointer("/track.richsync.get/message/body/richsync/richsync_body")
                     .and_then(|v| v.as_str())
-                {
-                    if let Some(parsed) = crate::lyrics::parse::parse_richsync_body(richsync_body) {
-                        // Return parsed lines and the original JSON body
-                        return Ok(Some((parsed, richsync_body.to_string())));
-                    }
-                }
+                && let Some(parsed) = crate::lyrics::parse::parse_richsync_body(richsync_body)
+            {
+                // Return parsed lines and the original JSON body
+                return Ok(Some((parsed, richsync_body.to_string())));
             }
 
             // Fall back to subtitles (line-level timing)
-            if is_success(calls, "track.subtitles.get") {
-                if let Some(subtitle_body) = calls
+            if is_success(calls, "track.subtitles.get")
+                && let Some(subtitle_body) = calls
                     .pointer("/track.subtitles.get/message/body/subtitle_list/0/subtitle/subtitle_body")
                     .and_then(|v| v.as_str())
-                {
-                    if let Some(parsed) = crate::lyrics::parse::parse_subtitle_body(subtitle_body) {
-                        // Return parsed lines and the original JSON body
-                        return Ok(Some((parsed, subtitle_body.to_string())));
-                    }
-                }
+                && let Some(parsed) = crate::lyrics::parse::parse_subtitle_body(subtitle_body)
+            {
+                // Return parsed lines and the original JSON body
+                return Ok(Some((parsed, subtitle_body.to_string())));
             }
         }
 
@@ -92,37 +101,24 @@ pub async fn fetch_lyrics_from_musixmatch_usertoken(
 
     // Strategy 1: If we have a Spotify track ID, try direct lookup first
     if let Some(sid) = track_spotify_id {
-        let mut params = vec![
-            ("track_spotify_id".to_string(), sid.to_string()),
-            ("usertoken".to_string(), token.clone()),
-        ];
-        if let Some(len) = duration.map(|d| d.round() as i64) {
-            params.push(("q_duration".to_string(), len.to_string()));
-        }
-        
-        if let Some((parsed, raw)) = try_macro_for_lyrics(&client, &params).await? {
+        let params = spotify_params(sid, duration, &token);
+        if let Some((parsed, raw)) = try_macro_for_lyrics(client, &params).await? {
             return Ok((parsed, Some(raw)));
         }
     }
 
-    // Strategy 2: Search by track metadata and use similarity matching
-    let search_base = "https://apic-desktop.musixmatch.com/ws/1.1/track.search?format=json&app_id=web-desktop-app-v1.0&";
-    let mut search_params = vec![
-        format!("q_artist={}", urlencoding::encode(artist)),
-        format!("q_track={}", urlencoding::encode(title)),
-        format!("usertoken={}", urlencoding::encode(&token)),
-        "page_size=10".to_string(),
-        "f_has_lyrics=1".to_string(),
-    ];
-    
-    if !album.is_empty() {
-        search_params.push(format!("q_album={}", urlencoding::encode(album)));
-    }
-    if let Some(d) = duration {
-        search_params.push(format!("q_duration={}", d.round() as i64));
+    // Strategy 2: A track ID resolved by an earlier search
+    if let Some((commontrack_id, track_length)) =
+        crate::lyrics::database::fetch_musixmatch_track(artist, title, album).await
+    {
+        let params = commontrack_params(commontrack_id, track_length, &token);
+        if let Some((parsed, raw)) = try_macro_for_lyrics(client, &params).await? {
+            return Ok((parsed, Some(raw)));
+        }
     }
 
-    let search_url = search_base.to_string() + &search_params.join("&");
+    // Strategy 3: Search by track metadata and use similarity matching
+    let search_url = search_url(artist, title, album, duration, &token);
     let search_resp = client
         .get(&search_url)
         .header("Cookie", "x-mxm-token-guid=")
@@ -134,6 +130,9 @@ pub async fn fetch_lyrics_from_musixmatch_usertoken(
     }
 
     let search_json: Value = search_resp.json().await?;
+    if let Some(err) = auth_error(&search_json) {
+        return Err(err);
+    }
     let track_list = search_json
         .pointer("/message/body/track_list")
         .and_then(|v| v.as_array())
@@ -163,44 +162,153 @@ pub async fn fetch_lyrics_from_musixmatch_usertoken(
         duration,
     );
 
-    if let Some((idx, _score)) = best_match {
-        if let Some(best) = candidates.get(idx) {
-            // Check if track is instrumental
-            if best.get("instrumental").and_then(|v| v.as_bool()).unwrap_or(false) {
-                let line = LyricLine {
-                    time: 0.0,
-                    text: "♪ Instrumental ♪".to_string(),
-                    words: None,
-                };
-                return Ok((vec![line], None));
-            }
+    if let Some((idx, _score)) = best_match
+        && let Some(best) = candidates.get(idx)
+    {
+        // Check if track is instrumental
+        if best.get("instrumental").and_then(|v| v.as_bool()).unwrap_or(false) {
+            let line = LyricLine {
+                time: 0.0,
+                text: "♪ Instrumental ♪".to_string(),
+                words: None,
+                translation: None,
+                agent: None,
+                cue: false,
+            };
+            return Ok((vec![line], None));
+        }
 
-            // Try to fetch lyrics using commontrack_id
-            if let Some(commontrack_id) = best
-                .get("commontrack_id")
+        // Try to fetch lyrics using commontrack_id
+        if let Some(commontrack_id) = best
+            .get("commontrack_id")
+            .and_then(|v| v.as_i64())
+            .or_else(|| best.get("track_id").and_then(|v| v.as_i64()))
+        {
+            let track_length = best
+                .get("track_length")
                 .and_then(|v| v.as_i64())
-                .or_else(|| best.get("track_id").and_then(|v| v.as_i64()))
-            {
-                let track_length = best
-                    .get("track_length")
-                    .and_then(|v| v.as_i64())
-                    .or_else(|| best.get("length").and_then(|v| v.as_i64()));
-
-                let mut params = vec![
-                    ("commontrack_id".to_string(), commontrack_id.to_string()),
-                    ("usertoken".to_string(), token.clone()),
-                ];
-                
-                if let Some(len) = track_length {
-                    params.push(("q_duration".to_string(), len.to_string()));
-                }
-
-                if let Some((parsed, raw)) = try_macro_for_lyrics(&client, &params).await? {
-                    return Ok((parsed, Some(raw)));
-                }
+                .or_else(|| best.get("length").and_then(|v| v.as_i64()));
+
+            let params = commontrack_params(commontrack_id, track_length, &token);
+            if let Some((parsed, raw)) = try_macro_for_lyrics(client, &params).await? {
+                // Only remember matches that have lyrics; others are searched again next time
+                crate::lyrics::database::store_musixmatch_track(artist, title, album, commontrack_id, track_length).await;
+                return Ok((parsed, Some(raw)));
             }
         }
     }
 
     Ok((Vec::new(), None))
 }
+
+/// The requests [`fetch_lyrics_from_musixmatch_usertoken`] would send, in
+/// order, with the user token redacted (`--explain`). Each one is only
+/// sent if the previous found nothing.
+pub async fn explain(
+    artist: &str,
+    title: &str,
+    album: &str,
+    duration: Option<f64>,
+    track_spotify_id: Option<&str>,
+) -> Vec<String> {
+    if super::setting("MUSIXMATCH_USERTOKEN", "musixmatch", |s| s.token.as_ref()).is_none() {
+        return vec!["skipped: no MUSIXMATCH_USERTOKEN or [providers.musixmatch] token".to_string()];
+    }
+    let token = super::REDACTED;
+
+    let mut requests = Vec::new();
+    if let Some(sid) = track_spotify_id {
+        requests.push(format!("GET {}", macro_url(&spotify_params(sid, duration, token))));
+    }
+    if let Some((commontrack_id, track_length)) =
+        crate::lyrics::database::fetch_musixmatch_track(artist, title, album).await
+    {
+        requests.push(format!("GET {}", macro_url(&commontrack_params(commontrack_id, track_length, token))));
+    }
+    requests.push(format!("GET {}", search_url(artist, title, album, duration, token)));
+    requests.push("then macro.subtitles.get for the best search match".to_string());
+    requests
+}
+
+/// `macro.subtitles.get` URL (richsync plus subtitles) with `params`.
+fn macro_url(params: &[(String, String)]) -> String {
+    let macro_base = "https://apic-desktop.musixmatch.com/ws/1.1/macro.subtitles.get?format=json&namespace=lyrics_richsynched&subtitle_format=mxm&optional_calls=track.richsync&app_id=web-desktop-app-v1.0&";
+    macro_base.to_string()
+        + &params
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, urlencoding::encode(v)))
+            .collect::<Vec<_>>()
+            .join("&")
+}
+
+/// `track.search` URL for the track's metadata.
+fn search_url(artist: &str, title: &str, album: &str, duration: Option<f64>, token: &str) -> String {
+    let search_base = "https://apic-desktop.musixmatch.com/ws/1.1/track.search?format=json&app_id=web-desktop-app-v1.0&";
+    let mut search_params = vec![
+        format!("q_artist={}", urlencoding::encode(artist)),
+        format!("q_track={}", urlencoding::encode(title)),
+        format!("usertoken={}", urlencoding::encode(token)),
+        "page_size=10".to_string(),
+        "f_has_lyrics=1".to_string(),
+    ];
+
+    if !album.is_empty() {
+        search_params.push(format!("q_album={}", urlencoding::encode(album)));
+    }
+    if let Some(d) = duration {
+        search_params.push(format!("q_duration={}", d.round() as i64));
+    }
+
+    search_base.to_string() + &search_params.join("&")
+}
+
+/// Query parameters for a direct lookup by Spotify track ID.
+fn spotify_params(spotify_id: &str, duration: Option<f64>, token: &str) -> Vec<(String, String)> {
+    let mut params = vec![
+        ("track_spotify_id".to_string(), spotify_id.to_string()),
+        ("usertoken".to_string(), token.to_string()),
+    ];
+    if let Some(len) = duration.map(|d| d.round() as i64) {
+        params.push(("q_duration".to_string(), len.to_string()));
+    }
+    params
+}
+
+/// Query parameters for fetching lyrics of a known Musixmatch track.
+fn commontrack_params(commontrack_id: i64, track_length: Option<i64>, token: &str) -> Vec<(String, String)> {
+    let mut params = vec![
+        ("commontrack_id".to_string(), commontrack_id.to_string()),
+        ("usertoken".to_string(), token.to_string()),
+    ];
+    if let Some(len) = track_length {
+        params.push(("q_duration".to_string(), len.to_string()));
+    }
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auth_error_detection() {
+        let renew = serde_json::json!({ "message": { "header": { "status_code": 401, "hint": "renew" } } });
+        assert!(auth_error(&renew).unwrap().to_string().contains("token expired"));
+
+        let captcha = serde_json::json!({ "message": { "header": { "status_code": 401, "hint": "captcha" } } });
+        assert!(auth_error(&captcha).unwrap().to_string().contains("captcha"));
+
+        let ok = serde_json::json!({ "message": { "header": { "status_code": 200 } } });
+        assert!(auth_error(&ok).is_none());
+    }
+
+    #[test]
+    fn test_request_urls() {
+        let url = search_url("Simon & Garfunkel", "The Boxer", "", Some(308.4), "REDACTED");
+        assert!(url.contains("q_artist=Simon%20%26%20Garfunkel&q_track=The%20Boxer&usertoken=REDACTED"));
+        assert!(url.ends_with("&q_duration=308") && !url.contains("q_album"));
+
+        let url = macro_url(&commontrack_params(42, Some(308), "REDACTED"));
+        assert!(url.ends_with("&commontrack_id=42&usertoken=REDACTED&q_duration=308"));
+    }
+}
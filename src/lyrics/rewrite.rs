@@ -0,0 +1,181 @@
+//! User-defined metadata rewrite rules.
+//!
+//! Rules are regex substitutions from the `[[rewrite]]` tables of the config
+//! file. They are applied to artist/title/album before the cache lookup and
+//! provider queries, so messy library tags ("NCS Release", "feat." clauses)
+//! can be mapped to names providers actually know. The player's original
+//! metadata is still what gets displayed.
+//!
+//! `[[ignore]]` tables use the same field/pattern form to mark tracks
+//! (background noise, specific artists) that never trigger a lookup.
+
+use crate::config::{IgnoreRule, RewriteField, RewriteRule};
+use crate::mpris::TrackMetadata;
+use regex::Regex;
+
+/// A compiled rewrite rule.
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    field: RewriteField,
+    pattern: Regex,
+    replace: String,
+}
+
+/// Compiled set of rewrite rules, applied in file order.
+#[derive(Debug, Clone, Default)]
+pub struct RewriteRules {
+    rules: Vec<CompiledRule>,
+}
+
+impl RewriteRules {
+    /// Compiles rules from the config file.
+    ///
+    /// Rules with an invalid pattern are logged and skipped.
+    pub fn compile(rules: &[RewriteRule]) -> Self {
+        let rules = rules
+            .iter()
+            .filter_map(|rule| match Regex::new(&rule.pattern) {
+                Ok(pattern) => Some(CompiledRule {
+                    field: rule.field,
+                    pattern,
+                    replace: rule.replace.clone(),
+                }),
+                Err(e) => {
+                    tracing::warn!(pattern = %rule.pattern, error = %e, "Ignoring invalid rewrite rule");
+                    None
+                }
+            })
+            .collect();
+
+        Self { rules }
+    }
+
+    /// Returns a copy of `meta` with all rules applied.
+    pub fn apply(&self, meta: &TrackMetadata) -> TrackMetadata {
+        let mut query = meta.clone();
+
+        for rule in &self.rules {
+            let targets: &mut [&mut String] = match rule.field {
+                RewriteField::Artist => &mut [&mut query.artist],
+                RewriteField::Title => &mut [&mut query.title],
+                RewriteField::Album => &mut [&mut query.album],
+                RewriteField::All => &mut [&mut query.artist, &mut query.title, &mut query.album],
+            };
+
+            for value in targets.iter_mut() {
+                let replaced = rule.pattern.replace_all(value, rule.replace.as_str());
+                if replaced != value.as_str() {
+                    **value = collapse_whitespace(&replaced);
+                }
+            }
+        }
+
+        if query != *meta {
+            tracing::debug!(
+                artist = %query.artist,
+                title = %query.title,
+                album = %query.album,
+                "Metadata rewritten for lookup"
+            );
+        }
+
+        query
+    }
+}
+
+/// Compiled `[[ignore]]` patterns.
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    rules: Vec<(RewriteField, Regex)>,
+}
+
+impl IgnoreRules {
+    /// Compiles rules from the config file.
+    ///
+    /// Rules with an invalid pattern are logged and skipped.
+    pub fn compile(rules: &[IgnoreRule]) -> Self {
+        let rules = rules
+            .iter()
+            .filter_map(|rule| match Regex::new(&rule.pattern) {
+                Ok(pattern) => Some((rule.field, pattern)),
+                Err(e) => {
+                    tracing::warn!(pattern = %rule.pattern, error = %e, "Ignoring invalid ignore rule");
+                    None
+                }
+            })
+            .collect();
+
+        Self { rules }
+    }
+
+    /// Returns `true` if any rule matches `meta`.
+    pub fn matches(&self, meta: &TrackMetadata) -> bool {
+        self.rules.iter().any(|(field, pattern)| match field {
+            RewriteField::Artist => pattern.is_match(&meta.artist),
+            RewriteField::Title => pattern.is_match(&meta.title),
+            RewriteField::Album => pattern.is_match(&meta.album),
+            RewriteField::All => [&meta.artist, &meta.title, &meta.album]
+                .iter()
+                .any(|value| pattern.is_match(value)),
+        })
+    }
+}
+
+/// Trims and collapses runs of whitespace left behind by removals.
+fn collapse_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_rules() {
+        let rules = RewriteRules::compile(&[
+            RewriteRule {
+                field: RewriteField::Artist,
+                pattern: "^NCS Release$".to_string(),
+                replace: "NoCopyrightSounds".to_string(),
+            },
+            RewriteRule {
+                field: RewriteField::Title,
+                pattern: r"(?i)\s*[(\[]feat\.[^)\]]*[)\]]".to_string(),
+                replace: String::new(),
+            },
+            RewriteRule {
+                field: RewriteField::All,
+                pattern: "(".to_string(), // invalid, skipped
+                replace: String::new(),
+            },
+        ]);
+
+        let meta = TrackMetadata {
+            artist: "NCS Release".to_string(),
+            title: "Song (feat. Someone) Remix".to_string(),
+            album: "Album".to_string(),
+            ..Default::default()
+        };
+        let query = rules.apply(&meta);
+        assert_eq!(query.artist, "NoCopyrightSounds");
+        assert_eq!(query.title, "Song Remix");
+        assert_eq!(query.album, "Album");
+    }
+
+    #[test]
+    fn test_ignore_rules() {
+        let rules = IgnoreRules::compile(&[
+            IgnoreRule { field: RewriteField::Title, pattern: "(?i)white noise".to_string() },
+            IgnoreRule { field: RewriteField::All, pattern: "^Sleep Sounds$".to_string() },
+        ]);
+
+        let track = |artist: &str, title: &str| TrackMetadata {
+            artist: artist.to_string(),
+            title: title.to_string(),
+            ..Default::default()
+        };
+        assert!(rules.matches(&track("Nature", "Pure White Noise 10h")));
+        assert!(rules.matches(&track("Sleep Sounds", "Rain")));
+        assert!(!rules.matches(&track("White Noise", "Song")));
+    }
+}
@@ -1,11 +1,12 @@
 use once_cell::sync::Lazy;
 use reqwest::Client;
 use thiserror::Error;
+use unicode_segmentation::UnicodeSegmentation;
 
 // Shared HTTP client with reasonable defaults for timeouts
 static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
     Client::builder()
-        .user_agent("LyricsMPRIS/1.0")
+        .user_agent(super::providers::user_agent())
         .timeout(std::time::Duration::from_secs(10))
         .build()
         .expect("failed to build HTTP client")
@@ -14,29 +15,137 @@ static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
 /// Provider result: parsed lines plus optional raw lyrics string (LRC format or JSON)
 pub type ProviderResult = Result<(Vec<LyricLine>, Option<String>), LyricsError>;
 
+/// HTTP validators of a provider response (`ETag`, `Last-Modified`), sent back
+/// as `If-None-Match`/`If-Modified-Since` so unchanged lyrics aren't downloaded again.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HttpValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl HttpValidators {
+    /// Validators of a response, unless it asks not to be stored.
+    pub fn from_headers(headers: &reqwest::header::HeaderMap) -> Self {
+        use reqwest::header::{CACHE_CONTROL, ETAG, LAST_MODIFIED};
+
+        let get = |name| headers.get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+        let no_store = get(CACHE_CONTROL).is_some_and(|cc| {
+            cc.split(',').any(|directive| directive.trim().eq_ignore_ascii_case("no-store"))
+        });
+        if no_store {
+            return Self::default();
+        }
+        Self { etag: get(ETAG), last_modified: get(LAST_MODIFIED) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+
+    /// Adds the conditional request headers to `request`.
+    pub fn apply(&self, mut request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        if let Some(etag) = &self.etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &self.last_modified {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+        request
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct LyricLine {
     pub time: f64,
     pub text: String,
     /// Optional per-word timings (start, end, text) for karaoke rendering.
     pub words: Option<Vec<WordTiming>>,
+    /// Optional translation of the line supplied by the provider.
+    pub translation: Option<String>,
+    /// Vocal part singing the line, for duets (TTML `ttm:agent`, e.g. `v1`).
+    pub agent: Option<String>,
+    /// Marked by a `# cue` comment in LRC: flashes or rings the bell when
+    /// it becomes active (see [`crate::ui::cue`]).
+    pub cue: bool,
 }
 
+/// A timed word (or syllable) of a karaoke line.
+///
+/// The grapheme cluster boundaries of `text` are computed once on
+/// construction and stored as byte offsets, so highlighting can advance
+/// grapheme by grapheme (emoji, combining accents) without re-segmenting
+/// the text on every frame. Build words with [`WordTiming::new`].
 #[derive(Debug, Clone, PartialEq)]
 pub struct WordTiming {
     pub start: f64,
     pub end: f64,
     pub text: String,
-    /// Byte indices of grapheme cluster boundaries in `text`.
-    /// To extract grapheme at index i: &text[boundaries[i]..boundaries[i+1]]
-    /// The last boundary equals text.len() for convenience.
-    pub grapheme_boundaries: Vec<usize>,
+    /// Byte offset of every grapheme start, followed by `text.len()`
+    /// (so grapheme `i` is `text[boundaries[i]..boundaries[i + 1]]`)
+    boundaries: Vec<u32>,
 }
 
 impl WordTiming {
+    /// A word spanning `start..end` seconds.
+    pub fn new(start: f64, end: f64, text: impl Into<String>) -> Self {
+        let text = text.into();
+        let boundaries = text
+            .grapheme_indices(true)
+            .map(|(offset, _)| offset)
+            .chain(std::iter::once(text.len()))
+            .map(|offset| offset as u32)
+            .collect();
+        Self { start, end, text, boundaries }
+    }
+
     /// Returns the number of grapheme clusters in this word.
     pub fn grapheme_count(&self) -> usize {
-        self.grapheme_boundaries.len().saturating_sub(1)
+        self.boundaries.len() - 1
+    }
+
+    /// Byte offset in `text` where grapheme `i` starts (`text.len()` for
+    /// `i == grapheme_count()`).
+    pub fn grapheme_offset(&self, i: usize) -> usize {
+        self.boundaries[i] as usize
+    }
+
+    /// Index of the grapheme starting at byte `offset`, or of the next
+    /// one if `offset` falls inside a grapheme.
+    pub fn grapheme_at(&self, offset: usize) -> usize {
+        self.boundaries.binary_search(&(offset as u32)).unwrap_or_else(|i| i)
+    }
+
+    /// The text split after the first `i` graphemes.
+    pub fn split_at_grapheme(&self, i: usize) -> (&str, &str) {
+        self.text.split_at(self.grapheme_offset(i))
+    }
+
+    /// The graphemes `from..to` of this word, with the timing interpolated
+    /// linearly (the same way karaoke highlighting advances through a word).
+    pub fn slice(&self, from: usize, to: usize) -> WordTiming {
+        let total = self.grapheme_count().max(1) as f64;
+        let time_at = |grapheme: usize| self.start + (self.end - self.start) * grapheme as f64 / total;
+        let base = self.boundaries[from];
+        WordTiming {
+            start: time_at(from),
+            end: time_at(to),
+            text: self.text[base as usize..self.boundaries[to] as usize].to_string(),
+            boundaries: self.boundaries[from..=to].iter().map(|b| b - base).collect(),
+        }
+    }
+
+    /// Appends a hyphen marking a word broken across lines; it is
+    /// highlighted together with the last grapheme.
+    pub fn with_hyphen(mut self) -> WordTiming {
+        self.text.push('-');
+        if self.boundaries.len() == 1 {
+            // An empty word: the hyphen is its only grapheme
+            self.boundaries.insert(0, 0);
+        }
+        if let Some(last) = self.boundaries.last_mut() {
+            *last = self.text.len() as u32;
+        }
+        self
     }
 }
 
@@ -54,3 +163,47 @@ pub enum LyricsError {
 pub(crate) fn http_client() -> &'static Client {
     &HTTP_CLIENT
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_validators() {
+        use reqwest::header::{HeaderMap, HeaderValue, CACHE_CONTROL, ETAG, IF_NONE_MATCH, LAST_MODIFIED};
+
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"abc\""));
+        headers.insert(LAST_MODIFIED, HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"));
+        let validators = HttpValidators::from_headers(&headers);
+        assert_eq!(validators.etag.as_deref(), Some("\"abc\""));
+        assert!(validators.last_modified.is_some());
+
+        let request = validators.apply(reqwest::Client::new().get("http://localhost/")).build().unwrap();
+        assert_eq!(request.headers()[IF_NONE_MATCH], "\"abc\"");
+
+        headers.insert(CACHE_CONTROL, HeaderValue::from_static("private, No-Store"));
+        assert!(HttpValidators::from_headers(&headers).is_empty());
+    }
+
+    #[test]
+    fn test_word_graphemes() {
+        // Family emoji (ZWJ sequence), flag, and "é" written as e + combining accent
+        let word = WordTiming::new(0.0, 4.0, "a👨‍👩‍👧🇯🇵e\u{301}");
+        assert_eq!(word.grapheme_count(), 4);
+        assert_eq!(word.split_at_grapheme(2), ("a👨‍👩‍👧", "🇯🇵e\u{301}"));
+        assert_eq!(word.grapheme_offset(4), word.text.len());
+        // Inside the emoji: snaps to the next grapheme
+        assert_eq!(word.grapheme_at(3), 2);
+
+        let piece = word.slice(1, 3);
+        assert_eq!(piece.text, "👨‍👩‍👧🇯🇵");
+        assert_eq!((piece.start, piece.end, piece.grapheme_count()), (1.0, 3.0, 2));
+        let hyphenated = piece.with_hyphen();
+        assert_eq!(hyphenated.split_at_grapheme(1), ("👨‍👩‍👧", "🇯🇵-"));
+
+        let empty = WordTiming::new(0.0, 1.0, "");
+        assert_eq!(empty.grapheme_count(), 0);
+        assert_eq!(empty.with_hyphen().grapheme_count(), 1);
+    }
+}
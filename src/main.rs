@@ -1,159 +1,462 @@
+mod commands;
+mod config;
 mod event;
+mod exit;
+mod hooks;
+mod i18n;
 mod lyrics;
 mod mpris;
+mod playlist;
 mod pool;
+mod server;
 mod state;
 mod timer;
 mod text_utils;
 mod ui;
+mod watch;
 
-use crate::mpris::metadata::get_metadata;
-use crate::mpris::playback::get_position;
 use clap::Parser;
 use std::error::Error;
 use tracing_subscriber::EnvFilter;
 // polling removed; no Duration needed here
 
 /// Application configuration from CLI
-#[derive(Parser, Debug, Clone)]
-#[command(author, version, about)]
+#[derive(Parser, Debug, Clone, serde::Serialize)]
+#[command(author, version, about, disable_version_flag = true)]
 pub struct Config {
     /// Pipe current lyric line to stdout (default is modern UI)
     #[arg(long)]
     pipe: bool,
     
-    /// Blocklist for MPRIS player service names (comma-separated, case-insensitive)
+    /// Print explicit markers on track change and pause/resume (pipe mode)
+    #[arg(long = "markers", value_enum, value_name = "STYLE", requires = "pipe")]
+    pub markers: Option<crate::ui::pipe::MarkerStyle>,
+
+    /// Prefix printed lines with the lyric's LRC time (default) or wall-clock time (pipe mode)
+    #[arg(
+        long = "timestamps",
+        value_enum,
+        value_name = "STYLE",
+        num_args = 0..=1,
+        default_missing_value = "lyric",
+        requires = "pipe"
+    )]
+    pub timestamps: Option<crate::ui::pipe::TimestampStyle>,
+
+    /// Also print the upcoming line after the active one (pipe mode)
+    #[arg(long = "with-next", requires = "pipe")]
+    pub with_next: bool,
+
+    /// Separator between active and upcoming line with --with-next
+    #[arg(long = "next-separator", value_name = "SEP", default_value = " | ", requires = "with_next")]
+    pub next_separator: String,
+
+    /// Print each line as a JSON object (pipe mode)
+    #[arg(long = "json", requires = "pipe")]
+    pub json: bool,
+
+    /// Print at most one line every MS milliseconds, coalescing to the latest (pipe mode)
+    #[arg(long = "min-interval", value_name = "MS", requires = "pipe")]
+    pub min_interval: Option<u64>,
+
+    /// Name the lyrics provider on every line or once per track (pipe mode)
+    #[arg(long = "attribution", value_enum, value_name = "WHERE", requires = "pipe")]
+    pub attribution: Option<crate::ui::pipe::Attribution>,
+
+    /// Print a heartbeat line every SECONDS seconds, even when nothing changes (pipe mode)
+    #[arg(long = "heartbeat", value_name = "SECONDS", requires = "pipe")]
+    pub heartbeat: Option<u64>,
+
+    /// Print the cache key and each provider's requests for the playing track (tokens redacted) without fetching, then exit
+    #[arg(long = "explain")]
+    pub explain: bool,
+
+    /// Never write warnings to the terminal; log them (and RUST_LOG output) to a file instead
+    #[arg(short = 'q', long = "quiet")]
+    pub quiet: bool,
+
+    /// Machine-readable output instead of the TUI (`state-jsonl`: full snapshot per track, then deltas)
+    #[arg(long = "output", value_enum, value_name = "FORMAT")]
+    pub output: Option<crate::ui::state_stream::OutputFormat>,
+
+    /// Print the full lyrics of the playing track as LRC (default), plain text or ASS karaoke subtitles, then exit
+    #[arg(
+        long = "dump",
+        value_enum,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        default_missing_value = "lrc"
+    )]
+    pub dump: Option<crate::ui::pipe::DumpFormat>,
+
+    /// Blocklist for MPRIS players, matched against the service name and the player's
+    /// Identity (comma-separated, case-insensitive)
     #[arg(
         long = "block",
         value_name = "SERVICE1,SERVICE2",
         value_delimiter = ','
     )]
     block: Vec<String>,
+    /// Follow this MPRIS player instead of discovering the active one
+    /// (`spotify` or `org.mpris.MediaPlayer2.spotify`); needed without playerctld
+    #[arg(long = "player-service", value_name = "SERVICE")]
+    pub player_service: Option<String>,
     /// Disable karaoke highlighting (per-word). Use --no-karaoke to disable karaoke (default: enabled).
     #[arg(long = "no-karaoke")]
     pub no_karaoke: bool,
     /// Maximum number of visible lyric lines (treating wrapped lines as one line). Default: unlimited
     #[arg(long = "visible-lines", value_name = "COUNT")]
     pub visible_lines: Option<usize>,
+    /// Break words wider than the terminal with a hyphen (TUI)
+    #[arg(long = "hyphenate")]
+    pub hyphenate: bool,
+    /// Pause per-word karaoke redraws while the terminal is unfocused (TUI, needs focus reporting)
+    #[arg(long = "idle-unfocused")]
+    pub idle_unfocused: bool,
+    /// Drop per-word karaoke redraws to per-line updates while the terminal is unfocused or the screen is locked (TUI)
+    #[arg(long = "low-power")]
+    pub low_power: bool,
+    /// Redraw karaoke highlighting at most FPS times per second (TUI; default and maximum 60)
+    #[arg(long = "karaoke-fps", value_name = "FPS")]
+    pub karaoke_fps: Option<u32>,
+    /// Always show the next line under the current one in a preview style (TUI), even with --visible-lines
+    #[arg(long = "preview-next")]
+    pub preview_next: bool,
+    /// Omit already-sung lines: the current line stays at the top with upcoming lines below (TUI)
+    #[arg(long = "hide-past")]
+    pub hide_past: bool,
+    /// Karaoke fill curve within a word (TUI): `linear` or `ease-in-out` (slower start and finish)
+    #[arg(long = "karaoke-easing", value_enum, value_name = "CURVE", default_value = "linear")]
+    pub karaoke_easing: crate::ui::progression::Easing,
+    /// Fade the old lyrics out and the new ones in over MS milliseconds on track change (TUI)
+    #[arg(long = "fade", value_name = "MS")]
+    pub fade: Option<u64>,
+    /// Show the artist and title centered for the first seconds of each track (TUI)
+    #[arg(long = "splash")]
+    pub splash: bool,
+    /// When to use colors: `auto` (unless NO_COLOR is set), `always` or `never`;
+    /// covers the TUI and log output on stderr
+    #[arg(long = "color", value_enum, value_name = "WHEN", default_value = "auto")]
+    pub color: crate::ui::styles::ColorChoice,
+    /// Color theme (TUI): `default` or `high-contrast` (white on black, no dim)
+    #[arg(long = "theme", value_enum, value_name = "THEME", default_value = "default")]
+    pub theme: crate::ui::styles::Theme,
+    /// Never use italics, even if the terminal claims support (TUI)
+    #[arg(long = "no-italics")]
+    pub no_italics: bool,
+    /// Use only ASCII symbols in badges and borders (TUI)
+    #[arg(long = "ascii")]
+    pub ascii: bool,
     /// Comma-separated list of lyric providers in preferred order (e.g. "lrclib,musixmatch").
-    /// If empty, the LYRIC_PROVIDERS env var will be used as a fallback.
+    /// If empty, the LYRIC_PROVIDERS env var, then `[providers] order` from the config file is used.
     #[arg(long, value_delimiter = ',')]
+    #[serde(skip)]
     pub providers: Vec<String>,
     /// Path to local lyrics database JSON file for caching
     #[arg(long = "database")]
     pub database: Option<String>,
-    /// Cached current player service for efficient D-Bus queries
-    pub player_service: Option<String>,
+    /// Allowed track duration mismatch for cache hits, in percent
+    #[arg(long = "duration-tolerance", value_name = "PERCENT", default_value_t = 5.0)]
+    pub duration_tolerance: f64,
+    /// Accept cached lyrics regardless of track duration (e.g. radio edits)
+    #[arg(long = "ignore-duration")]
+    pub ignore_duration: bool,
+    /// Refetch cached lyrics older than DAYS in the background, replacing them if they changed
+    #[arg(long = "cache-max-age", value_name = "DAYS")]
+    pub cache_max_age: Option<u64>,
+    /// Watch this directory for `current.lrc` / `Artist - Title.lrc` and load them immediately
+    #[arg(long = "drop-dir", value_name = "DIR")]
+    pub drop_dir: Option<std::path::PathBuf>,
+    /// Also poll the player every MS milliseconds (for players with broken D-Bus signals)
+    #[arg(long = "poll-interval", value_name = "MS")]
+    pub poll_interval: Option<u64>,
+    /// Run CMD (via `sh -c`) when a new track starts; see README for the environment
+    #[arg(long = "on-track-change", value_name = "CMD")]
+    pub on_track_change: Option<String>,
+    /// Run CMD (via `sh -c`) when a new lyric line becomes active
+    #[arg(long = "on-line-change", value_name = "CMD")]
+    pub on_line_change: Option<String>,
+    /// Resolve canonical artist/title via MusicBrainz before querying providers
+    #[arg(long = "musicbrainz")]
+    pub musicbrainz: bool,
+    /// Scroll unsynced (plain) LRCLIB lyrics at a steady speed when no provider has synced ones
+    #[arg(long = "teleprompter")]
+    pub teleprompter: bool,
+    /// Path to the TOML configuration file (default: ~/.config/lyricsmpris/config.toml)
+    #[arg(long = "config", value_name = "PATH")]
+    #[serde(skip)]
+    pub config: Option<std::path::PathBuf>,
+    /// Print the effective configuration (defaults + file + flags) as TOML and exit
+    #[arg(long = "print-config")]
+    #[serde(skip)]
+    pub print_config: bool,
+    /// Write a commented starter config file (to --config PATH or the default location) and exit
+    #[arg(long = "init-config")]
+    #[serde(skip)]
+    pub init_config: bool,
+    /// Print version
+    #[arg(short = 'V', long = "version")]
+    #[serde(skip)]
+    pub version: bool,
+    /// With --version: also print build, provider and path information
+    #[arg(long = "verbose", requires = "version")]
+    #[serde(skip)]
+    pub verbose: bool,
+    /// Settings loaded from the configuration file
+    #[arg(skip)]
+    #[serde(flatten)]
+    pub file: config::FileConfig,
+    /// One-shot subcommand to run instead of the viewer
+    #[command(subcommand)]
+    #[serde(skip)]
+    pub command: Option<commands::Command>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             pipe: false,
+            markers: None,
+            dump: None,
+            timestamps: None,
+            with_next: false,
+            next_separator: " | ".to_string(),
+            json: false,
+            min_interval: None,
+            attribution: None,
+            heartbeat: None,
+            explain: false,
+            quiet: false,
+            output: None,
             block: vec![],
-            providers: vec!["lrclib".to_string(), "musixmatch".to_string()],
+            providers: vec![event::LRCLIB.to_string(), event::MUSIXMATCH.to_string()],
             database: None,
+            duration_tolerance: 5.0,
+            ignore_duration: false,
+            cache_max_age: None,
+            drop_dir: None,
+            musicbrainz: false,
+            teleprompter: false,
+            poll_interval: None,
+            on_track_change: None,
+            on_line_change: None,
+            config: None,
+            print_config: false,
+            init_config: false,
+            version: false,
+            verbose: false,
+            file: config::FileConfig::default(),
             player_service: None,
+            command: None,
             no_karaoke: false,
             visible_lines: None,
+            hyphenate: false,
+            idle_unfocused: false,
+            low_power: false,
+            karaoke_fps: None,
+            preview_next: false,
+            hide_past: false,
+            karaoke_easing: crate::ui::progression::Easing::Linear,
+            fade: None,
+            splash: false,
+            color: crate::ui::styles::ColorChoice::Auto,
+            theme: crate::ui::styles::Theme::Default,
+            no_italics: false,
+            ascii: false,
         }
     }
 }
 
+/// Fills an empty provider list from `LYRIC_PROVIDERS`, then the config file.
 fn providers_from_env_if_empty(cli: &mut Config) {
     if cli.providers.is_empty()
         && let Ok(s) = std::env::var("LYRIC_PROVIDERS")
     {
         let parts: Vec<String> = s
             .split(',')
-            .map(|p| p.trim().to_lowercase())
+            .map(|p| p.trim())
+            // Script paths are case-sensitive
+            .map(|p| if p.starts_with("exec:") { p.to_string() } else { p.to_lowercase() })
             .filter(|p| !p.is_empty())
             .collect();
         if !parts.is_empty() {
             cli.providers = parts;
         }
     }
+    if cli.providers.is_empty() {
+        cli.providers = cli.file.providers.order.clone();
+    }
 }
 
-/// Initializes the database if a path is provided in the configuration.
-async fn initialize_database(config: &Config) {
-    if let Some(db_path) = &config.database {
-        lyrics::database::initialize(std::path::PathBuf::from(db_path)).await;
+/// Handles `--init-config`: writes the starter file, refusing to overwrite.
+fn init_config(cfg: &Config) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let path = cfg
+        .config
+        .clone()
+        .or_else(config::default_config_path)
+        .ok_or("cannot determine the config directory; pass --config PATH")?;
+    config::write_starter(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+/// Handles `--version`; with `--verbose` also the details needed to triage
+/// bug reports.
+fn print_version(cfg: &Config) {
+    println!("lyricsmpris {}", env!("CARGO_PKG_VERSION"));
+    if !cfg.verbose {
+        return;
     }
+
+    let features = env!("LYRICSMPRIS_FEATURES");
+    let order = if cfg.providers.is_empty() { Config::default().providers } else { cfg.providers.clone() };
+    let config_path = cfg.config.clone().or_else(config::default_config_path);
+    let config_status = match &config_path {
+        Some(path) if path.exists() => format!("{} (loaded)", path.display()),
+        Some(path) => format!("{} (not found, using defaults)", path.display()),
+        None => "none (no config directory)".to_string(),
+    };
+
+    println!("commit:    {}", env!("LYRICSMPRIS_GIT_HASH"));
+    println!("build:     {} ({}-{})", if cfg!(debug_assertions) { "debug" } else { "release" }, std::env::consts::ARCH, std::env::consts::OS);
+    println!("features:  {}", if features.is_empty() { "none" } else { features });
+    println!("providers: {}, {}PATH", event::PROVIDERS.join(", "), lyrics::providers::exec::EXEC_PREFIX);
+    println!("order:     {}", order.join(", "));
+    println!("backends:  MPRIS over D-Bus (zbus), SQLite lyrics cache (sqlx)");
+    println!("config:    {}", config_status);
+    println!("database:  {}", cfg.database.as_deref().unwrap_or("disabled"));
 }
 
-/// Fetches initial metadata from the player service.
-///
-/// Returns default metadata on error with warning log.
-async fn fetch_initial_metadata(service: &str) -> crate::mpris::TrackMetadata {
-    match get_metadata(service).await {
-        Ok(meta) => meta,
-        Err(e) => {
-            tracing::warn!(
-                service = %service,
-                error = %e,
-                "D-Bus error getting initial metadata"
-            );
-            Default::default()
+/// Handles `--print-config`: prints the settings as the app will use them.
+fn print_config(cfg: &Config) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut effective = cfg.clone();
+    effective.file.providers.order = if cfg.providers.is_empty() {
+        Config::default().providers
+    } else {
+        cfg.providers.clone()
+    };
+    // Output is often pasted into bug reports
+    for &name in event::PROVIDERS {
+        if let Some(settings) = effective.file.providers.settings_mut(name)
+            && settings.token.is_some()
+        {
+            settings.token = Some("<redacted>".to_string());
         }
     }
+    print!("{}", toml::to_string(&effective)?);
+    Ok(())
 }
 
-/// Fetches initial playback position from the player service.
-///
-/// Returns 0.0 on error with warning log.
-async fn fetch_initial_position(service: &str) -> f64 {
-    match get_position(service).await {
-        Ok(pos) => pos,
-        Err(e) => {
-            tracing::warn!(
-                service = %service,
-                error = %e,
-                "D-Bus error getting initial position"
-            );
-            0.0
-        }
+/// Initializes the database if a path is provided in the configuration.
+async fn initialize_database(config: &Config) {
+    if let Some(db_path) = &config.database {
+        lyrics::database::initialize(std::path::PathBuf::from(db_path)).await;
     }
 }
 
 /// Starts the appropriate UI mode based on configuration.
-async fn start_ui(
-    meta: crate::mpris::TrackMetadata,
-    position: f64,
-    config: Config,
-) -> Result<(), Box<dyn Error + Send + Sync>> {
-    if config.pipe {
-        crate::ui::pipe::display_lyrics_pipe(meta, position, config).await
+async fn start_ui(config: Config) -> Result<(), Box<dyn Error + Send + Sync>> {
+    exit::require_dbus().await?;
+    if config.explain {
+        commands::run_explain(&config).await
+    } else if let Some(format) = config.dump {
+        crate::ui::pipe::dump_lyrics(config, format).await
+    } else if let Some(format) = config.output {
+        crate::ui::state_stream::display_state_stream(config, format).await
+    } else if config.pipe {
+        crate::ui::pipe::display_lyrics_pipe(config).await
     } else {
         let enable_karaoke = !config.no_karaoke;
-        crate::ui::modern::display_lyrics_modern(meta, position, config, enable_karaoke).await
+        crate::ui::modern::display_lyrics_modern(config, enable_karaoke)
+            .await
+            .map_err(|e| match e.downcast::<std::io::Error>() {
+                Ok(e) => exit::Failure::Terminal(*e).into(),
+                Err(e) => e,
+            })
     }
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
-    // Initialize tracing with environment filter
-    // Logs are OFF by default. Users can enable with RUST_LOG environment variable.
-    // When enabled, logs go to stderr to avoid polluting stdout (used for pipe mode and TUI)
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::from_default_env())
-        .with_target(true)
-        .with_thread_ids(false)
-        .with_writer(std::io::stderr)
+/// Log file used with `--quiet`: `$XDG_STATE_HOME/lyricsmpris/lyricsmpris.log`.
+fn quiet_log_path() -> Option<std::path::PathBuf> {
+    let base = std::env::var_os("XDG_STATE_HOME")
+        .filter(|v| !v.is_empty())
+        .map(std::path::PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| std::path::PathBuf::from(home).join(".local/state")))?;
+    Some(base.join("lyricsmpris").join("lyricsmpris.log"))
+}
+
+/// Initializes tracing with the `RUST_LOG` filter.
+///
+/// Logs are OFF by default and go to stderr to avoid polluting stdout (used
+/// for pipe mode and TUI). With `--quiet` nothing is written to the
+/// terminal: warnings (or what `RUST_LOG` selects) are appended to
+/// [`quiet_log_path`] instead. Warnings also always reach the TUI's status
+/// bar ([`ui::status`]). Log lines on stderr are colored as `--color` says.
+fn init_tracing(quiet: bool, color: ui::styles::ColorChoice) {
+    use tracing_subscriber::{Layer, filter::LevelFilter, layer::SubscriberExt, util::SubscriberInitExt};
+
+    let fmt = tracing_subscriber::fmt::layer().with_target(true).with_thread_ids(false);
+    let logs = if !quiet {
+        use std::io::IsTerminal;
+        fmt.with_ansi(color.enabled(std::io::stderr().is_terminal()))
+            .with_writer(std::io::stderr)
+            .with_filter(EnvFilter::from_default_env())
+            .boxed()
+    } else {
+        let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn"));
+        let file = quiet_log_path().and_then(|path| {
+            std::fs::create_dir_all(path.parent()?).ok()?;
+            std::fs::OpenOptions::new().create(true).append(true).open(path).ok()
+        });
+        match file {
+            Some(file) => fmt.with_ansi(false).with_writer(std::sync::Mutex::new(file)).with_filter(filter).boxed(),
+            None => fmt.with_writer(std::io::sink).with_filter(filter).boxed(),
+        }
+    };
+    tracing_subscriber::registry()
+        .with(logs)
+        .with(ui::status::StatusLayer.with_filter(LevelFilter::WARN))
         .init();
+}
 
+#[tokio::main]
+async fn main() -> std::process::ExitCode {
+    match run().await {
+        Ok(()) => std::process::ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::ExitCode::from(exit::code(&*e))
+        }
+    }
+}
+
+/// Runs the selected mode; the error decides the exit code ([`exit`]).
+async fn run() -> Result<(), Box<dyn Error + Send + Sync>> {
     let mut cfg = Config::parse();
+    init_tracing(cfg.quiet, cfg.color);
+    i18n::init();
+    if cfg.init_config {
+        return init_config(&cfg);
+    }
+    cfg.file = config::FileConfig::load(cfg.config.as_deref());
+    lyrics::providers::configure(cfg.file.providers.clone());
     providers_from_env_if_empty(&mut cfg);
+    if cfg.version {
+        print_version(&cfg);
+        return Ok(());
+    }
+    if cfg.print_config {
+        return print_config(&cfg);
+    }
 
     initialize_database(&cfg).await;
 
-    // Fetch initial state from player (fallback to defaults on error)
-    let service = cfg.player_service.as_deref().unwrap_or("");
-    let meta = fetch_initial_metadata(service).await;
-    let position = fetch_initial_position(service).await;
+    if let Some(command) = &cfg.command {
+        return commands::run(command, &cfg).await;
+    }
 
     // Start UI and propagate any errors
-    start_ui(meta, position, cfg).await.map_err(|e| {
+    start_ui(cfg).await.map_err(|e| {
         tracing::error!(error = %e, "Application error");
         e
     })
@@ -0,0 +1,147 @@
+//! Metadata cleanup for web browsers exposing MPRIS.
+//!
+//! Browsers forward the page or video title as `xesam:title`, usually with
+//! site and upload decorations ("Artist - Song (Official Video) - YouTube")
+//! and often without any `xesam:artist`. Providers can't match that, so for
+//! browser players the title is stripped of known decorations and split into
+//! artist and title when the artist is missing.
+
+use crate::mpris::metadata::TrackMetadata;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Bus-name fragments identifying browser MPRIS players.
+const BROWSER_SERVICES: &[&str] = &[
+    "firefox",
+    "chromium",
+    "chrome",
+    "brave",
+    "vivaldi",
+    "opera",
+    "edge",
+    "epiphany",
+    "plasma-browser-integration",
+];
+
+/// Trailing site names: " - YouTube", " | SoundCloud", ...
+static SITE_SUFFIX_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\s*[-–—|]\s*(youtube music|youtube|soundcloud|bandcamp|vimeo|spotify|dailymotion)\s*$")
+        .unwrap()
+});
+
+/// Upload decorations in brackets: "(Official Video)", "[Lyrics]", "(HD)", ...
+static DECORATION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\s*[(\[]\s*(official\s+)?(music\s+|lyrics?\s+|hd\s+|4k\s+)?(video|audio|visuali[sz]er|mv|m/v|lyrics?|hd|hq|4k|official)(\s+video)?\s*[)\]]",
+    )
+    .unwrap()
+});
+
+/// CJK full-width bracket tags: "【MV】", "【Official】", ...
+static CJK_TAG_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s*【[^】]*】\s*").unwrap());
+
+/// Unread-notification counters prefixed by browsers: "(3) Song".
+static NOTIFICATION_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\(\d+\)\s+").unwrap());
+
+/// Separators used in "Artist - Title" video titles.
+const ARTIST_SEPARATORS: &[&str] = &[" - ", " – ", " — ", " | "];
+
+/// Returns `true` if the bus name belongs to a web browser.
+pub fn is_browser(service: &str) -> bool {
+    let service_lower = service.to_lowercase();
+    BROWSER_SERVICES.iter().any(|b| service_lower.contains(b))
+}
+
+/// Cleans up browser-provided metadata in place.
+///
+/// Does nothing for non-browser services.
+pub fn clean_metadata(service: &str, meta: &mut TrackMetadata) {
+    if !is_browser(service) {
+        return;
+    }
+
+    let mut title = clean_title(&meta.title);
+
+    // YouTube auto-generated channels are named "Artist - Topic"
+    let mut artist = meta.artist.trim().trim_end_matches(" - Topic").to_string();
+
+    if artist.is_empty()
+        && let Some((left, right)) = split_artist_title(&title)
+    {
+        artist = left;
+        title = right;
+    }
+
+    meta.title = title;
+    meta.artist = artist;
+}
+
+/// Strips site suffixes and upload decorations from a browser title.
+fn clean_title(title: &str) -> String {
+    let mut cleaned = NOTIFICATION_RE.replace(title.trim(), "").to_string();
+
+    // Decorations can be stacked ("... (Official Video) [HD] - YouTube"),
+    // so strip until nothing changes.
+    loop {
+        let next = SITE_SUFFIX_RE.replace(&cleaned, "");
+        let next = DECORATION_RE.replace_all(&next, "");
+        let next = CJK_TAG_RE.replace_all(&next, " ");
+        let next = next.trim().to_string();
+        if next == cleaned {
+            break;
+        }
+        cleaned = next;
+    }
+
+    cleaned
+}
+
+/// Splits "Artist - Title" on the first recognised separator.
+pub(crate) fn split_artist_title(title: &str) -> Option<(String, String)> {
+    ARTIST_SEPARATORS.iter().find_map(|sep| {
+        let (left, right) = title.split_once(sep)?;
+        let (left, right) = (left.trim(), right.trim());
+        (!left.is_empty() && !right.is_empty()).then(|| (left.to_string(), right.to_string()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn browser_meta(title: &str, artist: &str) -> TrackMetadata {
+        let mut meta = TrackMetadata {
+            title: title.to_string(),
+            artist: artist.to_string(),
+            ..Default::default()
+        };
+        clean_metadata("org.mpris.MediaPlayer2.firefox.instance_1_42", &mut meta);
+        meta
+    }
+
+    #[test]
+    fn test_browser_title_cleanup() {
+        let meta = browser_meta("Arctic Monkeys - Do I Wanna Know? (Official Video) - YouTube", "");
+        assert_eq!(meta.artist, "Arctic Monkeys");
+        assert_eq!(meta.title, "Do I Wanna Know?");
+
+        let meta = browser_meta("【MV】夜に駆ける", "YOASOBI - Topic");
+        assert_eq!(meta.artist, "YOASOBI");
+        assert_eq!(meta.title, "夜に駆ける");
+
+        // Existing artist is kept and the title isn't split
+        let meta = browser_meta("Song - Live [HD]", "Band");
+        assert_eq!(meta.artist, "Band");
+        assert_eq!(meta.title, "Song - Live");
+    }
+
+    #[test]
+    fn test_non_browser_untouched() {
+        let mut meta = TrackMetadata {
+            title: "Song (Official Video)".to_string(),
+            ..Default::default()
+        };
+        clean_metadata("org.mpris.MediaPlayer2.spotify", &mut meta);
+        assert_eq!(meta.title, "Song (Official Video)");
+    }
+}
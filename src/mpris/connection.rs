@@ -1,6 +1,8 @@
 //! D-Bus connection management and player discovery for MPRIS.
 
-use std::sync::Arc;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use tokio::sync::OnceCell;
 use zbus::proxy;
 
@@ -11,11 +13,16 @@ pub enum MprisError {
     ZBus(#[from] zbus::Error),
     #[error("Failed to establish D-Bus connection")]
     NoConnection,
+    #[error("Player signal streams ended")]
+    StreamsEnded,
 }
 
 /// Global D-Bus connection singleton
 static DBUS_CONNECTION: OnceCell<Arc<zbus::Connection>> = OnceCell::const_new();
 
+/// Second session connection, carrying only signal subscriptions
+static SIGNAL_CONNECTION: OnceCell<Arc<zbus::Connection>> = OnceCell::const_new();
+
 /// Get or create a shared D-Bus session connection
 pub async fn get_dbus_conn() -> Result<Arc<zbus::Connection>, MprisError> {
     DBUS_CONNECTION
@@ -29,6 +36,27 @@ pub async fn get_dbus_conn() -> Result<Arc<zbus::Connection>, MprisError> {
         .cloned()
 }
 
+/// Get or create the connection for signal streams (`Seeked`, property
+/// changes).
+///
+/// Kept apart from [`get_dbus_conn`] so a property query to a player that
+/// is slow to answer can't hold up signal delivery. Falls back to the
+/// shared connection if a second one can't be opened.
+pub async fn get_signal_conn() -> Result<Arc<zbus::Connection>, MprisError> {
+    SIGNAL_CONNECTION
+        .get_or_try_init(|| async {
+            match zbus::Connection::session().await {
+                Ok(conn) => Ok(Arc::new(conn)),
+                Err(e) => {
+                    tracing::debug!(error = %e, "No separate D-Bus connection for signals, sharing the main one");
+                    get_dbus_conn().await
+                }
+            }
+        })
+        .await
+        .cloned()
+}
+
 /// Proxy interface for playerctld to get active MPRIS players
 #[proxy(
     interface = "com.github.altdesktop.playerctld",
@@ -40,6 +68,19 @@ trait Playerctld {
     fn player_names(&self) -> zbus::Result<Vec<String>>;
 }
 
+/// Root MPRIS interface, for the player's human-readable name
+#[proxy(
+    interface = "org.mpris.MediaPlayer2",
+    default_path = "/org/mpris/MediaPlayer2"
+)]
+trait MediaPlayer2 {
+    #[zbus(property)]
+    fn identity(&self) -> zbus::Result<String>;
+}
+
+/// `Identity` of each bus name seen so far (fixed for the lifetime of a name)
+static IDENTITIES: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(Default::default);
+
 /// Get list of active MPRIS player service names
 /// 
 /// This queries playerctld if available, otherwise returns an empty list.
@@ -63,3 +104,92 @@ pub fn is_blocked(service: &str, block_list: &[String]) -> bool {
         .iter()
         .any(|blocked| service_lower.contains(&blocked.to_lowercase()))
 }
+
+/// Get a player's MPRIS `Identity` ("Chromium", "Firefox"), if it reports one
+pub async fn get_identity(service: &str) -> Option<String> {
+    if let Some(identity) = IDENTITIES.lock().ok()?.get(service) {
+        return Some(identity.clone());
+    }
+
+    let conn = get_dbus_conn().await.ok()?;
+    let proxy = MediaPlayer2Proxy::builder(&conn)
+        .destination(service.to_string())
+        .ok()?
+        .build()
+        .await
+        .ok()?;
+    let identity = proxy.identity().await.ok()?;
+    IDENTITIES.lock().ok()?.insert(service.to_string(), identity.clone());
+    Some(identity)
+}
+
+/// Check if a player should be blocked
+///
+/// Like [`is_blocked`], but also matches the block list against the
+/// player's `Identity`, for players whose bus names carry random instance
+/// suffixes (`org.mpris.MediaPlayer2.chromium.instance12345`).
+pub async fn is_player_blocked(service: &str, block_list: &[String]) -> bool {
+    if block_list.is_empty() {
+        return false;
+    }
+    if is_blocked(service, block_list) {
+        return true;
+    }
+    get_identity(service)
+        .await
+        .is_some_and(|identity| is_blocked(&identity, block_list))
+}
+
+/// Get the first player in `names` that isn't blocked
+pub async fn first_unblocked(names: &[String], block_list: &[String]) -> Option<String> {
+    for name in names {
+        if !is_player_blocked(name, block_list).await {
+            return Some(name.clone());
+        }
+    }
+    None
+}
+
+/// Full bus name for a `--player-service` value: `spotify` becomes
+/// `org.mpris.MediaPlayer2.spotify`, full names are kept as they are.
+pub fn bus_name(service: &str) -> String {
+    const PREFIX: &str = "org.mpris.MediaPlayer2.";
+    if service.starts_with(PREFIX) {
+        service.to_string()
+    } else {
+        format!("{PREFIX}{service}")
+    }
+}
+
+/// Get the player to follow: `player_service` (`--player-service`) while
+/// it is on the bus, otherwise the first unblocked player playerctld knows
+/// about.
+pub async fn select_player(
+    player_service: Option<&str>,
+    block_list: &[String],
+) -> Result<Option<String>, MprisError> {
+    if let Some(service) = player_service {
+        let service = bus_name(service);
+        let conn = get_dbus_conn().await?;
+        let dbus = zbus::fdo::DBusProxy::new(&conn).await?;
+        let running = dbus
+            .name_has_owner(service.as_str().try_into().map_err(zbus::Error::from)?)
+            .await
+            .map_err(zbus::Error::from)?;
+        return Ok(running.then_some(service));
+    }
+    let names = get_active_player_names().await?;
+    tracing::debug!(available_players = ?names, "Discovered MPRIS players");
+    Ok(first_unblocked(&names, block_list).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bus_name() {
+        assert_eq!(bus_name("spotify"), "org.mpris.MediaPlayer2.spotify");
+        assert_eq!(bus_name("org.mpris.MediaPlayer2.vlc"), "org.mpris.MediaPlayer2.vlc");
+    }
+}
@@ -1,11 +1,13 @@
 //! Event watching and handler registration for MPRIS signals.
 
-use crate::mpris::connection::{get_active_player_names, get_dbus_conn, is_blocked, MprisError};
-use crate::mpris::metadata::{extract_metadata, TrackMetadata};
+use crate::mpris::connection::{get_dbus_conn, get_signal_conn, select_player, MprisError};
+use crate::mpris::metadata::{extract_service_metadata, TrackMetadata};
 use crate::mpris::playback::get_position;
+use crate::mpris::quirks::{self, Quirks};
 use futures_util::StreamExt;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use zbus::proxy;
 use zvariant::OwnedValue;
 
@@ -49,6 +51,39 @@ where
     }
 }
 
+/// Difference (in seconds) between a reported `Position` and the expected
+/// one above which the change is treated as a seek.
+const SEEK_DETECTION_THRESHOLD: f64 = 1.5;
+
+/// Difference (in seconds) below which a reported `Position` is regular
+/// progress the event loop already estimates, and is dropped.
+const POSITION_DRIFT_THRESHOLD: f64 = 0.25;
+
+/// First delay before resubscribing to a player whose signal streams ended
+/// or whose proxy couldn't be built; doubled on each failure in a row.
+const RESUBSCRIBE_BACKOFF_MIN: Duration = Duration::from_millis(250);
+/// Longest delay between resubscription attempts.
+const RESUBSCRIBE_BACKOFF_MAX: Duration = Duration::from_secs(8);
+
+/// Delay before the next resubscription attempt.
+#[derive(Debug, Default)]
+struct Backoff {
+    delay: Option<Duration>,
+}
+
+impl Backoff {
+    /// Records a failure and returns the delay before the next attempt.
+    fn fail(&mut self) -> Duration {
+        let delay = self.delay.map_or(RESUBSCRIBE_BACKOFF_MIN, |d| (d * 2).min(RESUBSCRIBE_BACKOFF_MAX));
+        self.delay = Some(delay);
+        delay
+    }
+
+    fn reset(&mut self) {
+        self.delay = None;
+    }
+}
+
 /// Represents the current state of the active player
 #[derive(Debug, Clone, Default)]
 struct PlayerState {
@@ -56,6 +91,8 @@ struct PlayerState {
     track: TrackMetadata,
     playback_status: String,
     position: f64,
+    /// When `position` was last observed
+    position_at: Option<Instant>,
 }
 
 impl PlayerState {
@@ -63,6 +100,33 @@ impl PlayerState {
         !self.service.is_empty()
     }
 
+    /// Records an observed position.
+    fn set_position(&mut self, position: f64) {
+        self.position = position;
+        self.position_at = Some(Instant::now());
+    }
+
+    /// Position expected now from the last observation, advancing while playing.
+    fn expected_position(&self) -> f64 {
+        match self.position_at {
+            Some(at) if self.playback_status == "Playing" => self.position + at.elapsed().as_secs_f64(),
+            _ => self.position,
+        }
+    }
+
+    /// Whether a reported position is a jump rather than normal progress.
+    ///
+    /// Players that never emit `Seeked` still update `Position`; a large
+    /// difference from the expected position is how their seeks show up.
+    fn is_seek(&self, position: f64) -> bool {
+        self.drift(position) > SEEK_DETECTION_THRESHOLD
+    }
+
+    /// Distance between a reported position and the expected one.
+    fn drift(&self, position: f64) -> f64 {
+        (position - self.expected_position()).abs()
+    }
+
     fn clear(&mut self) {
         *self = Self::default();
     }
@@ -102,20 +166,42 @@ trait Playerctld {
 pub struct MprisEventHandler<C: MprisEventCallback> {
     callback: C,
     block_list: Arc<Vec<String>>,
+    /// Player to follow instead of discovering one (`--player-service`)
+    player_service: Option<String>,
     state: PlayerState,
+    /// Config file, for per-player quirk overrides
+    file_config: crate::config::FileConfig,
+    /// Quirks of the active player
+    quirks: Quirks,
+    /// Connection for property queries
     conn: Arc<zbus::Connection>,
+    /// Dedicated connection for signal streams (see [`get_signal_conn`])
+    signal_conn: Arc<zbus::Connection>,
+    /// Pending delay before resubscribing to the active player
+    backoff: Backoff,
 }
 
 impl<C: MprisEventCallback> MprisEventHandler<C> {
     /// Create a new MPRIS event handler
-    pub async fn new(callback: C, block_list: Vec<String>) -> Result<Self, MprisError> {
+    pub async fn new(
+        callback: C,
+        block_list: Vec<String>,
+        player_service: Option<String>,
+        file_config: crate::config::FileConfig,
+    ) -> Result<Self, MprisError> {
         let conn = get_dbus_conn().await?;
+        let signal_conn = get_signal_conn().await?;
 
         let mut handler = Self {
             callback,
             block_list: Arc::new(block_list),
+            player_service,
             state: PlayerState::default(),
+            file_config,
+            quirks: Quirks::default(),
             conn: conn.clone(),
+            signal_conn,
+            backoff: Backoff::default(),
         };
 
         // Discover initial active player
@@ -127,7 +213,7 @@ impl<C: MprisEventCallback> MprisEventHandler<C> {
     /// Main event loop - processes incoming MPRIS signals
     pub async fn handle_events(&mut self) -> Result<(), MprisError> {
         // Subscribe to playerctld property changes to detect player switches
-        let playerctld_proxy = PlayerctldProxy::new(&self.conn).await.ok();
+        let playerctld_proxy = PlayerctldProxy::new(&self.signal_conn).await.ok();
 
         let mut player_names_stream = if let Some(ref proxy) = playerctld_proxy {
             tracing::debug!("Subscribed to playerctld player_names changes");
@@ -164,31 +250,68 @@ impl<C: MprisEventCallback> MprisEventHandler<C> {
     }
 
     /// Handle events from the currently active player
+    ///
+    /// When the subscription fails (the proxy can't be built, or every signal
+    /// stream ended because the player restarted under the same name), the
+    /// next call waits with exponential backoff before subscribing again.
     async fn handle_player_events(&mut self) -> Result<(), MprisError> {
         if !self.state.is_active() {
             // No active player, wait a bit before checking again
             tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+            // Without playerctld nothing announces a --player-service player starting
+            if self.player_service.is_some() {
+                self.discover_active_player().await?;
+            }
             return Ok(());
         }
+        if let Some(delay) = self.backoff.delay {
+            tokio::time::sleep(delay).await;
+        }
 
         let service = self.state.service.clone();
+        let subscribed_at = Instant::now();
+        match self.watch_player(&service).await {
+            Ok(()) => self.backoff.reset(),
+            Err(e) => {
+                // A subscription that worked for a while starts over with a short delay
+                if subscribed_at.elapsed() > RESUBSCRIBE_BACKOFF_MAX {
+                    self.backoff.reset();
+                }
+                let delay = self.backoff.fail();
+                tracing::debug!(service = %service, error = %e, delay_ms = delay.as_millis() as u64, "Resubscribing to player events");
+            }
+        }
+        Ok(())
+    }
+
+    /// Subscribes to the signals of `service` and handles them until the
+    /// player disconnects (`Ok`) or its streams end.
+    async fn watch_player(&mut self, service: &str) -> Result<(), MprisError> {
         tracing::debug!(service = %service, "Subscribing to player events");
         
+        let signals = MediaPlayer2PlayerProxy::builder(&self.signal_conn)
+            .destination(service)?
+            .build()
+            .await?;
+        // Queries go over the other connection, so a slow reply can't hold up
+        // the signals; uncached, they always reach the player
         let proxy = MediaPlayer2PlayerProxy::builder(&self.conn)
-            .destination(service.as_str())?
+            .destination(service)?
+            .cache_properties(zbus::proxy::CacheProperties::No)
             .build()
             .await?;
 
         // Subscribe to signals and property changes
-        let mut seeked_stream = proxy.receive_seeked().await?;
-        let mut metadata_stream = proxy.receive_metadata_changed().await;
-        let mut position_stream = proxy.receive_position_changed().await;
-        let mut status_stream = proxy.receive_playback_status_changed().await;
+        let mut seeked_stream = signals.receive_seeked().await?;
+        let mut metadata_stream = signals.receive_metadata_changed().await;
+        let mut position_stream = signals.receive_position_changed().await;
+        let mut status_stream = signals.receive_playback_status_changed().await;
 
         loop {
             tokio::select! {
                 // Handle Seeked signal
-                Some(signal) = seeked_stream.next() => {
+                signal = seeked_stream.next() => {
+                    let Some(signal) = signal else { break };
                     if let Ok(args) = signal.args() {
                         tracing::debug!(service = %service, position = args.position, "Seeked signal received");
                         self.handle_seek_signal(args.position).await;
@@ -196,7 +319,10 @@ impl<C: MprisEventCallback> MprisEventHandler<C> {
                 }
                 
                 // Handle Metadata property change
-                Some(_) = metadata_stream.next() => {
+                change = metadata_stream.next() => {
+                    if change.is_none() {
+                        break;
+                    }
                     tracing::debug!(service = %service, "Metadata changed");
                     if let Err(e) = self.handle_metadata_change(&proxy).await {
                         tracing::warn!(
@@ -208,7 +334,10 @@ impl<C: MprisEventCallback> MprisEventHandler<C> {
                 }
                 
                 // Handle Position property change (not common, but some players use it)
-                Some(_) = position_stream.next() => {
+                change = position_stream.next() => {
+                    if change.is_none() {
+                        break;
+                    }
                     tracing::debug!(service = %service, "Position changed");
                     if let Err(e) = self.handle_position_change(&proxy).await {
                         tracing::warn!(
@@ -220,7 +349,10 @@ impl<C: MprisEventCallback> MprisEventHandler<C> {
                 }
                 
                 // Handle PlaybackStatus property change
-                Some(_) = status_stream.next() => {
+                change = status_stream.next() => {
+                    if change.is_none() {
+                        break;
+                    }
                     tracing::debug!(service = %service, "Playback status changed");
                     if let Err(e) = self.handle_status_change(&proxy).await {
                         tracing::warn!(
@@ -244,18 +376,24 @@ impl<C: MprisEventCallback> MprisEventHandler<C> {
                                 "Failed to discover player after disconnect"
                             );
                         }
-                        break; // Exit inner loop to restart with new player
+                        return Ok(()); // Restart with the new player
                     }
                 }
             }
         }
 
-        Ok(())
+        // A stream ended: the signal subscriptions are gone, rebuild them
+        Err(MprisError::StreamsEnded)
     }
 
     async fn handle_seek_signal(&mut self, position_microsecs: i64) {
         let position = position_microsecs as f64 / 1_000_000.0;
-        self.state.position = position;
+        if self.quirks.redundant_seeked && !self.state.is_seek(position) {
+            tracing::trace!(service = %self.state.service, position = position, "Dropping redundant Seeked signal");
+            self.state.set_position(position);
+            return;
+        }
+        self.state.set_position(position);
         self.callback.on_seek(
             self.state.track.clone(),
             position,
@@ -268,14 +406,17 @@ impl<C: MprisEventCallback> MprisEventHandler<C> {
         proxy: &MediaPlayer2PlayerProxy<'_>,
     ) -> Result<(), MprisError> {
         let metadata_map = proxy.metadata().await?;
-        let new_track = extract_metadata(&metadata_map);
+        let new_track = extract_service_metadata(&metadata_map, &self.state.service);
         
         if new_track != self.state.track {
             self.state.track = new_track;
             
-            // Also update position when track changes
-            if let Ok(pos_microsecs) = proxy.position().await {
-                self.state.position = pos_microsecs as f64 / 1_000_000.0;
+            // Also update position when track changes (unless the player
+            // is known to still report the previous track's position)
+            if self.quirks.stale_track_change_position {
+                self.state.set_position(0.0);
+            } else if let Ok(pos_microsecs) = proxy.position().await {
+                self.state.set_position(pos_microsecs as f64 / 1_000_000.0);
             }
             
             self.callback.on_track_change(
@@ -294,7 +435,18 @@ impl<C: MprisEventCallback> MprisEventHandler<C> {
     ) -> Result<(), MprisError> {
         if let Ok(pos_microsecs) = proxy.position().await {
             let position = pos_microsecs as f64 / 1_000_000.0;
-            self.state.position = position;
+            let drift = self.state.drift(position);
+            self.state.set_position(position);
+
+            // Regular progress: nothing the event loop doesn't already know
+            if drift < POSITION_DRIFT_THRESHOLD {
+                return Ok(());
+            }
+
+            // Anything else re-anchors the position in place, like a seek
+            if drift > SEEK_DETECTION_THRESHOLD {
+                tracing::debug!(service = %self.state.service, position = position, "Position jump treated as seek");
+            }
             self.callback.on_seek(
                 self.state.track.clone(),
                 position,
@@ -316,7 +468,7 @@ impl<C: MprisEventCallback> MprisEventHandler<C> {
             
             // Get fresh position on playback status change
             let position = if let Ok(pos) = get_position(&self.state.service).await {
-                self.state.position = pos;
+                self.state.set_position(pos);
                 pos
             } else {
                 self.state.position
@@ -335,13 +487,10 @@ impl<C: MprisEventCallback> MprisEventHandler<C> {
 
     /// Discovers and switches to the active unblocked player
     async fn discover_active_player(&mut self) -> Result<(), MprisError> {
-        let names = get_active_player_names().await?;
-        tracing::debug!(available_players = ?names, "Discovered available players");
-
-        if let Some(service) = names.iter().find(|s| !is_blocked(s, &self.block_list)) {
-            if *service != self.state.service {
+        if let Some(service) = select_player(self.player_service.as_deref(), &self.block_list).await? {
+            if service != self.state.service {
                 tracing::debug!(old_service = %self.state.service, new_service = %service, "Switching to player");
-                self.switch_to_player(service).await?;
+                self.switch_to_player(&service).await?;
             }
         } else if self.state.is_active() {
             // No active players found, but we had one before
@@ -362,7 +511,7 @@ impl<C: MprisEventCallback> MprisEventHandler<C> {
         let metadata = proxy
             .metadata()
             .await
-            .map(|map| extract_metadata(&map))
+            .map(|map| extract_service_metadata(&map, service))
             .unwrap_or_default();
         
         let position = proxy
@@ -385,11 +534,18 @@ impl<C: MprisEventCallback> MprisEventHandler<C> {
             "Switched to player"
         );
 
+        self.backoff.reset();
+        self.quirks = quirks::resolve(service, self.file_config.player_override(service));
+        if self.quirks != Quirks::default() {
+            tracing::debug!(service = %service, quirks = ?self.quirks, "Applying player quirks");
+        }
+
         self.state = PlayerState {
             service: service.to_string(),
             track: metadata.clone(),
             playback_status,
             position,
+            position_at: Some(Instant::now()),
         };
 
         self.callback.on_track_change(metadata, position, service.to_string());
@@ -417,8 +573,39 @@ where
         on_track_change: F,
         on_seek: G,
         block_list: Vec<String>,
+        player_service: Option<String>,
+        file_config: crate::config::FileConfig,
     ) -> Result<Self, MprisError> {
         let callback = ClosureCallback::new(on_track_change, on_seek);
-        Self::new(callback, block_list).await
+        Self::new(callback, block_list, player_service, file_config).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_jump_is_seek() {
+        let mut state = PlayerState { playback_status: "Playing".into(), ..Default::default() };
+        state.set_position(30.0);
+        assert!(!state.is_seek(30.5));
+        assert!(state.is_seek(90.0));
+        assert!(state.is_seek(10.0));
+        assert!(state.drift(30.1) < POSITION_DRIFT_THRESHOLD);
+        assert!(state.drift(31.0) > POSITION_DRIFT_THRESHOLD);
+    }
+
+    #[test]
+    fn test_resubscribe_backoff() {
+        let mut backoff = Backoff::default();
+        assert_eq!(backoff.fail(), RESUBSCRIBE_BACKOFF_MIN);
+        assert_eq!(backoff.fail(), RESUBSCRIBE_BACKOFF_MIN * 2);
+        for _ in 0..10 {
+            backoff.fail();
+        }
+        assert_eq!(backoff.delay, Some(RESUBSCRIBE_BACKOFF_MAX));
+        backoff.reset();
+        assert_eq!(backoff.fail(), RESUBSCRIBE_BACKOFF_MIN);
     }
 }
@@ -13,6 +13,8 @@ pub struct TrackMetadata {
     pub album: String,
     pub length: Option<f64>,
     pub spotify_id: Option<String>,
+    /// Internet radio stream, with artist/title parsed from the stream title
+    pub is_stream: bool,
 }
 
 /// Internal metadata structure matching MPRIS specification
@@ -73,6 +75,7 @@ impl From<MprisMetadata> for TrackMetadata {
             album,
             length,
             spotify_id,
+            is_stream: false,
         }
     }
 }
@@ -152,9 +155,21 @@ pub fn extract_metadata(map: &HashMap<String, OwnedValue>) -> TrackMetadata {
         album,
         length,
         spotify_id,
+        is_stream: false,
     }
 }
 
+/// Extract metadata for a specific player service.
+///
+/// Like [`extract_metadata`], plus player-specific cleanup (e.g. browser
+/// titles) and radio stream title parsing.
+pub fn extract_service_metadata(map: &HashMap<String, OwnedValue>, service: &str) -> TrackMetadata {
+    let mut meta = extract_metadata(map);
+    crate::mpris::browser::clean_metadata(service, &mut meta);
+    crate::mpris::radio::parse_stream_title(&mut meta);
+    meta
+}
+
 /// MPRIS MediaPlayer2.Player interface proxy
 #[proxy(
     interface = "org.mpris.MediaPlayer2.Player",
@@ -179,11 +194,45 @@ pub async fn get_metadata(service: &str) -> Result<TrackMetadata, MprisError> {
         .await?;
 
     match proxy.metadata().await {
-        Ok(metadata_map) => Ok(extract_metadata(&metadata_map)),
+        Ok(metadata_map) => Ok(extract_service_metadata(&metadata_map, service)),
         Err(_) => Ok(TrackMetadata::default()),
     }
 }
 
+/// MPRIS MediaPlayer2.TrackList interface proxy
+#[proxy(
+    interface = "org.mpris.MediaPlayer2.TrackList",
+    default_path = "/org/mpris/MediaPlayer2"
+)]
+trait TrackList {
+    #[zbus(property)]
+    fn tracks(&self) -> zbus::Result<Vec<zvariant::OwnedObjectPath>>;
+
+    fn get_tracks_metadata(
+        &self,
+        track_ids: &[zvariant::OwnedObjectPath],
+    ) -> zbus::Result<Vec<HashMap<String, OwnedValue>>>;
+}
+
+/// Query the metadata of every track in a player's play queue.
+///
+/// Fails if the player doesn't implement the optional `TrackList` interface.
+pub async fn get_track_list(service: &str) -> Result<Vec<TrackMetadata>, MprisError> {
+    let conn = get_dbus_conn().await?;
+
+    let proxy = TrackListProxy::builder(&conn)
+        .destination(service)?
+        .build()
+        .await?;
+
+    let tracks = proxy.tracks().await?;
+    if tracks.is_empty() {
+        return Ok(Vec::new());
+    }
+    let maps = proxy.get_tracks_metadata(&tracks).await?;
+    Ok(maps.iter().map(|map| extract_service_metadata(map, service)).collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
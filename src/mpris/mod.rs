@@ -1,11 +1,14 @@
 //! MPRIS module: re-exports and module declarations for submodules.
 
+pub mod browser;
 pub mod connection;
 pub mod events;
 pub mod metadata;
 pub mod playback;
+pub mod quirks;
+pub mod radio;
 
 // Re-export main API for compatibility
-pub use connection::{get_active_player_names, is_blocked};
+pub use connection::select_player;
 pub use metadata::TrackMetadata;
 pub use playback::get_playback_status;
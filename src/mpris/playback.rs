@@ -4,10 +4,11 @@ use crate::mpris::connection::{get_dbus_conn, MprisError};
 use zbus::proxy;
 
 /// Playback status values according to MPRIS specification
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum PlaybackStatus {
     Playing,
     Paused,
+    #[default]
     Stopped,
 }
 
@@ -31,12 +32,6 @@ impl PlaybackStatus {
     }
 }
 
-impl Default for PlaybackStatus {
-    fn default() -> Self {
-        Self::Stopped
-    }
-}
-
 impl From<String> for PlaybackStatus {
     fn from(s: String) -> Self {
         Self::from_str(&s)
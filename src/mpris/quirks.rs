@@ -0,0 +1,67 @@
+//! Built-in workarounds for players with non-conforming MPRIS behaviour.
+//!
+//! Quirks are keyed by a case-insensitive substring of the bus name, like
+//! `--block`. Matching `[players.<name>]` sections in the config file can
+//! switch each quirk on or off explicitly.
+
+use crate::config::PlayerOverride;
+
+/// Behavioural workarounds for one player.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Quirks {
+    /// The reported `Position` is stale (still from the previous track)
+    /// right after a track change; estimate from zero instead.
+    pub stale_track_change_position: bool,
+    /// The player emits `Seeked` continuously during normal playback;
+    /// drop signals whose position matches regular progress.
+    pub redundant_seeked: bool,
+}
+
+/// Players with known quirks, keyed by bus-name substring.
+const BUILTIN: &[(&str, Quirks)] = &[
+    (
+        "spotify",
+        Quirks { stale_track_change_position: true, redundant_seeked: false },
+    ),
+    (
+        "vlc",
+        Quirks { stale_track_change_position: false, redundant_seeked: true },
+    ),
+];
+
+/// Resolves the quirks for `service`: the built-in profile (if any), with
+/// explicitly set fields of the config-file override taking precedence.
+pub fn resolve(service: &str, overrides: Option<&PlayerOverride>) -> Quirks {
+    let service_lower = service.to_lowercase();
+    let mut quirks = BUILTIN
+        .iter()
+        .find(|(key, _)| service_lower.contains(key))
+        .map(|(_, quirks)| *quirks)
+        .unwrap_or_default();
+
+    if let Some(overrides) = overrides {
+        if let Some(stale) = overrides.ignore_track_change_position {
+            quirks.stale_track_change_position = stale;
+        }
+        if let Some(redundant) = overrides.ignore_redundant_seeked {
+            quirks.redundant_seeked = redundant;
+        }
+    }
+
+    quirks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_quirks() {
+        assert!(resolve("org.mpris.MediaPlayer2.spotify", None).stale_track_change_position);
+        assert!(resolve("org.mpris.MediaPlayer2.vlc", None).redundant_seeked);
+        assert_eq!(resolve("org.mpris.MediaPlayer2.mpv", None), Quirks::default());
+
+        let disable = PlayerOverride { ignore_track_change_position: Some(false), ..Default::default() };
+        assert!(!resolve("org.mpris.MediaPlayer2.spotify", Some(&disable)).stale_track_change_position);
+    }
+}
@@ -0,0 +1,50 @@
+//! Metadata handling for internet radio streams.
+//!
+//! Players playing a stream usually report the station's ICY `StreamTitle`
+//! ("Artist - Song") as `xesam:title`, with no artist and no length. Such
+//! titles are split into artist and title and the track is marked as a
+//! stream: its `Position` counts from when the stream was opened, so lyrics
+//! are timed from the moment the stream title changed instead.
+
+use crate::mpris::browser::split_artist_title;
+use crate::mpris::metadata::TrackMetadata;
+
+/// Splits radio stream titles in place and marks the track as a stream.
+///
+/// Tracks with a length or an artist are left untouched.
+pub fn parse_stream_title(meta: &mut TrackMetadata) {
+    if meta.length.is_some_and(|len| len > 0.0) || !meta.artist.trim().is_empty() {
+        return;
+    }
+    let Some((artist, title)) = split_artist_title(meta.title.trim()) else {
+        return;
+    };
+
+    meta.artist = artist;
+    meta.title = title;
+    meta.length = None;
+    meta.is_stream = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_title_parsing() {
+        let mut meta = TrackMetadata { title: "Daft Punk - One More Time".into(), ..Default::default() };
+        parse_stream_title(&mut meta);
+        assert_eq!((meta.artist.as_str(), meta.title.as_str()), ("Daft Punk", "One More Time"));
+        assert!(meta.is_stream);
+
+        // Regular tracks keep their metadata
+        let mut meta = TrackMetadata {
+            title: "Song - Live".into(),
+            length: Some(200.0),
+            ..Default::default()
+        };
+        parse_stream_title(&mut meta);
+        assert_eq!(meta.title, "Song - Live");
+        assert!(!meta.is_stream);
+    }
+}
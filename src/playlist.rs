@@ -0,0 +1,84 @@
+//! M3U playlists for `lyricsmpris prefetch`.
+//!
+//! Tracks are named by the `#EXTINF:<seconds>,Artist - Title` line when the
+//! playlist is extended, otherwise by the file name (`Artist - Title.flac`).
+//! Entries that don't follow the `Artist - Title` pattern are skipped, since
+//! tags aren't read from the audio files themselves.
+
+use crate::mpris::TrackMetadata;
+
+/// Tracks listed in an M3U playlist, in order.
+pub fn parse_m3u(text: &str) -> Vec<TrackMetadata> {
+    let mut tracks = Vec::new();
+    let mut extinf: Option<(Option<f64>, String)> = None;
+
+    for line in text.trim_start_matches('\u{feff}').lines().map(str::trim) {
+        if let Some(info) = line.strip_prefix("#EXTINF:") {
+            // `#EXTINF:<seconds> [attributes],<display name>`
+            let (head, name) = info.split_once(',').unwrap_or((info, ""));
+            let length = head
+                .split_whitespace()
+                .next()
+                .and_then(|secs| secs.parse::<f64>().ok())
+                .filter(|&secs| secs > 0.0);
+            extinf = Some((length, name.trim().to_string()));
+        } else if line.is_empty() || line.starts_with('#') {
+            continue;
+        } else {
+            let (length, name) = extinf.take().unwrap_or_else(|| (None, file_stem(line).to_string()));
+            if let Some(track) = track_from_name(&name, length) {
+                tracks.push(track);
+            }
+        }
+    }
+    tracks
+}
+
+/// File name of a path or URL without its extension.
+fn file_stem(entry: &str) -> &str {
+    let name = entry.rsplit(['/', '\\']).next().unwrap_or(entry);
+    name.rsplit_once('.').map_or(name, |(stem, _)| stem)
+}
+
+fn track_from_name(name: &str, length: Option<f64>) -> Option<TrackMetadata> {
+    let (artist, title) = name.split_once(" - ")?;
+    let (artist, title) = (artist.trim(), title.trim());
+    if artist.is_empty() || title.is_empty() {
+        return None;
+    }
+    Some(TrackMetadata {
+        artist: artist.to_string(),
+        title: title.to_string(),
+        length,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_m3u() {
+        let playlist = "\u{feff}#EXTM3U\n\
+            #EXTINF:354 tvg-id=\"x\",Queen - Bohemian Rhapsody\n\
+            /music/queen/track01.flac\n\
+            \n\
+            /music/Daft Punk - One More Time.mp3\n\
+            #EXTINF:-1,Some Radio Stream\n\
+            http://radio.example/stream\n\
+            C:\\Music\\Björk - Jóga.ogg\n";
+
+        let tracks = parse_m3u(playlist);
+        let names: Vec<(&str, &str, Option<f64>)> =
+            tracks.iter().map(|t| (t.artist.as_str(), t.title.as_str(), t.length)).collect();
+        assert_eq!(
+            names,
+            [
+                ("Queen", "Bohemian Rhapsody", Some(354.0)),
+                ("Daft Punk", "One More Time", None),
+                ("Björk", "Jóga", None),
+            ]
+        );
+    }
+}
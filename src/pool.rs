@@ -35,9 +35,10 @@
 //! └─────────────────┘
 //! ```
 
-use crate::event::{self, Event, MprisEvent, process_event, send_update};
+use crate::event::{self, Event, MprisEvent, UserCommand, process_event, send_update};
 use crate::mpris::{TrackMetadata, events::MprisEventHandler};
 use crate::state::{StateBundle, Update};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
@@ -45,39 +46,178 @@ use tokio::sync::mpsc;
 ///
 /// Wraps the main application config and provides convenient accessors
 /// for event loop operations.
-struct LoopConfig {
+pub struct LoopConfig {
     /// Shared reference to main app config
     inner: Arc<crate::Config>,
     /// Ordered list of lyrics providers
     providers: Vec<String>,
+    /// Provider orders for tracks detected as a given language
+    language_providers: HashMap<String, Vec<String>>,
+    /// `--block` entries plus config-file players marked `blocked`
+    block_list: Vec<String>,
+    /// Compiled metadata rewrite rules from the config file
+    rewrite_rules: crate::lyrics::rewrite::RewriteRules,
+    /// Compiled `[[ignore]]` patterns from the config file
+    ignore_rules: crate::lyrics::rewrite::IgnoreRules,
+    /// Event channel for results of background work (e.g. lyrics upgrades);
+    /// `None` when no event loop is running
+    events: Option<mpsc::Sender<Event>>,
 }
 
 impl LoopConfig {
     /// Creates a new loop configuration from the main app config.
     ///
     /// If no providers are specified, defaults to ["lrclib", "musixmatch"].
-    fn new(mut config: crate::Config) -> Self {
-        let providers = if config.providers.is_empty() {
+    /// Providers disabled in the config file are dropped.
+    pub fn new(mut config: crate::Config) -> Self {
+        let mut providers = if config.providers.is_empty() {
             vec!["lrclib".to_string(), "musixmatch".to_string()]
         } else {
             std::mem::take(&mut config.providers)
         };
+        providers.retain(|p| config.file.providers.is_enabled(p));
+        let language_providers = config
+            .file
+            .providers
+            .languages
+            .iter()
+            .map(|(language, order)| {
+                let mut order = order.clone();
+                order.retain(|p| config.file.providers.is_enabled(p));
+                (language.clone(), order)
+            })
+            .collect();
+
+        let mut block_list = config.block.clone();
+        block_list.extend(config.file.blocked_players().cloned());
+        let rewrite_rules = crate::lyrics::rewrite::RewriteRules::compile(&config.file.rewrite);
+        let ignore_rules = crate::lyrics::rewrite::IgnoreRules::compile(&config.file.ignore);
 
         Self {
             inner: Arc::new(config),
             providers,
+            language_providers,
+            block_list,
+            rewrite_rules,
+            ignore_rules,
+            events: None,
         }
     }
 
+    /// Attaches the event loop's channel so background tasks can report back.
+    fn with_events(mut self, events: mpsc::Sender<Event>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Returns a sender into the event loop, if one is running.
+    pub fn events(&self) -> Option<mpsc::Sender<Event>> {
+        self.events.clone()
+    }
+
     /// Returns the list of blocked player services.
     fn block_list(&self) -> &[String] {
-        &self.inner.block
+        &self.block_list
+    }
+
+    /// Returns the player to follow instead of discovering one (`--player-service`).
+    fn player_service(&self) -> Option<&str> {
+        self.inner.player_service.as_deref()
     }
 
     /// Returns the ordered list of lyrics providers.
-    fn providers(&self) -> &[String] {
+    pub fn providers(&self) -> &[String] {
         &self.providers
     }
+
+    /// Returns the provider order for `meta`: the `[providers.languages]`
+    /// entry for the language its title/artist is written in, if any,
+    /// otherwise the general order.
+    pub fn providers_for(&self, meta: &TrackMetadata) -> &[String] {
+        if self.language_providers.is_empty() {
+            return &self.providers;
+        }
+        let sample = format!("{} {}", meta.title, meta.artist);
+        crate::text_utils::detect_language(&sample)
+            .and_then(|language| self.language_providers.get(language))
+            .map_or(&self.providers[..], Vec::as_slice)
+    }
+
+    /// Returns whether tracks are normalized via MusicBrainz before provider lookups.
+    pub fn musicbrainz(&self) -> bool {
+        self.inner.musicbrainz
+    }
+
+    /// Returns the `--heartbeat` interval, at which the loop re-sends its
+    /// state as proof of life.
+    pub fn heartbeat(&self) -> Option<std::time::Duration> {
+        self.inner.heartbeat.filter(|&secs| secs > 0).map(std::time::Duration::from_secs)
+    }
+
+    /// Returns whether unsynced lyrics are scrolled when there are no synced ones.
+    pub fn teleprompter(&self) -> bool {
+        self.inner.teleprompter
+    }
+
+    /// Returns the allowed duration mismatch for cache hits, as a fraction.
+    pub fn duration_tolerance(&self) -> f64 {
+        self.inner.duration_tolerance.max(0.0) / 100.0
+    }
+
+    /// Returns whether track durations are ignored when matching cache entries.
+    pub fn ignore_duration(&self) -> bool {
+        self.inner.ignore_duration
+    }
+
+    /// Returns the duration tolerance for reusing cached lyrics outside a
+    /// cache lookup; infinite with `--ignore-duration`.
+    pub fn match_tolerance(&self) -> f64 {
+        if self.ignore_duration() { f64::INFINITY } else { self.duration_tolerance() }
+    }
+
+    /// Returns the age after which cache entries are refetched, if set.
+    pub fn cache_max_age(&self) -> Option<std::time::Duration> {
+        self.inner.cache_max_age.map(|days| std::time::Duration::from_secs(days * 86_400))
+    }
+
+    /// Returns the drop folder watched for manual lyrics files, if any.
+    pub fn drop_dir(&self) -> Option<&std::path::Path> {
+        self.inner.drop_dir.as_deref()
+    }
+
+    /// Returns the fallback polling interval, if polling is enabled.
+    ///
+    /// Intervals below 100 ms are raised to 100 ms.
+    pub fn poll_interval(&self) -> Option<std::time::Duration> {
+        self.inner
+            .poll_interval
+            .map(|ms| std::time::Duration::from_millis(ms.max(100)))
+    }
+
+    /// Returns the metadata rewrite rules applied before lookups.
+    pub fn rewrite_rules(&self) -> &crate::lyrics::rewrite::RewriteRules {
+        &self.rewrite_rules
+    }
+
+    /// Returns the patterns of tracks that are never looked up.
+    pub fn ignore_rules(&self) -> &crate::lyrics::rewrite::IgnoreRules {
+        &self.ignore_rules
+    }
+
+    /// Returns the provider pinned for `meta` by a `[[pin]]` rule, if any.
+    pub fn pinned_provider(&self, meta: &TrackMetadata) -> Option<&str> {
+        self.inner.file.pinned_provider(&meta.artist, &meta.title, &meta.album)
+    }
+
+    /// Returns the quirks (built-in profile plus config overrides) for a player service.
+    pub fn quirks(&self, service: &str) -> crate::mpris::quirks::Quirks {
+        crate::mpris::quirks::resolve(service, self.player_override(service))
+    }
+
+    /// Returns the config-file overrides for the given player service, if any.
+    pub fn player_override(&self, service: &str) -> Option<&crate::config::PlayerOverride> {
+        self.inner.file.player_override(service)
+    }
 }
 
 /// Encapsulates the runtime state needed by the event loop.
@@ -106,6 +246,7 @@ impl LoopState {
 ///
 /// * `update_tx` - Channel for sending state updates to UI/consumers
 /// * `shutdown_rx` - Receives shutdown signal to terminate loop
+/// * `commands` - Requests from an interactive UI, if any
 /// * `config` - Application configuration including provider settings
 ///
 /// # Architecture
@@ -121,12 +262,28 @@ impl LoopState {
 pub async fn listen(
     update_tx: mpsc::Sender<Update>,
     shutdown_rx: mpsc::Receiver<()>,
+    commands: Option<mpsc::Receiver<UserCommand>>,
     config: crate::Config,
 ) {
-    let loop_config = LoopConfig::new(config);
+    let update_tx = match crate::hooks::Hooks::from_config(&config) {
+        Some(hooks) => hooks.tap(update_tx),
+        None => update_tx,
+    };
+    let (event_tx, event_rx) = mpsc::channel::<Event>(16);
+    if let Some(mut commands) = commands {
+        let event_tx = event_tx.clone();
+        tokio::spawn(async move {
+            while let Some(command) = commands.recv().await {
+                if event_tx.send(Event::User(command)).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    let loop_config = LoopConfig::new(config).with_events(event_tx.clone());
     let mut loop_state = LoopState::new();
     
-    let event_rx = initialize_loop(&mut loop_state, &update_tx, &loop_config).await;
+    initialize_loop(&mut loop_state, &update_tx, &loop_config, event_tx).await;
 
     run_event_loop(
         loop_state,
@@ -138,81 +295,104 @@ pub async fn listen(
     .await;
 }
 
+/// Fetches lyrics for the currently playing track once, without starting
+/// the event loop.
+///
+/// Runs the same player discovery and lookup pipeline as [`listen`].
+/// Returns `None` if no (non-blocked) player is active.
+pub async fn fetch_current(config: crate::Config) -> Option<StateBundle> {
+    let loop_config = LoopConfig::new(config);
+    let mut loop_state = LoopState::new();
+
+    let service = discover_active_player(&loop_config).await?;
+    initialize_with_player(&mut loop_state, &service, &loop_config, None).await;
+
+    Some(loop_state.state_bundle)
+}
+
 /// Initializes the event loop infrastructure.
 ///
 /// This function:
-/// 1. Creates the event channel
-/// 2. Discovers active player
-/// 3. Fetches initial metadata and lyrics (if player found)
-/// 4. Spawns MPRIS event watcher
-///
-/// # Returns
-///
-/// The receiver end of the event channel for the main loop to consume.
+/// 1. Discovers active player
+/// 2. Fetches initial metadata and lyrics (if player found), sending the
+///    track info before the lookup and the lyrics after it
+/// 3. Spawns MPRIS event watcher feeding `event_tx`
 async fn initialize_loop(
     loop_state: &mut LoopState,
     update_tx: &mpsc::Sender<Update>,
     config: &LoopConfig,
-) -> mpsc::Receiver<Event> {
+    event_tx: mpsc::Sender<Event>,
+) {
     tracing::debug!("Initializing event loop");
-    let (event_tx, event_rx) = mpsc::channel::<Event>(16);
     
     let active_service = discover_active_player(config).await;
     
     if let Some(service) = active_service {
         tracing::debug!(service = %service, "Active player found");
-        initialize_with_player(loop_state, &service, config).await;
+        initialize_with_player(loop_state, &service, config, Some(update_tx)).await;
     } else {
         tracing::debug!("No active player found");
         handle_no_player(loop_state, update_tx).await;
     }
     
+    if let Some(dir) = config.drop_dir() {
+        crate::watch::spawn_drop_folder_watcher(dir.to_path_buf(), event_tx.clone());
+    }
+
+    if let Some(interval) = config.poll_interval() {
+        spawn_poller(event_tx.clone(), interval, config);
+    }
+
     spawn_mpris_watcher(event_tx, config);
-    
-    event_rx
 }
 
 /// Initializes state with an active player.
 ///
-/// Fetches initial metadata and lyrics for the current track.
+/// Fetches initial metadata and lyrics for the current track. With
+/// `update_tx`, the track info is sent before the (possibly slow) lyrics
+/// lookup and the result after it.
 async fn initialize_with_player(
     loop_state: &mut LoopState,
     service: &str,
     config: &LoopConfig,
+    update_tx: Option<&mpsc::Sender<Update>>,
 ) {
     tracing::debug!(
         service = %service,
         providers = ?config.providers(),
         "Initializing with active player"
     );
+    loop_state.state_bundle.service = Some(service.to_string());
     let initial_metadata = fetch_initial_metadata(service, config).await;
+    if let Some(update_tx) = update_tx {
+        loop_state.state_bundle.player_state.update_from_metadata(&initial_metadata);
+        send_update(&mut loop_state.state_bundle, update_tx, true).await;
+    }
     initialize_lyrics_state(loop_state, &initial_metadata, service, config).await;
+    if let Some(update_tx) = update_tx {
+        send_update(&mut loop_state.state_bundle, update_tx, true).await;
+    }
 }
 
-/// Discovers the first active, non-blocked media player service.
+/// Discovers the first active, non-blocked media player service, or the
+/// `--player-service` one.
 ///
 /// # Returns
 ///
 /// - `Some(service)` if an active, non-blocked player is found
-/// - `None` if no players are available or all are blocked
+/// - `None` if no players are available, all are blocked, or the
+///   `--player-service` player isn't running
 ///
 /// # Error Handling
 ///
 /// D-Bus enumeration errors are logged and treated as no player.
-async fn discover_active_player(config: &LoopConfig) -> Option<String> {
-    match crate::mpris::get_active_player_names().await {
-        Ok(names) => {
-            tracing::debug!(available_players = ?names, "Discovered MPRIS players");
-            
-            let blocked_count = names.iter().filter(|s| crate::mpris::is_blocked(s, config.block_list())).count();
-            let active = names
-                .into_iter()
-                .find(|service| !crate::mpris::is_blocked(service, config.block_list()));
-            
+pub async fn discover_active_player(config: &LoopConfig) -> Option<String> {
+    match crate::mpris::select_player(config.player_service(), config.block_list()).await {
+        Ok(active) => {
             if let Some(ref service) = active {
                 tracing::debug!(selected_player = %service, "Selected active player");
-            } else if blocked_count > 0 {
-                tracing::debug!(blocked_count = blocked_count, "All discovered players are blocked");
+            } else {
+                tracing::debug!("No active, unblocked player");
             }
             
             active
@@ -227,6 +407,30 @@ async fn discover_active_player(config: &LoopConfig) -> Option<String> {
     }
 }
 
+/// Resolves the metadata a lookup would use and the providers it would ask,
+/// without fetching anything (used by `compare`).
+///
+/// `track` defaults to the active player's current track; rewrite rules
+/// are applied either way, `--musicbrainz` normalization (which may search
+/// MusicBrainz) is left to the caller. Returns `None` if no player is
+/// playing anything.
+pub async fn lookup_plan(config: crate::Config, track: Option<TrackMetadata>) -> Option<(TrackMetadata, Vec<String>)> {
+    let loop_config = LoopConfig::new(config);
+    let meta = match track {
+        Some(meta) => meta,
+        None => {
+            let service = discover_active_player(&loop_config).await?;
+            fetch_initial_metadata(&service, &loop_config).await
+        }
+    };
+    if meta.title.is_empty() {
+        return None;
+    }
+    let query = loop_config.rewrite_rules().apply(&meta);
+    let providers = loop_config.providers_for(&query).to_vec();
+    Some((query, providers))
+}
+
 /// Handles the case where no active player is found.
 ///
 /// Clears all state and notifies the UI to display an empty state.
@@ -236,7 +440,8 @@ async fn handle_no_player(
 ) {
     loop_state.state_bundle.clear_lyrics();
     loop_state.state_bundle.player_state = Default::default();
-    send_update(&loop_state.state_bundle, update_tx, true).await;
+    loop_state.state_bundle.service = None;
+    send_update(&mut loop_state.state_bundle, update_tx, true).await;
 }
 
 /// Fetches initial metadata for the discovered player service.
@@ -285,7 +490,7 @@ async fn initialize_lyrics_state(
     let _position = event::fetch_and_update_lyrics(
         metadata,
         &mut loop_state.state_bundle,
-        config.providers(),
+        config,
         Some(service),
     )
     .await;
@@ -321,6 +526,8 @@ fn spawn_mpris_watcher(
     let update_tx = event_tx.clone();
     let seek_tx = event_tx;
     let block_list = config.block_list().to_vec();
+    let player_service = config.player_service().map(str::to_string);
+    let file_config = config.inner.file.clone();
 
     tokio::spawn(async move {
         let handler_result = MprisEventHandler::with_closures(
@@ -335,6 +542,8 @@ fn spawn_mpris_watcher(
                 ));
             },
             block_list,
+            player_service,
+            file_config,
         )
         .await;
 
@@ -357,6 +566,64 @@ fn spawn_mpris_watcher(
     });
 }
 
+/// Spawns a background task that polls the active player (`--poll-interval`).
+///
+/// This is a fallback for players whose D-Bus signals are missing or broken
+/// (some Electron apps). Each poll is fed through the regular pipeline as a
+/// synthesized `PlayerUpdate`, so track changes, pauses and seeks are handled
+/// exactly like signalled ones. Runs alongside the signal watcher.
+fn spawn_poller(
+    event_tx: mpsc::Sender<Event>,
+    interval: std::time::Duration,
+    config: &LoopConfig,
+) {
+    tracing::debug!(interval_ms = interval.as_millis() as u64, "Spawning player poller");
+    let block_list = config.block_list().to_vec();
+    let player_service = config.player_service().map(str::to_string);
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut had_player = false;
+
+        while !event_tx.is_closed() {
+            ticker.tick().await;
+
+            let service = crate::mpris::select_player(player_service.as_deref(), &block_list)
+                .await
+                .ok()
+                .flatten();
+
+            let Some(service) = service else {
+                // Report the player going away once, not on every tick
+                if had_player {
+                    had_player = false;
+                    let _ = event_tx.try_send(Event::Mpris(MprisEvent::PlayerUpdate(
+                        TrackMetadata::default(),
+                        0.0,
+                        String::new(),
+                    )));
+                }
+                continue;
+            };
+
+            let meta = match crate::mpris::metadata::get_metadata(&service).await {
+                Ok(meta) => meta,
+                Err(e) => {
+                    tracing::debug!(service = %service, error = %e, "Poll: failed to read metadata");
+                    continue;
+                }
+            };
+            let Ok(position) = crate::mpris::playback::get_position(&service).await else {
+                continue;
+            };
+
+            had_player = true;
+            let _ = event_tx.try_send(Event::Mpris(MprisEvent::PlayerUpdate(meta, position, service)));
+        }
+    });
+}
+
 /// Main event processing loop.
 ///
 /// This is the core loop that processes events until shutdown.
@@ -364,6 +631,9 @@ fn spawn_mpris_watcher(
 /// # Event Sources
 ///
 /// - MPRIS events (from background watcher task)
+/// - Line boundaries while playing (line advance and occasional position re-read, see [`event::handle_line_boundary`])
+/// - The end of a burst of seeks (see [`event::flush_seeks`])
+/// - A keepalive every `--heartbeat` interval, re-sending the current state
 /// - Shutdown signal (for graceful termination)
 ///
 /// # Termination
@@ -377,7 +647,17 @@ async fn run_event_loop(
     mut shutdown_rx: mpsc::Receiver<()>,
     config: LoopConfig,
 ) {
+    let mut dump_signal = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::user_defined1())
+        .map_err(|e| tracing::warn!(error = %e, "Failed to install SIGUSR1 handler, state dumps disabled"))
+        .ok();
+    let mut keepalive = config
+        .heartbeat()
+        .map(|period| tokio::time::interval_at(tokio::time::Instant::now() + period, period));
+
     loop {
+        let boundary = event::next_line_boundary(&loop_state.state_bundle);
+        let seek_flush = loop_state.state_bundle.seeks.flush_in();
+
         tokio::select! {
             // Shutdown signal received - clean up and terminate
             _ = shutdown_rx.recv() => {
@@ -389,10 +669,67 @@ async fn run_event_loop(
             event = event_rx.recv() => {
                 handle_event(event, &mut loop_state, &update_tx, &config).await;
             }
+
+            // Next lyric line is due - confirm it against the real position
+            _ = async {
+                match boundary {
+                    Some(delay) => tokio::time::sleep(delay).await,
+                    None => futures_util::future::pending::<()>().await,
+                }
+            } => {
+                event::handle_line_boundary(&mut loop_state.state_bundle, &update_tx, &config).await;
+            }
+
+            // A burst of seeks settled - render its final position
+            _ = async {
+                match seek_flush {
+                    Some(delay) => tokio::time::sleep(delay).await,
+                    None => futures_util::future::pending::<()>().await,
+                }
+            } => {
+                event::flush_seeks(&mut loop_state.state_bundle, &update_tx).await;
+            }
+
+            // Proof of life for the pipe UI's `--heartbeat`
+            _ = async {
+                match keepalive.as_mut() {
+                    Some(interval) => interval.tick().await,
+                    None => futures_util::future::pending().await,
+                }
+            } => {
+                event::send_update(&mut loop_state.state_bundle, &update_tx, true).await;
+            }
+
+            // `kill -USR1` - dump the live state for bug reports
+            _ = async {
+                match dump_signal.as_mut() {
+                    Some(signal) => signal.recv().await,
+                    None => futures_util::future::pending().await,
+                }
+            } => {
+                dump_state(&loop_state.state_bundle);
+            }
         }
     }
 }
 
+/// Writes the live state as JSON to `$XDG_RUNTIME_DIR/lyricsmpris-state-<pid>.json`
+/// (the temp dir without a runtime dir), for diagnosing frozen lyrics.
+fn dump_state(state: &StateBundle) {
+    let mut dump = state.debug_dump();
+    dump["next_line_boundary_in"] = event::next_line_boundary(state).map(|d| d.as_secs_f64()).into();
+
+    let dir = std::env::var_os("XDG_RUNTIME_DIR").map_or_else(std::env::temp_dir, std::path::PathBuf::from);
+    let path = dir.join(format!("lyricsmpris-state-{}.json", std::process::id()));
+    let result = serde_json::to_string_pretty(&dump)
+        .map_err(std::io::Error::other)
+        .and_then(|json| std::fs::write(&path, json));
+    match result {
+        Ok(()) => tracing::info!(path = %path.display(), "Wrote state dump"),
+        Err(e) => tracing::warn!(path = %path.display(), error = %e, "Failed to write state dump"),
+    }
+}
+
 /// Processes a shutdown event and cleans up state.
 ///
 /// Sends a final update to observers before terminating.
@@ -406,7 +743,7 @@ async fn handle_shutdown(
         Event::Shutdown,
         &mut loop_state.state_bundle,
         update_tx,
-        config.providers(),
+        config,
     )
     .await;
 }
@@ -431,7 +768,7 @@ async fn handle_event(
         event,
         &mut loop_state.state_bundle,
         update_tx,
-        config.providers(),
+        config,
     )
     .await;
 }
\ No newline at end of file
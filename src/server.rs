@@ -0,0 +1,171 @@
+//! LRCLIB-compatible HTTP server for sharing the local cache.
+//!
+//! `lyricsmpris --database PATH serve --listen 0.0.0.0:8910` exposes the
+//! SQLite cache over `GET /api/get`, accepting the same query parameters as
+//! lrclib.net (`artist_name`, `track_name`, `album_name`, `duration`) and
+//! answering with the same JSON shape. Other machines can then use it by
+//! pointing `LRCLIB_URL` at this server.
+//!
+//! The server is deliberately minimal: HTTP/1.1, GET only, one request per
+//! connection.
+
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Maximum accepted request head size.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Time allowed for a client to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Serves the cache until the process is terminated.
+pub async fn serve(listen: &str, tolerance: f64) -> std::io::Result<()> {
+    let listener = TcpListener::bind(listen).await?;
+    tracing::info!(address = %listener.local_addr()?, "Serving lyrics cache");
+    eprintln!("Serving lyrics cache on http://{}/api/get", listener.local_addr()?);
+
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to accept connection");
+                continue;
+            }
+        };
+
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, tolerance).await {
+                tracing::debug!(peer = %peer, error = %e, "Connection error");
+            }
+        });
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, tolerance: f64) -> std::io::Result<()> {
+    let head = match tokio::time::timeout(READ_TIMEOUT, read_request_head(&mut stream)).await {
+        Ok(head) => head?,
+        Err(_) => return Ok(()),
+    };
+
+    let (status, body) = match parse_request_line(&head) {
+        Some(("GET", target)) => route(target, tolerance).await,
+        Some(_) => (405, error_body(405, "MethodNotAllowed", "Only GET is supported")),
+        None => (400, error_body(400, "BadRequest", "Malformed request")),
+    };
+
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Error",
+    };
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Reads until the end of the request head (`\r\n\r\n`).
+async fn read_request_head(stream: &mut TcpStream) -> std::io::Result<String> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") && buf.len() < MAX_REQUEST_BYTES {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+/// Splits `GET /path?query HTTP/1.1` into method and target.
+fn parse_request_line(head: &str) -> Option<(&str, &str)> {
+    let mut parts = head.lines().next()?.split_whitespace();
+    let method = parts.next()?;
+    let target = parts.next()?;
+    parts.next()?.starts_with("HTTP/").then_some((method, target))
+}
+
+async fn route(target: &str, tolerance: f64) -> (u16, serde_json::Value) {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    if path != "/api/get" {
+        return (404, error_body(404, "NotFound", "Unknown endpoint"));
+    }
+
+    let params = parse_query(query);
+    let param = |key: &str| params.get(key).map(String::as_str).unwrap_or("");
+    let (artist, title, album) = (param("artist_name"), param("track_name"), param("album_name"));
+    if artist.is_empty() || title.is_empty() {
+        return (400, error_body(400, "BadRequest", "artist_name and track_name are required"));
+    }
+    let duration = params.get("duration").and_then(|d| d.parse::<f64>().ok());
+
+    match crate::lyrics::database::fetch_from_database(artist, title, album, duration, tolerance).await {
+        Some(Ok(cached)) if !cached.lines.is_empty() => {
+            let lines = cached.lines;
+            tracing::debug!(artist = %artist, title = %title, "Served cached lyrics");
+            (
+                200,
+                json!({
+                    "id": 0,
+                    "trackName": title,
+                    "artistName": artist,
+                    "albumName": album,
+                    "duration": duration,
+                    "instrumental": false,
+                    "plainLyrics": crate::lyrics::export::to_plain(&lines),
+                    "syncedLyrics": crate::lyrics::export::to_lrc(&lines),
+                }),
+            )
+        }
+        _ => (404, error_body(404, "TrackNotFound", "Failed to find specified track")),
+    }
+}
+
+/// LRCLIB-style error body.
+fn error_body(code: u16, name: &str, message: &str) -> serde_json::Value {
+    json!({ "code": code, "name": name, "message": message })
+}
+
+/// Decodes an `application/x-www-form-urlencoded` query string.
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter_map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let decode = |s: &str| urlencoding::decode(&s.replace('+', " ")).ok().map(|v| v.into_owned());
+            Some((decode(key)?, decode(value)?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request() {
+        let head = "GET /api/get?artist_name=Arctic+Monkeys&track_name=Do%20I%20Wanna%20Know%3F HTTP/1.1\r\nHost: x\r\n\r\n";
+        let (method, target) = parse_request_line(head).unwrap();
+        assert_eq!(method, "GET");
+
+        let params = parse_query(target.split_once('?').unwrap().1);
+        assert_eq!(params["artist_name"], "Arctic Monkeys");
+        assert_eq!(params["track_name"], "Do I Wanna Know?");
+        assert!(parse_request_line("garbage").is_none());
+    }
+}
@@ -22,6 +22,25 @@ use crate::timer::{sanitize_position, PlaybackTimer};
 use std::cmp::Ordering;
 use std::sync::Arc;
 
+/// Minimum backwards movement (in seconds) treated as a rewind rather than
+/// reporting jitter.
+pub const BACKWARD_JUMP_THRESHOLD: f64 = 1.0;
+
+/// Reported positions within this distance (in seconds) of the estimate are
+/// treated as jitter and smoothed rather than applied directly.
+const POSITION_JITTER_TOLERANCE: f64 = 0.35;
+
+/// Fraction of a jitter-sized correction applied per position report.
+const POSITION_SMOOTHING: f64 = 0.5;
+
+/// Longest offset (in seconds) a gapless or crossfaded track can already
+/// have played by the time its metadata is reported.
+const CROSSFADE_WINDOW: f64 = 12.0;
+
+/// Positions within this distance (in seconds) of where the previous track
+/// would be are treated as stale reports still counting that track.
+const STALE_POSITION_TOLERANCE: f64 = 1.0;
+
 // ============================================================================
 // Provider Enumeration
 // ============================================================================
@@ -32,17 +51,107 @@ use std::sync::Arc;
 /// - [`Provider::LRCLIB`]: LRCLIB database (returns LRC timestamp format)
 /// - [`Provider::MusixmatchRichsync`]: Word-level synchronized lyrics (JSON)
 /// - [`Provider::MusixmatchSubtitles`]: Line-level synchronized lyrics (JSON)
+/// - [`Provider::AmllTtml`]: AMLL TTML database (word-level synchronized XML)
+/// - [`Provider::NeteaseYrc`]: NetEase YRC karaoke lyrics (word-level synchronized text)
+/// - [`Provider::LocalFile`]: `Artist - Title.lrc` from a local lyrics directory
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[non_exhaustive]
+#[allow(clippy::upper_case_acronyms)]
 pub enum Provider {
     /// LRCLIB provider - returns LRC format: `[MM:SS.CC]lyrics`
     LRCLIB,
+    /// LRCLIB unsynced lyrics, timed evenly over the track (`--teleprompter`)
+    LrclibPlain,
     /// Musixmatch provider - richsync format with word-level timing (JSON)
     MusixmatchRichsync,
     /// Musixmatch provider - subtitle format with line-level timing (JSON)
     MusixmatchSubtitles,
+    /// AMLL TTML database - TTML with word-level timing (XML)
+    AmllTtml,
+    /// NetEase YRC format with word-level timing (imported or cached)
+    NeteaseYrc,
+    /// Local `.lrc` file (`~/.lyrics`, `$XDG_DATA_HOME/lyrics`)
+    LocalFile,
+    /// User script (`--providers exec:PATH`) printing LRC
+    Exec,
 }
 
+impl Provider {
+    /// Returns `true` if this provider's lyrics carry per-word timings.
+    pub fn is_word_synced(self) -> bool {
+        matches!(self, Self::MusixmatchRichsync | Self::AmllTtml | Self::NeteaseYrc)
+    }
+
+    /// Name of the `--providers` entry that returns these lyrics (`exec:`
+    /// standing for every script), or `None` for local files and formats
+    /// that are only imported.
+    pub fn source(self) -> Option<&'static str> {
+        match self {
+            Self::LRCLIB | Self::LrclibPlain => Some("lrclib"),
+            Self::MusixmatchRichsync | Self::MusixmatchSubtitles => Some("musixmatch"),
+            Self::AmllTtml => Some("amll"),
+            Self::Exec => Some(crate::lyrics::providers::exec::EXEC_PREFIX),
+            Self::NeteaseYrc | Self::LocalFile => None,
+        }
+    }
+
+    /// Stable identifier used in machine-readable output.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::LRCLIB => "lrclib",
+            Self::LrclibPlain => "lrclib-plain",
+            Self::MusixmatchRichsync => "musixmatch-richsync",
+            Self::MusixmatchSubtitles => "musixmatch-subtitles",
+            Self::AmllTtml => "amll-ttml",
+            Self::NeteaseYrc => "netease-yrc",
+            Self::LocalFile => "local-file",
+            Self::Exec => "exec",
+        }
+    }
+}
+
+
+/// Kind of a failed lyrics lookup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Network,
+    /// The provider reported an error
+    Api,
+    /// The provider's response couldn't be read
+    Parse,
+}
+
+impl ErrorKind {
+    /// Stable name for machine-readable output (`--dump`, `--output state-jsonl`).
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Network => "network",
+            Self::Api => "api",
+            Self::Parse => "parse",
+        }
+    }
+}
+
+/// A failed lyrics lookup, kept untranslated; the TUI localizes it with
+/// [`crate::i18n::lyrics_error`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LookupError {
+    pub kind: ErrorKind,
+    /// Detail from the network stack or the provider
+    pub detail: String,
+}
+
+impl From<&crate::lyrics::LyricsError> for LookupError {
+    fn from(err: &crate::lyrics::LyricsError) -> Self {
+        use crate::lyrics::LyricsError;
+        let (kind, detail) = match err {
+            LyricsError::Network(e) => (ErrorKind::Network, e.to_string()),
+            LyricsError::Api(detail) => (ErrorKind::Api, detail.clone()),
+            LyricsError::Serde(e) => (ErrorKind::Parse, e.to_string()),
+        };
+        Self { kind, detail }
+    }
+}
 
 // ============================================================================
 // Update Snapshot
@@ -66,8 +175,9 @@ pub enum Provider {
 /// - `position`: Current playback position in seconds
 /// - `playing`: Playback state (true = playing, false = paused)
 /// - `version`: Monotonic counter for change detection
-/// - `err`: Error message from the most recent operation
+/// - `err`: Error from the most recent operation
 /// - `provider`: Source of the current lyrics
+/// - `delta`: What changed since the previous update
 #[derive(Debug, Clone, PartialEq)]
 pub struct Update {
     /// Lyrics lines (shared via Arc for efficient cloning)
@@ -85,8 +195,8 @@ pub struct Update {
     /// Monotonically increasing version counter for change detection
     pub version: u64,
     
-    /// Error message from the most recent operation (if any)
-    pub err: Option<String>,
+    /// Error from the most recent operation (if any)
+    pub err: Option<LookupError>,
     
     /// Current track artist
     pub artist: String,
@@ -99,6 +209,66 @@ pub struct Update {
     
     /// Provider that supplied the current lyrics
     pub provider: Option<Provider>,
+
+    /// Estimated confidence that the lyrics match the track, `None` without
+    /// lyrics (see [`crate::lyrics::confidence`])
+    pub confidence: Option<f64>,
+
+    /// Changes since the previous update sent by the same event loop
+    pub delta: UpdateDelta,
+}
+
+/// What changed between two consecutive updates.
+///
+/// Sent along with every [`Update`], so consumers can react to the change
+/// (print a line, rebuild their layout) without diffing whole snapshots.
+/// The first update of a session has every flag set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UpdateDelta {
+    /// Different artist, title or album
+    pub track_changed: bool,
+    /// New lyrics (also set on a track change)
+    pub lyrics_replaced: bool,
+    /// Another line became active
+    pub index_changed: bool,
+    /// The reported position moved
+    pub position_changed: bool,
+    /// Playback paused or resumed
+    pub playing_changed: bool,
+    /// The error message appeared, changed or cleared
+    pub err_changed: bool,
+}
+
+impl UpdateDelta {
+    /// Changes from `last` (the previously sent update, if any) to `upd`.
+    #[must_use]
+    pub fn between(last: Option<&Update>, upd: &Update) -> Self {
+        let Some(last) = last else {
+            return Self {
+                track_changed: true,
+                lyrics_replaced: true,
+                index_changed: true,
+                position_changed: true,
+                playing_changed: true,
+                err_changed: true,
+            };
+        };
+        let track_changed = (&last.artist, &last.title, &last.album) != (&upd.artist, &upd.title, &upd.album);
+        Self {
+            track_changed,
+            lyrics_replaced: track_changed || !Arc::ptr_eq(&last.lines, &upd.lines),
+            index_changed: last.index != upd.index,
+            position_changed: last.position != upd.position,
+            playing_changed: last.playing != upd.playing,
+            err_changed: last.err != upd.err,
+        }
+    }
+
+    /// Whether nothing changed at all.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 impl Default for Update {
@@ -114,6 +284,8 @@ impl Default for Update {
             title: String::new(),
             album: String::new(),
             provider: None,
+            confidence: None,
+            delta: UpdateDelta::default(),
         }
     }
 }
@@ -159,10 +331,14 @@ pub struct PlayerState {
     pub position: f64,
     
     /// Error from the most recent operation (if any)
-    pub err: Option<String>,
+    pub err: Option<LookupError>,
     
     /// Track length in seconds (if known)
     pub length: Option<f64>,
+
+    /// Radio stream: the player's position counts from when the stream was
+    /// opened, so it is estimated from the last stream-title change instead
+    pub stream: bool,
     
     /// Internal timer for position estimation during playback
     timer: PlaybackTimer,
@@ -178,6 +354,7 @@ impl Default for PlayerState {
             position: 0.0,
             err: None,
             length: None,
+            stream: false,
             timer: PlaybackTimer::default(),
         }
     }
@@ -199,6 +376,7 @@ impl PlayerState {
         self.artist.clone_from(&meta.artist);
         self.album.clone_from(&meta.album);
         self.length = meta.length;
+        self.stream = meta.is_stream;
         self.timer.reset(0.0);
         self.position = 0.0;
         self.err = None;
@@ -221,6 +399,7 @@ impl PlayerState {
         self.artist.clone_from(&meta.artist);
         self.album.clone_from(&meta.album);
         self.length = meta.length;
+        self.stream = meta.is_stream;
         self.err = None;
     }
 
@@ -287,11 +466,28 @@ impl PlayerState {
             || self.album != meta.album
     }
 
+    /// Returns `true` if `position` lies clearly behind the estimated
+    /// position (rewind, A-B repeat), by more than [`BACKWARD_JUMP_THRESHOLD`].
+    #[must_use]
+    pub fn is_backward_jump(&self, position: f64) -> bool {
+        position < self.estimate_position() - BACKWARD_JUMP_THRESHOLD
+    }
+
     /// Sets a new anchor position without changing playback state.
     ///
     /// The position is automatically sanitized (no NaN, no negatives).
+    /// While playing, reports within [`POSITION_JITTER_TOLERANCE`] of the
+    /// estimate only move the anchor part of the way, so players whose
+    /// reported position oscillates don't flicker the line at boundaries.
+    /// Larger differences (seeks) are applied directly.
     pub fn set_position(&mut self, position: f64) {
-        let pos = sanitize_position(position);
+        let mut pos = sanitize_position(position);
+        if self.playing {
+            let estimated = self.estimate_position();
+            if (pos - estimated).abs() < POSITION_JITTER_TOLERANCE {
+                pos = estimated + (pos - estimated) * POSITION_SMOOTHING;
+            }
+        }
         self.timer.set_position(pos);
         self.position = pos;
     }
@@ -317,6 +513,97 @@ impl PlayerState {
     }
 }
 
+/// Where the previous track was when a new one was reported.
+///
+/// Gapless and crossfading players emit the new `Metadata` before
+/// `Position` resets, so for a moment they keep reporting the previous
+/// track's progress. This tells such stale reports apart from the genuine
+/// nonzero offset a crossfaded track starts at.
+#[derive(Debug, Clone, Copy)]
+pub struct TrackTransition {
+    previous_position: f64,
+    playing: bool,
+    at: std::time::Instant,
+}
+
+impl TrackTransition {
+    /// Records a track change away from `previous`.
+    #[must_use]
+    pub fn new(previous: &PlayerState) -> Self {
+        Self {
+            previous_position: previous.estimate_position(),
+            playing: previous.playing,
+            at: std::time::Instant::now(),
+        }
+    }
+
+    /// Whether the change happened recently enough for stale reports to arrive.
+    #[must_use]
+    pub fn is_recent(&self) -> bool {
+        self.at.elapsed().as_secs_f64() < CROSSFADE_WINDOW
+    }
+
+    /// Returns `true` if `position` continues the previous track's progress.
+    #[must_use]
+    pub fn is_stale(&self, position: f64) -> bool {
+        let elapsed = if self.playing { self.at.elapsed().as_secs_f64() } else { 0.0 };
+        // A track left right after it started can't be told from the new one
+        self.previous_position > STALE_POSITION_TOLERANCE
+            && (position - (self.previous_position + elapsed)).abs() < STALE_POSITION_TOLERANCE
+    }
+
+    /// Start position for the new track given the position reported with
+    /// its metadata: kept if it is a plausible crossfade offset, otherwise 0.
+    #[must_use]
+    pub fn start_offset(&self, reported: f64, length: Option<f64>) -> f64 {
+        let plausible = reported.is_finite()
+            && reported > 0.0
+            && reported <= CROSSFADE_WINDOW
+            && length.is_none_or(|len| reported < len)
+            && !self.is_stale(reported);
+        if plausible { reported } else { 0.0 }
+    }
+}
+
+/// Seeks closer together than this are one scrub of the seek bar.
+const SEEK_COALESCE_WINDOW: std::time::Duration = std::time::Duration::from_millis(150);
+
+/// Tracks bursts of `Seeked` events while the user drags a seek bar.
+///
+/// The first seek of a burst is rendered right away; later ones only move
+/// the position, and the final one is rendered once the burst settles.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SeekBurst {
+    last: Option<std::time::Instant>,
+    /// A seek was applied but not rendered yet
+    pending: bool,
+}
+
+impl SeekBurst {
+    /// Records a seek; returns `true` if it should be rendered now.
+    pub fn record(&mut self) -> bool {
+        let now = std::time::Instant::now();
+        let scrubbing = self.last.is_some_and(|last| now - last < SEEK_COALESCE_WINDOW);
+        self.last = Some(now);
+        self.pending = scrubbing;
+        !scrubbing
+    }
+
+    /// Time until the burst settles and its final position is due.
+    #[must_use]
+    pub fn flush_in(&self) -> Option<std::time::Duration> {
+        if !self.pending {
+            return None;
+        }
+        Some(SEEK_COALESCE_WINDOW.saturating_sub(self.last?.elapsed()))
+    }
+
+    /// Returns whether a seek is waiting to be rendered, clearing it.
+    pub fn take_pending(&mut self) -> bool {
+        std::mem::take(&mut self.pending)
+    }
+}
+
 // ============================================================================
 // Lyric State
 // ============================================================================
@@ -507,6 +794,31 @@ pub struct StateBundle {
     
     /// Timestamp when lyrics were last loaded (for filtering stale Seeked events)
     pub lyrics_loaded_at: Option<std::time::Instant>,
+
+    /// Most recent track change (for filtering positions of the previous track)
+    pub transition: Option<TrackTransition>,
+
+    /// MPRIS service of the active player (for position re-reads at line boundaries)
+    pub service: Option<String>,
+
+    /// Seeks coalesced while scrubbing
+    pub seeks: SeekBurst,
+
+    /// Version and playing state of the last update sent to the UI
+    /// (`(version << 1) | playing`), to skip redundant updates
+    pub last_sent: Option<u64>,
+
+    /// Last update sent to the UI, that the next one's [`UpdateDelta`] is relative to
+    pub last_sent_update: Option<Update>,
+
+    /// Confidence in the current lyrics, recomputed when they change
+    pub confidence: Option<f64>,
+
+    /// When the position was last re-read at a line boundary
+    pub boundary_synced_at: Option<std::time::Instant>,
+
+    /// Providers rejected and pinned with the wrong-match and pin keys
+    pub choices: crate::lyrics::database::SessionChoices,
 }
 
 impl Default for StateBundle {
@@ -525,6 +837,14 @@ impl StateBundle {
             version: 0,
             provider: None,
             lyrics_loaded_at: None,
+            transition: None,
+            service: None,
+            seeks: SeekBurst::default(),
+            last_sent: None,
+            last_sent_update: None,
+            confidence: None,
+            boundary_synced_at: None,
+            choices: Default::default(),
         }
     }
 
@@ -540,6 +860,7 @@ impl StateBundle {
     pub fn clear_lyrics(&mut self) {
         self.lyric_state.update_lines(Vec::new());
         self.provider = None;
+        self.confidence = None;
         self.lyrics_loaded_at = None;
         self.increment_version();
     }
@@ -567,13 +888,13 @@ impl StateBundle {
     ///
     /// * `lines` - New lyrics lines (will be sanitized and sorted)
     /// * `meta` - Track metadata
-    /// * `err` - Optional error message
+    /// * `err` - Optional lookup error
     /// * `provider` - Source of the lyrics
     pub fn update_lyrics(
         &mut self,
         lines: Vec<LyricLine>,
         meta: &TrackMetadata,
-        err: Option<String>,
+        err: Option<LookupError>,
         provider: Option<Provider>,
     ) {
         let has_lyrics = !lines.is_empty();
@@ -581,6 +902,7 @@ impl StateBundle {
         self.player_state.update_metadata_only(meta);
         self.player_state.err = err;
         self.provider = provider;
+        self.set_provider_duration(None);
         
         // Record when lyrics were loaded for filtering stale Seeked events
         if has_lyrics {
@@ -590,6 +912,39 @@ impl StateBundle {
         self.increment_version();
     }
 
+    /// Scales the time left until each upcoming line by `1 / factor`, so the
+    /// lyrics scroll `factor` times faster from the current position on
+    /// (teleprompter mode). Lines already passed keep their times.
+    pub fn retime_lyrics(&mut self, factor: f64) {
+        let position = self.player_state.estimate_position();
+        let lines = self
+            .lyric_state
+            .lines
+            .iter()
+            .cloned()
+            .map(|mut line| {
+                if line.time > position {
+                    line.time = position + (line.time - position) / factor;
+                }
+                line
+            })
+            .collect();
+        self.lyric_state.update_lines(lines);
+        self.lyric_state.index = self.lyric_state.get_index(position);
+        self.increment_version();
+    }
+
+    /// Recomputes the confidence in the current lyrics, given the track
+    /// length the provider matched (if it reported one).
+    pub fn set_provider_duration(&mut self, provider_duration: Option<f64>) {
+        let evidence = crate::lyrics::confidence::Evidence {
+            provider: self.provider,
+            track_length: self.player_state.length,
+            provider_duration,
+        };
+        self.confidence = crate::lyrics::confidence::estimate(&self.lyric_state.lines, &evidence);
+    }
+
     /// Updates the active lyric line index based on playback position.
     ///
     /// Increments version and returns `true` if the index changed.
@@ -632,7 +987,7 @@ impl StateBundle {
             self.player_state.position
         };
         
-        Update {
+        let mut update = Update {
             lines: Arc::clone(&self.lyric_state.lines),
             index: self.lyric_state.index,
             position,
@@ -643,7 +998,11 @@ impl StateBundle {
             title: self.player_state.title.clone(),
             album: self.player_state.album.clone(),
             provider: self.provider,
-        }
+            confidence: self.confidence,
+            delta: UpdateDelta::default(),
+        };
+        update.delta = UpdateDelta::between(self.last_sent_update.as_ref(), &update);
+        update
     }
 
     /// Returns `true` if lyrics are currently loaded.
@@ -651,6 +1010,48 @@ impl StateBundle {
     pub fn has_lyrics(&self) -> bool {
         !self.lyric_state.is_empty()
     }
+
+    /// Snapshot of the internals for debug dumps (`kill -USR1`).
+    ///
+    /// Times are in seconds; instants are given as their age.
+    #[must_use]
+    pub fn debug_dump(&self) -> serde_json::Value {
+        let player = &self.player_state;
+        let lyrics = &self.lyric_state;
+        let age = |instant: std::time::Instant| instant.elapsed().as_secs_f64();
+        serde_json::json!({
+            "version": self.version,
+            "service": self.service,
+            "track": {
+                "title": player.title,
+                "artist": player.artist,
+                "album": player.album,
+                "length": player.length,
+                "stream": player.stream,
+            },
+            "playing": player.playing,
+            "err": player.err.as_ref().map(|e| e.kind.code()),
+            "err_detail": player.err.as_ref().map(|e| &e.detail),
+            "position": {
+                "anchor": player.position,
+                "timer_anchor": player.timer.anchor_position(),
+                "timer_running_for": player.timer.anchor_age().map(|d| d.as_secs_f64()),
+                "estimate": player.estimate_position(),
+            },
+            "lyrics": {
+                "provider": self.provider.map(Provider::name),
+                "lines": lyrics.lines.len(),
+                "index": lyrics.index,
+                "current_line": lyrics.index.and_then(|i| lyrics.lines.get(i)).map(|l| (l.time, &l.text)),
+                "loaded_ago": self.lyrics_loaded_at.map(age),
+            },
+            "transition": self.transition.as_ref().map(|t| serde_json::json!({
+                "previous_position": t.previous_position,
+                "playing": t.playing,
+                "ago": age(t.at),
+            })),
+        })
+    }
 }
 
 
@@ -662,6 +1063,78 @@ impl StateBundle {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_seek_burst_coalescing() {
+        let mut seeks = SeekBurst::default();
+        assert!(seeks.record());
+        assert_eq!(seeks.flush_in(), None);
+
+        // Scrubbing: rendered only once the burst settles
+        assert!(!seeks.record());
+        assert!(!seeks.record());
+        assert!(seeks.flush_in().is_some_and(|d| d <= SEEK_COALESCE_WINDOW));
+        assert!(seeks.take_pending());
+        assert!(!seeks.take_pending());
+        assert_eq!(seeks.flush_in(), None);
+    }
+
+    #[test]
+    fn test_debug_dump() {
+        let mut state = StateBundle::new();
+        let meta = TrackMetadata { title: "Song".into(), artist: "Band".into(), ..Default::default() };
+        let lines = vec![
+            LyricLine { time: 0.0, text: "One".into(), ..Default::default() },
+            LyricLine { time: 5.0, text: "Two".into(), ..Default::default() },
+        ];
+        state.update_lyrics(lines, &meta, None, Some(Provider::LRCLIB));
+        state.player_state.set_position(6.0);
+        state.update_index(6.0);
+
+        let dump = state.debug_dump();
+        assert_eq!(dump["track"]["title"], "Song");
+        assert_eq!(dump["lyrics"]["provider"], "lrclib");
+        assert_eq!(dump["lyrics"]["index"], 1);
+        assert_eq!(dump["lyrics"]["current_line"][1], "Two");
+        assert_eq!(dump["position"]["anchor"], 6.0);
+        assert!(dump["transition"].is_null());
+    }
+
+    #[test]
+    fn test_retime_lyrics() {
+        let mut state = StateBundle::new();
+        let meta = TrackMetadata { title: "Song".into(), ..Default::default() };
+        let lines = crate::lyrics::parse::time_plain_lyrics("One\nTwo\nThree\nFour", Some(40.0));
+        state.update_lyrics(lines, &meta, None, Some(Provider::LrclibPlain));
+        state.player_state.set_position(12.0);
+        state.update_index(12.0);
+
+        // Twice as fast: the gaps ahead halve, the current line stays
+        state.retime_lyrics(2.0);
+        let times: Vec<f64> = state.lyric_state.lines.iter().map(|l| l.time).collect();
+        assert_eq!(times, vec![0.0, 10.0, 16.0, 21.0]);
+        assert_eq!(state.lyric_state.index, Some(1));
+    }
+
+    #[test]
+    fn test_track_transition_start_offset() {
+        let mut previous = PlayerState::default();
+        previous.set_position(200.0);
+        let transition = TrackTransition::new(&previous);
+
+        // Crossfade: the new track is already a few seconds in
+        assert_eq!(transition.start_offset(3.5, Some(180.0)), 3.5);
+        // Still the previous track's position, or implausibly far in
+        assert_eq!(transition.start_offset(200.2, Some(240.0)), 0.0);
+        assert_eq!(transition.start_offset(40.0, None), 0.0);
+        assert!(transition.is_stale(199.5));
+        assert!(!transition.is_stale(3.5));
+
+        // Nothing was playing before: no position counts as stale
+        let fresh = TrackTransition::new(&PlayerState::default());
+        assert!(!fresh.is_stale(0.3));
+        assert_eq!(fresh.start_offset(0.3, None), 0.3);
+    }
+
     #[test]
     fn test_lyric_index_empty() {
         let state = LyricState::default();
@@ -672,7 +1145,7 @@ mod tests {
     fn test_lyric_index_before_first() {
         let mut state = LyricState::default();
         state.update_lines(vec![
-            LyricLine { time: 10.0, text: "First".into(), words: None },
+            LyricLine { time: 10.0, text: "First".into(), words: None, translation: None, agent: None, cue: false },
         ]);
         assert_eq!(state.get_index(5.0), None);
     }
@@ -681,11 +1154,37 @@ mod tests {
     fn test_lyric_index_basic() {
         let mut state = LyricState::default();
         state.update_lines(vec![
-            LyricLine { time: 10.0, text: "First".into(), words: None },
-            LyricLine { time: 20.0, text: "Second".into(), words: None },
+            LyricLine { time: 10.0, text: "First".into(), words: None, translation: None, agent: None, cue: false },
+            LyricLine { time: 20.0, text: "Second".into(), words: None, translation: None, agent: None, cue: false },
         ]);
         
         assert_eq!(state.get_index(15.0), Some(0));
         assert_eq!(state.get_index(25.0), Some(1));
     }
+
+    #[test]
+    fn test_position_jitter_smoothing() {
+        let mut player = PlayerState::default();
+        player.set_position(10.0);
+        player.start_playing();
+
+        player.set_position(10.2);
+        assert!((player.position - 10.1).abs() < 0.01);
+
+        player.set_position(20.0);
+        assert_eq!(player.position, 20.0);
+
+        player.pause();
+        player.set_position(20.2);
+        assert_eq!(player.position, 20.2);
+    }
+
+    #[test]
+    fn test_backward_jump() {
+        let mut player = PlayerState::default();
+        player.set_position(30.0);
+        assert!(player.is_backward_jump(10.0));
+        assert!(!player.is_backward_jump(29.5));
+        assert!(!player.is_backward_jump(40.0));
+    }
 }
\ No newline at end of file
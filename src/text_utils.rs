@@ -1,18 +1,135 @@
 // src/text_utils.rs
 // Utility functions for text formatting
 
-/// Wrap text to a given width, preserving empty lines and not splitting words
+use textwrap::WordSeparator;
+
+/// Wrap text to a given width, preserving empty lines.
+///
+/// Lines break at Unicode line-break opportunities (UAX #14), so text
+/// without spaces (Chinese, Japanese) wraps between characters instead of
+/// overflowing the terminal.
 pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
     let mut result = Vec::new();
+    let options = textwrap::Options::new(width).word_separator(WordSeparator::UnicodeBreakProperties);
     for line in text.lines() {
         if line.trim().is_empty() {
             result.push(String::new());
             continue;
         }
-        let wrapped = textwrap::wrap(line, width);
+        let wrapped = textwrap::wrap(line, &options);
         for w in wrapped {
             result.push(w.to_string());
         }
     }
     result
 }
+
+/// Like [`wrap_text`], but words wider than `width` are broken with a
+/// trailing hyphen instead of being cut mid-word without a mark.
+pub fn wrap_text_hyphenated(text: &str, width: usize) -> Vec<String> {
+    let mut result = Vec::new();
+    let options = textwrap::Options::new(width)
+        .word_separator(WordSeparator::UnicodeBreakProperties)
+        .break_words(false);
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            result.push(String::new());
+            continue;
+        }
+        for w in textwrap::wrap(line, &options) {
+            // Without `break_words` an overlong word ends up alone on its line
+            if display_width(&w) > width && width > 1 {
+                result.extend(hyphenate_word(&w, width));
+            } else {
+                result.push(w.to_string());
+            }
+        }
+    }
+    result
+}
+
+/// Splits `word` into pieces of at most `width` columns, all but the last
+/// ending in a hyphen.
+fn hyphenate_word(word: &str, width: usize) -> Vec<String> {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    for grapheme in word.graphemes(true) {
+        if !current.is_empty() && display_width(&current) + display_width(grapheme) > width - 1 {
+            pieces.push(std::mem::take(&mut current) + "-");
+        }
+        current.push_str(grapheme);
+    }
+    pieces.push(current);
+    pieces
+}
+
+/// Width of `text` in terminal columns (wide CJK characters count as two).
+pub fn display_width(text: &str) -> usize {
+    textwrap::core::display_width(text)
+}
+
+/// Guess the language of `text` from the Unicode scripts it uses.
+///
+/// Only scripts that identify a language well enough are recognized
+/// (Japanese kana, Hangul, Han, Cyrillic, ...). Latin-script text returns
+/// `None`, as the script alone doesn't tell the language.
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let (mut kana, mut hangul, mut han, mut other) = (0usize, 0usize, 0usize, [0usize; 6]);
+    for c in text.chars() {
+        match c as u32 {
+            0x3040..=0x30FF | 0x31F0..=0x31FF => kana += 1,
+            0xAC00..=0xD7AF | 0x1100..=0x11FF | 0x3130..=0x318F => hangul += 1,
+            0x4E00..=0x9FFF | 0x3400..=0x4DBF => han += 1,
+            0x0400..=0x04FF => other[0] += 1,
+            0x0370..=0x03FF => other[1] += 1,
+            0x0600..=0x06FF => other[2] += 1,
+            0x0590..=0x05FF => other[3] += 1,
+            0x0E00..=0x0E7F => other[4] += 1,
+            0x0900..=0x097F => other[5] += 1,
+            _ => {}
+        }
+    }
+
+    // Kana marks Japanese even when most characters are kanji
+    if kana > 0 {
+        return Some("ja");
+    }
+    let (best, count) = [("ko", hangul), ("zh", han)]
+        .into_iter()
+        .chain(["ru", "el", "ar", "he", "th", "hi"].into_iter().zip(other))
+        .max_by_key(|&(_, count)| count)?;
+    (count > 0).then_some(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(detect_language("夜に駆ける"), Some("ja"));
+        assert_eq!(detect_language("後来"), Some("zh"));
+        assert_eq!(detect_language("사랑해"), Some("ko"));
+        assert_eq!(detect_language("Кино"), Some("ru"));
+        assert_eq!(detect_language("Hello there"), None);
+    }
+
+    #[test]
+    fn test_cjk_wrapping() {
+        let wrapped = wrap_text("夜に駆ける夜に駆ける", 10);
+        assert!(wrapped.len() > 1);
+        assert!(wrapped.iter().all(|line| display_width(line) <= 10));
+        assert_eq!(wrapped.concat(), "夜に駆ける夜に駆ける");
+    }
+
+    #[test]
+    fn test_hyphenated_wrapping() {
+        assert_eq!(
+            wrap_text_hyphenated("Supercalifragilistic fun", 8),
+            vec!["Superca-", "lifragi-", "listic", "fun"]
+        );
+        assert_eq!(wrap_text_hyphenated("short words", 8), wrap_text("short words", 8));
+    }
+}
@@ -231,10 +231,15 @@ impl PlaybackTimer {
     /// This is the last position set via [`set_position`](Self::set_position)
     /// or [`reset`](Self::reset).
     #[must_use]
-    #[allow(dead_code)]
     pub const fn anchor_position(&self) -> f64 {
         self.anchor_position
     }
+
+    /// Returns how long ago the monotonic anchor was set, if the timer is running.
+    #[must_use]
+    pub fn anchor_age(&self) -> Option<std::time::Duration> {
+        self.anchor_instant.map(|instant| instant.elapsed())
+    }
 }
 
 /// Sanitizes a position value to ensure it's valid for playback tracking.
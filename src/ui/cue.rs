@@ -0,0 +1,78 @@
+//! Cue lines (TUI).
+//!
+//! Lines matching a `[cues]` pattern, or marked with a `# cue` comment in
+//! LRC, flash the screen and/or ring the terminal bell when they become
+//! active, for timing sing-along moments or automation cues.
+
+use crate::config::{CueAction, CuesConfig};
+use crate::lyrics::LyricLine;
+use regex::Regex;
+use std::io::Write;
+use std::time::Duration;
+
+/// How long the screen stays inverted for a flash.
+pub const FLASH_DURATION: Duration = Duration::from_millis(150);
+
+/// Compiled `[cues]` section.
+#[derive(Debug, Clone, Default)]
+pub struct Cues {
+    patterns: Vec<Regex>,
+    action: CueAction,
+}
+
+impl Cues {
+    /// Compiles the patterns from the config file.
+    ///
+    /// Invalid patterns are logged and skipped.
+    pub fn compile(config: &CuesConfig) -> Self {
+        let patterns = config
+            .patterns
+            .iter()
+            .filter_map(|pattern| match Regex::new(pattern) {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    tracing::warn!(pattern = %pattern, error = %e, "Ignoring invalid cue pattern");
+                    None
+                }
+            })
+            .collect();
+        Self { patterns, action: config.action }
+    }
+
+    /// Whether `line` is a cue.
+    pub fn is_cue(&self, line: &LyricLine) -> bool {
+        line.cue || self.patterns.iter().any(|pattern| pattern.is_match(&line.text))
+    }
+
+    /// Whether a cue flashes the screen.
+    pub fn flashes(&self) -> bool {
+        matches!(self.action, CueAction::Flash | CueAction::Both)
+    }
+
+    /// Rings the terminal bell, if cues do.
+    pub fn ring(&self) {
+        if matches!(self.action, CueAction::Bell | CueAction::Both) {
+            let mut stdout = std::io::stdout();
+            let _ = stdout.write_all(b"\x07").and_then(|()| stdout.flush());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cue_lines() {
+        let cues = Cues::compile(&CuesConfig {
+            patterns: vec!["(?i)^chorus".into(), "(".into()],
+            action: CueAction::Bell,
+        });
+        let line = |text: &str, cue| LyricLine { text: text.into(), cue, ..Default::default() };
+
+        assert!(cues.is_cue(&line("CHORUS: sing along", false)));
+        assert!(cues.is_cue(&line("Marked in the LRC", true)));
+        assert!(!cues.is_cue(&line("Verse", false)));
+        assert!(!cues.flashes());
+    }
+}
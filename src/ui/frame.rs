@@ -0,0 +1,107 @@
+//! Frame scheduling for the TUI.
+//!
+//! Updates, key presses and status messages request a frame instead of
+//! drawing right away. [`FrameScheduler`] draws the first request at once
+//! and folds everything arriving within the next [`FRAME_INTERVAL`] into a
+//! single draw, so a player flooding D-Bus with property changes costs one
+//! frame instead of one per message. Timer-driven karaoke frames go through
+//! the same scheduler, spaced by the `--karaoke-fps` interval instead.
+
+use std::time::{Duration, Instant};
+
+/// Shortest time between two frames (about 60 per second).
+pub const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Coalesces draw requests into frames at most [`FRAME_INTERVAL`] apart.
+#[derive(Debug, Clone, Default)]
+pub struct FrameScheduler {
+    /// When the last frame was drawn
+    last_frame: Option<Instant>,
+    /// When the requested frame is due, if one is pending
+    pending: Option<Instant>,
+    /// Shortest time between timer-driven frames (`--karaoke-fps`)
+    animation_interval: Option<Duration>,
+}
+
+impl FrameScheduler {
+    /// A scheduler spacing timer-driven frames to at most `fps` per second.
+    pub fn with_animation_fps(fps: Option<u32>) -> Self {
+        Self {
+            animation_interval: fps.filter(|&fps| fps > 0).map(|fps| Duration::from_secs_f64(1.0 / f64::from(fps))),
+            ..Default::default()
+        }
+    }
+
+    /// Asks for a frame: now, or one interval after the previous frame.
+    pub fn request(&mut self, now: Instant) {
+        self.request_after(now, FRAME_INTERVAL);
+    }
+
+    /// Asks for a timer-driven frame (karaoke progress), which also waits
+    /// out the animation interval.
+    pub fn request_animation(&mut self, now: Instant) {
+        let interval = self.animation_interval.map_or(FRAME_INTERVAL, |i| i.max(FRAME_INTERVAL));
+        self.request_after(now, interval);
+    }
+
+    /// Schedules a frame `interval` after the previous one; an earlier
+    /// pending frame is kept.
+    fn request_after(&mut self, now: Instant, interval: Duration) {
+        let due = self.last_frame.map_or(now, |last| last + interval).max(now);
+        self.pending = Some(self.pending.map_or(due, |pending| pending.min(due)));
+    }
+
+    /// When the pending frame should be drawn.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.pending
+    }
+
+    /// Records a frame drawn at `now`, satisfying any pending request.
+    pub fn drawn(&mut self, now: Instant) {
+        self.last_frame = Some(now);
+        self.pending = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requests_within_a_frame_coalesce() {
+        let start = Instant::now();
+        let mut frames = FrameScheduler::default();
+
+        // The first request after a quiet period is drawn at once
+        frames.request(start);
+        assert_eq!(frames.deadline(), Some(start));
+        frames.drawn(start);
+        assert_eq!(frames.deadline(), None);
+
+        // Requests during the next interval share one frame at its end
+        let due = start + FRAME_INTERVAL;
+        frames.request(start + Duration::from_millis(2));
+        frames.request(start + Duration::from_millis(9));
+        assert_eq!(frames.deadline(), Some(due));
+        frames.drawn(due);
+
+        let later = due + Duration::from_secs(1);
+        frames.request(later);
+        assert_eq!(frames.deadline(), Some(later));
+    }
+
+    #[test]
+    fn test_animation_frames_keep_their_rate() {
+        let start = Instant::now();
+        let mut frames = FrameScheduler::with_animation_fps(Some(10));
+        frames.drawn(start);
+
+        // Karaoke frames wait out the --karaoke-fps interval...
+        frames.request_animation(start + Duration::from_millis(5));
+        assert_eq!(frames.deadline(), Some(start + Duration::from_millis(100)));
+
+        // ...but a key press or update in between is drawn at the usual rate
+        frames.request(start + Duration::from_millis(10));
+        assert_eq!(frames.deadline(), Some(start + FRAME_INTERVAL));
+    }
+}
@@ -0,0 +1,188 @@
+//! Golden-file rendering tests.
+//!
+//! Representative [`Update`] fixtures are drawn onto a ratatui `TestBackend`
+//! and compared with the snapshots in `src/ui/snapshots/`. Each snapshot
+//! holds the screen text followed by a style mask: one character per cell,
+//! `g`/`y`/`c`/... for the foreground color (uppercase when bold), `-` for
+//! dimmed or italic text and a space for unstyled cells.
+//!
+//! After an intended layout change, regenerate the snapshots with
+//! `UPDATE_SNAPSHOTS=1 cargo test golden` and review the diff.
+
+use crate::lyrics::LyricLine;
+use crate::lyrics::types::WordTiming;
+use crate::state::{Provider, Update};
+use crate::ui::modern_helpers::{WrapCache, draw_loading, draw_splash, draw_ui_with_cache};
+use crate::ui::styles::LyricStyles;
+use crate::ui::translation::TranslationMode;
+use ratatui::{Terminal, backend::TestBackend, buffer::Cell, style::{Color, Modifier}};
+use std::sync::Arc;
+
+/// Draws `update` on a `width`×`height` screen and returns the snapshot text.
+fn render(update: Update, width: u16, height: u16) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    draw_ui_with_cache(
+        &mut terminal,
+        &Some(update),
+        &mut WrapCache::default(),
+        &LyricStyles::default(),
+        true,
+        None,
+        0,
+        TranslationMode::Original,
+        None,
+        None,
+    )
+    .unwrap();
+    snapshot(&terminal, width)
+}
+
+/// Snapshot text of what was last drawn on `terminal`.
+fn snapshot(terminal: &Terminal<TestBackend>, width: u16) -> String {
+    let buffer = terminal.backend().buffer();
+    let rows: Vec<&[Cell]> = buffer.content.chunks(width as usize).collect();
+    let mut text = String::new();
+    let mut mask = String::new();
+    for row in rows {
+        text.push('|');
+        mask.push('|');
+        let mut skip = 0;
+        for cell in row {
+            // Cells covered by a wide character repeat its style but not its text
+            if skip == 0 {
+                text.push_str(cell.symbol());
+                skip = crate::text_utils::display_width(cell.symbol()).max(1);
+            }
+            skip -= 1;
+            mask.push(style_code(cell));
+        }
+        text.push_str("|\n");
+        mask.push_str("|\n");
+    }
+    format!("{text}\n{mask}")
+}
+
+/// One-character summary of a cell's style for the mask.
+fn style_code(cell: &Cell) -> char {
+    let code = match cell.fg {
+        Color::Reset if cell.modifier.intersects(Modifier::DIM | Modifier::ITALIC) => '-',
+        Color::Reset if cell.modifier.contains(Modifier::BOLD) => 'b',
+        Color::Reset => ' ',
+        Color::Green => 'g',
+        Color::Yellow => 'y',
+        Color::Cyan => 'c',
+        Color::White => 'w',
+        Color::DarkGray => 'd',
+        _ => '?',
+    };
+    if cell.modifier.contains(Modifier::BOLD) { code.to_ascii_uppercase() } else { code }
+}
+
+/// Compares `actual` with the snapshot `name`, or rewrites it when
+/// `UPDATE_SNAPSHOTS` is set.
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = format!("{}/src/ui/snapshots/{name}.txt", env!("CARGO_MANIFEST_DIR"));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("missing snapshot {path} ({e}); run with UPDATE_SNAPSHOTS=1"));
+    assert!(actual == expected, "snapshot {name} changed\n--- expected\n{expected}\n--- actual\n{actual}");
+}
+
+fn line(time: f64, text: &str) -> LyricLine {
+    LyricLine { time, text: text.into(), ..Default::default() }
+}
+
+/// A line with one word every second starting at `time`.
+fn word_line(time: f64, text: &str) -> LyricLine {
+    let words = text
+        .split(' ')
+        .enumerate()
+        .map(|(i, word)| WordTiming::new(time + i as f64, time + i as f64 + 1.0, word))
+        .collect();
+    LyricLine { words: Some(words), ..line(time, text) }
+}
+
+fn update(lines: Vec<LyricLine>, index: Option<usize>, position: f64) -> Update {
+    Update { lines: Arc::new(lines), index, position, playing: true, ..Default::default() }
+}
+
+#[test]
+fn test_golden_karaoke_mid_word() {
+    let lines = vec![
+        word_line(0.0, "Is this the real life"),
+        word_line(5.0, "Is this just fantasy"),
+        word_line(10.0, "Caught in a landslide"),
+    ];
+    // Halfway through "fantasy"
+    let update = Update { provider: Some(Provider::MusixmatchRichsync), ..update(lines, Some(1), 8.5) };
+    assert_snapshot("karaoke_mid_word", &render(update, 32, 5));
+}
+
+#[test]
+fn test_golden_error() {
+    let update = Update {
+        err: Some(crate::state::LookupError {
+            kind: crate::state::ErrorKind::Api,
+            detail: "Musixmatch: token expired, set MUSIXMATCH_USERTOKEN to a fresh one".into(),
+        }),
+        ..update(Vec::new(), None, 0.0)
+    };
+    assert_snapshot("error", &render(update, 24, 6));
+}
+
+#[test]
+fn test_golden_wrapping() {
+    let lines = vec![
+        line(0.0, "A short line"),
+        line(4.0, "A much longer line that has to wrap over several rows of the screen"),
+        line(9.0, "Next"),
+    ];
+    assert_snapshot("wrapping", &render(update(lines, Some(1), 5.0), 20, 8));
+}
+
+#[test]
+fn test_golden_rtl_and_wide() {
+    let lines = vec![
+        line(0.0, "שלום עולם"),
+        line(3.0, "مرحبا بالعالم"),
+        line(6.0, "夜に駆ける"),
+    ];
+    assert_snapshot("rtl_and_wide", &render(update(lines, Some(1), 4.0), 20, 5));
+}
+
+#[test]
+fn test_golden_paused() {
+    let lines = vec![line(0.0, "First"), line(3.0, "Second"), line(6.0, "Third")];
+    let update = Update { playing: false, ..update(lines, Some(1), 4.0) };
+    assert_snapshot("paused", &render(update, 24, 5));
+}
+
+#[test]
+fn test_golden_low_confidence() {
+    let lines = vec![line(0.0, "First"), line(3.0, "Second"), line(6.0, "Third")];
+    let update = Update { confidence: Some(0.2), ..update(lines, Some(1), 4.0) };
+    assert_snapshot("low_confidence", &render(update, 28, 5));
+}
+
+#[test]
+fn test_golden_splash() {
+    let update = Update {
+        artist: "Queen".into(),
+        title: "Bohemian Rhapsody".into(),
+        album: "A Night at the Opera".into(),
+        ..update(Vec::new(), None, 0.0)
+    };
+    let mut terminal = Terminal::new(TestBackend::new(40, 7)).unwrap();
+    draw_splash(&mut terminal, &update, &LyricStyles::default()).unwrap();
+    assert_snapshot("splash", &snapshot(&terminal, 40));
+}
+
+#[test]
+fn test_golden_loading() {
+    let mut terminal = Terminal::new(TestBackend::new(20, 3)).unwrap();
+    draw_loading(&mut terminal, &LyricStyles::default()).unwrap();
+    assert_snapshot("loading", &snapshot(&terminal, 20));
+}
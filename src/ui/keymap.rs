@@ -0,0 +1,361 @@
+//! Key bindings for the modern TUI.
+//!
+//! A [`Keymap`] maps key sequences to [`Action`]s. It starts from one of the
+//! built-in presets (`default`, `vim`, `emacs`) selected in the `[keys]`
+//! config section, and per-action entries there replace the preset's keys:
+//!
+//! ```toml
+//! [keys]
+//! preset = "vim"
+//! toggle-karaoke = "K"
+//! quit = ["q", "ctrl-c"]
+//! ```
+//!
+//! Keys are written as `q`, `G`, `?`, `esc`, `space`, `up`, `pageup`,
+//! `home`, ... with optional `ctrl-` / `alt-` prefixes; space-separated keys
+//! form a sequence (`"g g"`).
+//!
+//! Several actions may share a key; the first one that applies wins (e.g.
+//! `Esc` follows the live line after scrolling, and quits otherwise).
+
+use crate::config::{KeyConfig, KeyPreset};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+/// Something the user can do with a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    Quit,
+    ToggleKaraoke,
+    CycleTranslation,
+    ScrollUp,
+    ScrollDown,
+    PageUp,
+    PageDown,
+    ScrollTop,
+    ScrollBottom,
+    /// Return to the live line after scrolling
+    Follow,
+    /// Toggle the help overlay
+    Help,
+    /// Report the lyrics as wrong and try the next provider
+    WrongMatch,
+    /// Always use the current lyrics' provider for the track
+    PinProvider,
+    /// Scroll unsynced lyrics faster (`--teleprompter`)
+    TeleprompterFaster,
+    /// Scroll unsynced lyrics slower (`--teleprompter`)
+    TeleprompterSlower,
+}
+
+impl Action {
+    /// All actions, in the order they are listed in help.
+    pub const ALL: [Action; 15] = [
+        Action::ScrollUp,
+        Action::ScrollDown,
+        Action::PageUp,
+        Action::PageDown,
+        Action::ScrollTop,
+        Action::ScrollBottom,
+        Action::Follow,
+        Action::ToggleKaraoke,
+        Action::CycleTranslation,
+        Action::WrongMatch,
+        Action::PinProvider,
+        Action::TeleprompterFaster,
+        Action::TeleprompterSlower,
+        Action::Help,
+        Action::Quit,
+    ];
+
+    /// Short description for the help overlay, in the current locale.
+    pub fn description(self) -> &'static str {
+        crate::i18n::tr(crate::i18n::Msg::Action(self))
+    }
+}
+
+/// A single key press with its relevant modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    code: KeyCode,
+    /// Only CONTROL and ALT; shift is part of the character
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    /// Parses `q`, `G`, `ctrl-c`, `alt-<`, `pageup`, ...
+    fn parse(spec: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = spec;
+        loop {
+            if let Some(r) = rest.strip_prefix("ctrl-").filter(|r| !r.is_empty()) {
+                modifiers |= KeyModifiers::CONTROL;
+                rest = r;
+            } else if let Some(r) = rest.strip_prefix("alt-").filter(|r| !r.is_empty()) {
+                modifiers |= KeyModifiers::ALT;
+                rest = r;
+            } else {
+                break;
+            }
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_lowercase().as_str() {
+                "esc" | "escape" => KeyCode::Esc,
+                "enter" | "return" => KeyCode::Enter,
+                "space" => KeyCode::Char(' '),
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                _ => return None,
+            },
+        };
+        Some(Self { code, modifiers })
+    }
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(key: KeyEvent) -> Self {
+        Self {
+            code: key.code,
+            modifiers: key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT),
+        }
+    }
+}
+
+impl std::fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            f.write_str("Ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            f.write_str("Alt-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Esc => f.write_str("Esc"),
+            KeyCode::Up => f.write_str("↑"),
+            KeyCode::Down => f.write_str("↓"),
+            KeyCode::Left => f.write_str("←"),
+            KeyCode::Right => f.write_str("→"),
+            KeyCode::PageUp => f.write_str("PgUp"),
+            KeyCode::PageDown => f.write_str("PgDn"),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// Result of looking up the keys pressed so far.
+#[derive(Debug, PartialEq)]
+pub enum Lookup {
+    /// Complete binding: candidate actions, in priority order
+    Actions(Vec<Action>),
+    /// Prefix of a longer sequence; wait for the next key
+    Pending,
+    /// Not bound
+    Unbound,
+}
+
+/// Key sequences mapped to actions.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(Vec<KeyChord>, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::preset(KeyPreset::Default)
+    }
+}
+
+impl Keymap {
+    /// Builds the keymap from the `[keys]` config section.
+    ///
+    /// Invalid key specs are logged and skipped.
+    pub fn from_config(config: &KeyConfig) -> Self {
+        let mut keymap = Self::preset(config.preset);
+        for (&action, specs) in &config.bindings {
+            let sequences: Vec<_> = specs
+                .iter()
+                .filter_map(|spec| {
+                    let seq = parse_sequence(spec);
+                    if seq.is_none() {
+                        tracing::warn!(key = %spec, ?action, "Invalid key binding, ignoring");
+                    }
+                    seq
+                })
+                .collect();
+            keymap.bindings.retain(|(_, a)| *a != action);
+            keymap.bindings.extend(sequences.into_iter().map(|seq| (seq, action)));
+        }
+        keymap
+    }
+
+    /// The built-in bindings of `preset`.
+    pub fn preset(preset: KeyPreset) -> Self {
+        let table: &[(&str, Action)] = match preset {
+            KeyPreset::Default => &[
+                ("esc", Action::Follow),
+                ("g g", Action::Follow),
+                ("?", Action::Help),
+                ("q", Action::Quit),
+                ("esc", Action::Quit),
+                ("ctrl-c", Action::Quit),
+                ("k", Action::ToggleKaraoke),
+                ("t", Action::CycleTranslation),
+                ("W", Action::WrongMatch),
+                ("P", Action::PinProvider),
+                ("+", Action::TeleprompterFaster),
+                ("-", Action::TeleprompterSlower),
+                ("up", Action::ScrollUp),
+                ("down", Action::ScrollDown),
+                ("pageup", Action::PageUp),
+                ("pagedown", Action::PageDown),
+                ("home", Action::ScrollTop),
+                ("end", Action::ScrollBottom),
+            ],
+            KeyPreset::Vim => &[
+                ("esc", Action::Follow),
+                ("f", Action::Follow),
+                ("?", Action::Help),
+                ("q", Action::Quit),
+                ("ctrl-c", Action::Quit),
+                ("K", Action::ToggleKaraoke),
+                ("t", Action::CycleTranslation),
+                ("W", Action::WrongMatch),
+                ("P", Action::PinProvider),
+                ("+", Action::TeleprompterFaster),
+                ("-", Action::TeleprompterSlower),
+                ("k", Action::ScrollUp),
+                ("up", Action::ScrollUp),
+                ("j", Action::ScrollDown),
+                ("down", Action::ScrollDown),
+                ("ctrl-u", Action::PageUp),
+                ("ctrl-b", Action::PageUp),
+                ("pageup", Action::PageUp),
+                ("ctrl-d", Action::PageDown),
+                ("ctrl-f", Action::PageDown),
+                ("pagedown", Action::PageDown),
+                ("g g", Action::ScrollTop),
+                ("home", Action::ScrollTop),
+                ("G", Action::ScrollBottom),
+                ("end", Action::ScrollBottom),
+            ],
+            KeyPreset::Emacs => &[
+                ("ctrl-g", Action::Follow),
+                ("esc", Action::Follow),
+                ("?", Action::Help),
+                ("ctrl-x ctrl-c", Action::Quit),
+                ("q", Action::Quit),
+                ("ctrl-c", Action::Quit),
+                ("k", Action::ToggleKaraoke),
+                ("t", Action::CycleTranslation),
+                ("W", Action::WrongMatch),
+                ("P", Action::PinProvider),
+                ("+", Action::TeleprompterFaster),
+                ("-", Action::TeleprompterSlower),
+                ("ctrl-p", Action::ScrollUp),
+                ("up", Action::ScrollUp),
+                ("ctrl-n", Action::ScrollDown),
+                ("down", Action::ScrollDown),
+                ("alt-v", Action::PageUp),
+                ("pageup", Action::PageUp),
+                ("ctrl-v", Action::PageDown),
+                ("pagedown", Action::PageDown),
+                ("alt-<", Action::ScrollTop),
+                ("home", Action::ScrollTop),
+                ("alt->", Action::ScrollBottom),
+                ("end", Action::ScrollBottom),
+            ],
+        };
+
+        Self {
+            bindings: table
+                .iter()
+                .filter_map(|&(spec, action)| Some((parse_sequence(spec)?, action)))
+                .collect(),
+        }
+    }
+
+    /// Looks up the keys pressed so far.
+    pub fn lookup(&self, keys: &[KeyChord]) -> Lookup {
+        let actions: Vec<Action> = self
+            .bindings
+            .iter()
+            .filter(|(seq, _)| seq.as_slice() == keys)
+            .map(|&(_, action)| action)
+            .collect();
+        if !actions.is_empty() {
+            return Lookup::Actions(actions);
+        }
+
+        let is_prefix = self
+            .bindings
+            .iter()
+            .any(|(seq, _)| seq.len() > keys.len() && seq.starts_with(keys));
+        if is_prefix { Lookup::Pending } else { Lookup::Unbound }
+    }
+
+    /// Keys bound to `action`, formatted for display (e.g. `["q", "Ctrl-c"]`).
+    pub fn keys_for(&self, action: Action) -> Vec<String> {
+        self.bindings
+            .iter()
+            .filter(|&&(_, a)| a == action)
+            .map(|(seq, _)| seq.iter().map(ToString::to_string).collect::<Vec<_>>().join(" "))
+            .collect()
+    }
+}
+
+/// Parses a space-separated key sequence.
+fn parse_sequence(spec: &str) -> Option<Vec<KeyChord>> {
+    let seq: Option<Vec<_>> = spec.split_whitespace().map(KeyChord::parse).collect();
+    seq.filter(|s| !s.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(spec: &str) -> Vec<KeyChord> {
+        parse_sequence(spec).unwrap()
+    }
+
+    #[test]
+    fn test_keymap_lookup() {
+        let keymap = Keymap::preset(KeyPreset::Default);
+        assert_eq!(keymap.lookup(&keys("esc")), Lookup::Actions(vec![Action::Follow, Action::Quit]));
+        assert_eq!(keymap.lookup(&keys("g")), Lookup::Pending);
+        assert_eq!(keymap.lookup(&keys("g g")), Lookup::Actions(vec![Action::Follow]));
+        assert_eq!(keymap.lookup(&keys("x")), Lookup::Unbound);
+
+        let vim = Keymap::preset(KeyPreset::Vim);
+        assert_eq!(vim.lookup(&keys("G")), Lookup::Actions(vec![Action::ScrollBottom]));
+        assert_eq!(vim.lookup(&keys("ctrl-d")), Lookup::Actions(vec![Action::PageDown]));
+
+        let config: crate::config::FileConfig = toml::from_str(
+            r#"
+            [keys]
+            preset = "emacs"
+            toggle-karaoke = "K"
+            "#,
+        )
+        .unwrap();
+        let emacs = Keymap::from_config(&config.keys);
+        assert_eq!(emacs.lookup(&keys("ctrl-x ctrl-c")), Lookup::Actions(vec![Action::Quit]));
+        assert_eq!(emacs.lookup(&keys("K")), Lookup::Actions(vec![Action::ToggleKaraoke]));
+        assert_eq!(emacs.lookup(&keys("k")), Lookup::Unbound);
+        assert_eq!(emacs.keys_for(Action::Quit), vec!["Ctrl-x Ctrl-c", "q", "Ctrl-c"]);
+        assert!(KeyChord::parse("bogus").is_none());
+    }
+}
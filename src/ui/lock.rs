@@ -0,0 +1,45 @@
+//! Screen lock detection (TUI, `--low-power`).
+//!
+//! logind sets the `LockedHint` property of the login session while the
+//! screen locker runs. [`watch`] follows it on the system bus so the TUI can
+//! stop per-word redraws nobody sees; without logind (or a session) the
+//! screen simply counts as unlocked.
+
+use futures_util::StreamExt;
+use tokio::sync::watch;
+use zbus::proxy;
+
+/// The caller's logind session (`auto` falls back to the user's display session)
+#[proxy(
+    interface = "org.freedesktop.login1.Session",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1/session/auto"
+)]
+trait Session {
+    #[zbus(property)]
+    fn locked_hint(&self) -> zbus::Result<bool>;
+}
+
+/// Receiver holding whether the screen is locked, updated as it changes.
+pub fn watch() -> watch::Receiver<bool> {
+    let (tx, rx) = watch::channel(false);
+    tokio::spawn(async move {
+        if let Err(err) = follow(&tx).await {
+            tracing::debug!(error = %err, "Can't watch the screen lock, assuming unlocked");
+        }
+    });
+    rx
+}
+
+async fn follow(tx: &watch::Sender<bool>) -> zbus::Result<()> {
+    let conn = zbus::Connection::system().await?;
+    let session = SessionProxy::new(&conn).await?;
+    let mut changes = session.receive_locked_hint_changed().await;
+    tx.send_replace(session.locked_hint().await?);
+    while let Some(change) = changes.next().await {
+        let locked = change.get().await?;
+        tracing::debug!(locked, "Screen lock changed");
+        tx.send_replace(locked);
+    }
+    Ok(())
+}
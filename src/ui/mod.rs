@@ -1,8 +1,18 @@
+#[cfg(test)]
+mod golden;
+pub mod cue;
+pub mod frame;
+pub mod keymap;
+pub mod lock;
 pub mod modern;
 pub mod modern_helpers;
 pub mod progression;
 pub mod pipe;
+pub mod state_stream;
+pub mod status;
 pub mod styles;
+pub mod transition;
+pub mod translation;
 pub mod util;
 
 // Re-export the ergonomic helper so callers can use `crate::ui::track_id(...)`.
@@ -8,14 +8,29 @@
 //!
 //! The event loop uses `tokio::select!` to handle:
 //! - Lyrics updates from MPRIS
-//! - User keyboard input (q/ESC to quit, k to toggle karaoke)
+//! - User keyboard input, resolved through a configurable [`Keymap`]
+//!   (q/ESC to quit, k to toggle karaoke, t to cycle translations, ...)
+//! - Terminal resize (rewrap and redraw) and focus changes
 //! - Per-word timer wakeups for smooth karaoke rendering
+//!   (suspended while unfocused with `--idle-unfocused`, and also while the
+//!   screen is locked with `--low-power`, see [`crate::ui::lock`])
+//! - Fade steps between tracks (`--fade`, see [`crate::ui::transition`])
+//! - A title splash at the start of each track (`--splash`)
+//! - Logged warnings, shown briefly in a status bar ([`crate::ui::status`])
 
+use crate::event::UserCommand;
 use crate::pool;
 use crate::state::Update;
-use crate::ui::styles::LyricStyles;
+use crate::ui::cue::{Cues, FLASH_DURATION};
+use crate::ui::frame::FrameScheduler;
+use crate::ui::keymap::{Action, KeyChord, Keymap, Lookup};
+use crate::ui::styles::{Capabilities, LyricStyles};
+use crate::ui::transition::{Fade, Step};
+use crate::ui::translation::{TranslationMode, TranslationPrefs};
+use crate::ui::modern_helpers::WrapCache;
 use crossterm::{
-    event::{Event, KeyCode},
+    cursor::{Hide, Show},
+    event::{DisableFocusChange, EnableFocusChange, Event, KeyEventKind},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
@@ -30,8 +45,8 @@ use ratatui::{Terminal, backend::CrosstermBackend};
 /// UI state for the modern TUI mode
 pub struct ModernUIState {
     pub last_update: Option<Update>,
-    /// Cached wrapped blocks for the current terminal width: (width, wrapped_blocks)
-    pub wrapped_cache: Option<(usize, Vec<Vec<String>>)>,
+    /// Wrapped blocks for the current terminal width, filled in as lines are drawn
+    pub wrapped_cache: WrapCache,
     pub last_track_id: Option<(String, String, String)>,
     pub should_exit: bool,
     /// Instant when the last Update was received; used to estimate current position
@@ -40,18 +55,75 @@ pub struct ModernUIState {
     pub karaoke_enabled: bool,
     /// Manual scroll offset when paused (in lyric blocks, not wrapped lines)
     pub scroll_offset: isize,
+    /// Original/translation display mode (cycled with 't')
+    pub translation_mode: TranslationMode,
+    /// Remembered translation modes per lyrics language
+    pub translation_prefs: TranslationPrefs,
+    /// Detected language of the current lyrics
+    pub lyrics_language: Option<&'static str>,
+    /// Key bindings
+    pub keymap: Keymap,
+    /// Keys of a partially typed sequence (e.g. the first `g` of `g g`)
+    pub pending_keys: Vec<KeyChord>,
+    /// Help overlay visible (toggled with '?')
+    pub show_help: bool,
+    /// Whether the terminal has focus (as reported by focus events)
+    pub focused: bool,
+    /// Stop per-word redraws while the terminal is unfocused
+    pub idle_when_unfocused: bool,
+    /// Whether the screen is locked (with `--low-power`)
+    pub locked: bool,
+    /// Length of the fade between tracks (`--fade`)
+    pub fade_duration: Option<std::time::Duration>,
+    /// Fade in progress after a track change
+    pub fade: Option<Fade>,
+    /// Show a title splash on track change (`--splash`)
+    pub splash_enabled: bool,
+    /// Splash on screen: when it ends and the track it shows
+    pub splash: Option<(Instant, Update)>,
+    /// Requests to the event loop (wrong-match reports)
+    pub commands: Option<mpsc::Sender<UserCommand>>,
+    /// Nothing received from the event loop yet (startup)
+    pub loading: bool,
+    /// Cue lines from the config file
+    pub cues: Cues,
+    /// Active line at the last cue check
+    pub cue_index: Option<usize>,
+    /// Cue flash on screen until then
+    pub flash_until: Option<Instant>,
 }
 
+/// How long the title splash stays up, unless the first line starts earlier.
+const SPLASH_DURATION: std::time::Duration = std::time::Duration::from_secs(2);
+
 impl ModernUIState {
     pub fn new() -> Self {
         Self {
             last_update: None,
-            wrapped_cache: None,
+            wrapped_cache: WrapCache::default(),
             last_track_id: None,
             should_exit: false,
             last_update_instant: None,
             karaoke_enabled: true,
             scroll_offset: 0,
+            translation_mode: TranslationMode::default(),
+            translation_prefs: TranslationPrefs::default(),
+            lyrics_language: None,
+            keymap: Keymap::default(),
+            pending_keys: Vec::new(),
+            show_help: false,
+            focused: true,
+            idle_when_unfocused: false,
+            locked: false,
+            fade_duration: None,
+            fade: None,
+            splash_enabled: false,
+            splash: None,
+            commands: None,
+            loading: true,
+            cues: Cues::default(),
+            cue_index: None,
+            flash_until: None,
         }
     }
 }
@@ -62,23 +134,36 @@ impl ModernUIState {
 
 /// Display lyrics in modern TUI mode (centered, highlighted, real-time)
 pub async fn display_lyrics_modern(
-    _meta: crate::mpris::TrackMetadata,
-    _pos: f64,
     mpris_config: crate::Config,
     karaoke_enabled: bool,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let max_visible_lines = mpris_config.visible_lines;
     let (tx, mut rx) = mpsc::channel(32);
     let (_shutdown_tx, shutdown_rx) = mpsc::channel(1);
-    tokio::spawn(pool::listen(tx, shutdown_rx, mpris_config.clone()));
-    enable_raw_mode().map_err(to_boxed_err)?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen).map_err(to_boxed_err)?;
-    let backend = CrosstermBackend::new(stdout);
+    let (command_tx, command_rx) = mpsc::channel(4);
+    tokio::spawn(pool::listen(tx, shutdown_rx, Some(command_rx), mpris_config.clone()));
+    let _terminal_guard = TerminalGuard::enter(mpris_config.idle_unfocused || mpris_config.low_power).map_err(to_boxed_err)?;
+    let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend).map_err(to_boxed_err)?;
-    let styles = LyricStyles::default();
+    let capabilities = Capabilities::detect()
+        .with_overrides(mpris_config.no_italics, mpris_config.ascii)
+        .with_color(mpris_config.color);
+    let mut styles = LyricStyles::for_theme(mpris_config.theme)
+        .with_parts(mpris_config.file.colors.parts.as_deref())
+        .degrade(capabilities);
+    styles.easing = mpris_config.karaoke_easing;
+    styles.preview_next = mpris_config.preview_next;
+    styles.hide_past = mpris_config.hide_past;
     let mut state = ModernUIState::new();
     state.karaoke_enabled = karaoke_enabled;
+    state.translation_prefs = TranslationPrefs::load();
+    state.keymap = Keymap::from_config(&mpris_config.file.keys);
+    state.commands = Some(command_tx);
+    state.wrapped_cache = WrapCache::new(mpris_config.hyphenate);
+    state.idle_when_unfocused = mpris_config.idle_unfocused || mpris_config.low_power;
+    state.splash_enabled = mpris_config.splash;
+    state.cues = Cues::compile(&mpris_config.file.cues);
+    state.fade_duration = mpris_config.fade.filter(|&ms| ms > 0).map(std::time::Duration::from_millis);
     // per-word sleep used to schedule redraws only at interesting times (word boundaries)
     let mut next_word_sleep: Option<Pin<Box<Sleep>>> = None;
     // Single background thread to poll for crossterm events and forward them
@@ -113,28 +198,62 @@ pub async fn display_lyrics_modern(
             }
         }
     });
-    // Main event loop: handle updates, user input, and timer-driven redraws
+    let mut status_rx = crate::ui::status::subscribe();
+    let mut lock_rx = if mpris_config.low_power {
+        crate::ui::lock::watch()
+    } else {
+        tokio::sync::watch::channel(false).1
+    };
+    let mut frames = FrameScheduler::with_animation_fps(mpris_config.karaoke_fps);
+    // Show the loading screen right away; player and lyrics arrive as updates
+    redraw_and_reschedule(&mut terminal, &mut state, &styles, &mut next_word_sleep, max_visible_lines)?;
+    frames.drawn(Instant::now());
+    // Main event loop: handle updates, user input and timers; changes within
+    // one frame are drawn together (see `crate::ui::frame`)
     while !state.should_exit {
         tokio::select! {
             biased;
 
+            // Draw the frame requested by the changes below; first, so a
+            // flood of updates can't hold it back
+            _ = async {
+                match frames.deadline() {
+                    Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+                    None => futures_util::future::pending::<()>().await,
+                }
+            } => {
+                redraw_and_reschedule(&mut terminal, &mut state, &styles, &mut next_word_sleep, max_visible_lines)?;
+                frames.drawn(Instant::now());
+            }
+
             // MPRIS lyrics/position updates
             update = rx.recv() => {
                 process_update(update, &mut state)?;
-                redraw_and_reschedule(&mut terminal, &mut state, &styles, &mut next_word_sleep, max_visible_lines)?;
+                frames.request(Instant::now());
             }
 
             // User keyboard input
             maybe_event = event_rx.recv() => {
                 if let Some(event) = maybe_event {
-                    process_event(event, &mut state)?;
-                    redraw_and_reschedule(&mut terminal, &mut state, &styles, &mut next_word_sleep, max_visible_lines)?;
+                    process_event(event, &mut state, max_visible_lines)?;
+                    frames.request(Instant::now());
                 } else {
                     // Event channel closed -> exit gracefully
                     state.should_exit = true;
                 }
             }
 
+            // A warning was logged: show it in the status bar
+            Ok(()) = status_rx.changed() => {
+                frames.request(Instant::now());
+            }
+
+            // Screen locked or unlocked (`--low-power`)
+            Ok(()) = lock_rx.changed() => {
+                state.locked = *lock_rx.borrow();
+                frames.request(Instant::now());
+            }
+
             // Per-word timer for smooth karaoke rendering
             _ = async {
                 if let Some(s) = &mut next_word_sleep {
@@ -143,15 +262,61 @@ pub async fn display_lyrics_modern(
                     futures_util::future::pending::<()>().await;
                 }
             } => {
-                redraw_and_reschedule(&mut terminal, &mut state, &styles, &mut next_word_sleep, max_visible_lines)?;
+                next_word_sleep = None;
+                frames.request_animation(Instant::now());
             }
         }
     }
-    disable_raw_mode().map_err(to_boxed_err)?;
-    execute!(io::stdout(), LeaveAlternateScreen).map_err(to_boxed_err)?;
     Ok(())
 }
 
+/// Raw mode, alternate screen and hidden cursor for the lifetime of the TUI.
+///
+/// The terminal is restored when the guard drops, which covers normal exit
+/// and errors; a panic hook restores it before the panic message is printed.
+struct TerminalGuard {
+    focus_change: bool,
+}
+
+impl TerminalGuard {
+    /// Sets up the terminal; `focus_change` also enables focus reporting.
+    fn enter(focus_change: bool) -> io::Result<Self> {
+        static PANIC_HOOK: std::sync::Once = std::sync::Once::new();
+        PANIC_HOOK.call_once(|| {
+            let previous = std::panic::take_hook();
+            std::panic::set_hook(Box::new(move |info| {
+                restore_terminal(true);
+                previous(info);
+            }));
+        });
+
+        enable_raw_mode()?;
+        let guard = Self { focus_change };
+        execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+        if focus_change {
+            execute!(io::stdout(), EnableFocusChange)?;
+        }
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal(self.focus_change);
+    }
+}
+
+/// Leaves raw mode and the alternate screen and shows the cursor again.
+/// Errors are ignored: this runs on exit paths with nothing left to report to.
+fn restore_terminal(focus_change: bool) {
+    let _ = disable_raw_mode();
+    let mut stdout = io::stdout();
+    if focus_change {
+        let _ = execute!(stdout, DisableFocusChange);
+    }
+    let _ = execute!(stdout, LeaveAlternateScreen, Show);
+}
+
 /// Redraw the UI and reschedule the next timer wakeup.
 /// 
 /// Consolidates the repeated pattern of:
@@ -169,37 +334,129 @@ fn redraw_and_reschedule<B: ratatui::backend::Backend>(
         &state.last_update,
         state.last_update_instant,
         state.karaoke_enabled,
+        styles.easing,
     );
 
     // Use estimated update if available, otherwise fall back to stored update
-    let draw_update = estimated_update.or_else(|| state.last_update.clone());
+    let mut draw_update = estimated_update.or_else(|| state.last_update.clone());
 
-    // Reset scroll offset when playback resumes
-    if let Some(ref upd) = draw_update {
-        if upd.playing {
-            state.scroll_offset = 0;
+    // During a track change fade, draw the step's frame instead
+    let now = Instant::now();
+    check_cue(state, draw_update.as_ref(), now);
+    let step = state.fade.as_ref().and_then(|fade| fade.step(now));
+    if step.is_none() {
+        state.fade = None;
+    }
+    let dimmed;
+    let styles = match step {
+        Some(Step::FadeOut) => {
+            draw_update = state.fade.as_ref().and_then(|fade| fade.previous.clone());
+            dimmed = styles.dimmed();
+            &dimmed
+        }
+        Some(Step::Blank) => {
+            draw_update = None;
+            styles
+        }
+        Some(Step::FadeIn) => {
+            dimmed = styles.dimmed();
+            &dimmed
         }
+        None => styles,
+    };
+    let flashed;
+    let styles = if state.flash_until.is_some() {
+        flashed = styles.flashed();
+        &flashed
+    } else {
+        styles
+    };
+
+    // Title splash until it times out or the first line starts
+    if let Some((until, _)) = &state.splash
+        && (now >= *until || draw_update.as_ref().is_some_and(|u| u.index.is_some()))
+    {
+        state.splash = None;
     }
+    let splash = state.splash.as_ref().filter(|_| !matches!(step, Some(Step::FadeOut | Step::Blank)));
+    let status = crate::ui::status::visible(now);
 
-    crate::ui::modern_helpers::draw_ui_with_cache(
-        terminal,
-        &draw_update,
-        &mut state.wrapped_cache,
-        styles,
-        state.karaoke_enabled,
-        max_visible_lines,
-        state.scroll_offset,
-    )?;
+    // Reset scroll offset when playback resumes
+    if let Some(ref upd) = draw_update
+        && upd.playing
+    {
+        state.scroll_offset = 0;
+    }
 
-    *next_word_sleep = next_sleep;
+    if state.loading {
+        crate::ui::modern_helpers::draw_loading(terminal, styles)?;
+    } else if let Some((_, track)) = splash {
+        crate::ui::modern_helpers::draw_splash(terminal, track, styles)?;
+    } else {
+        crate::ui::modern_helpers::draw_ui_with_cache(
+            terminal,
+            &draw_update,
+            &mut state.wrapped_cache,
+            styles,
+            state.karaoke_enabled,
+            max_visible_lines,
+            state.scroll_offset,
+            state.translation_mode,
+            state.show_help.then_some(&state.keymap),
+            status.as_ref().map(|(text, _)| text.as_str()),
+        )?;
+    }
+
+    // Line changes still arrive as updates; only the per-word timer idles
+    let idle = state.locked || (!state.focused && state.idle_when_unfocused);
+    *next_word_sleep = if idle { None } else { next_sleep };
+    if let Some(fade) = &state.fade {
+        wake_by(next_word_sleep, fade.next_step_in(now));
+    }
+    if let Some((until, _)) = &state.splash {
+        wake_by(next_word_sleep, until.saturating_duration_since(now));
+    }
+    if let Some(until) = state.flash_until {
+        wake_by(next_word_sleep, until.saturating_duration_since(now));
+    }
+    // Redraw once more to clear the status bar
+    if let Some((_, remaining)) = status {
+        wake_by(next_word_sleep, remaining);
+    }
     Ok(())
 }
 
-/// Helper: Update cached lines and last update
+/// Flashes and/or rings the bell when a cue line becomes the active line
+/// during playback (see [`crate::ui::cue`]), and ends a finished flash.
+fn check_cue(state: &mut ModernUIState, update: Option<&Update>, now: Instant) {
+    state.flash_until = state.flash_until.filter(|&until| until > now);
+    let index = update.and_then(|u| u.index);
+    if index == state.cue_index {
+        return;
+    }
+    state.cue_index = index;
+    let Some(update) = update.filter(|u| u.playing) else {
+        return;
+    };
+    if index.and_then(|i| update.lines.get(i)).is_some_and(|line| state.cues.is_cue(line)) {
+        state.cues.ring();
+        if state.cues.flashes() {
+            state.flash_until = Some(now + FLASH_DURATION);
+        }
+    }
+}
+
+/// Makes sure the redraw timer fires within `delay`.
+fn wake_by(sleep: &mut Option<Pin<Box<Sleep>>>, delay: std::time::Duration) {
+    let deadline = tokio::time::Instant::now() + delay;
+    match sleep {
+        Some(sleep) if sleep.deadline() <= deadline => {}
+        _ => *sleep = Some(Box::pin(tokio::time::sleep_until(deadline))),
+    }
+}
+
+/// Helper: Update the last update (the wrap cache notices new lyrics by itself)
 fn update_cache_and_state(state: &mut ModernUIState, update: &Update) {
-    // Explicitly clear old cache before creating new one to free memory immediately
-    state.wrapped_cache = None;
-    
     state.last_update = Some(update.clone());
     state.last_update_instant = Some(Instant::now());
 }
@@ -213,9 +470,20 @@ fn update_state(state: &mut ModernUIState, update: Option<Update>) {
         state.should_exit = true;
         return;
     };
+    state.loading = false;
 
     let track_id = crate::ui::track_id(&update);
     let is_new_track = state.last_track_id.as_ref() != Some(&track_id);
+    // The first track fades in too (nothing to fade out, see `Fade::new`)
+    if is_new_track && let Some(duration) = state.fade_duration {
+        state.fade = Some(Fade::new(Instant::now(), duration, state.last_update.clone()));
+    }
+    if is_new_track {
+        state.cue_index = None;
+    }
+    if is_new_track && state.splash_enabled && !update.title.is_empty() {
+        state.splash = Some((Instant::now() + SPLASH_DURATION, update.clone()));
+    }
 
     // Update with error message
     if update.lines.is_empty() && update.err.is_some() {
@@ -235,6 +503,14 @@ fn update_state(state: &mut ModernUIState, update: Option<Update>) {
 
     // Full update with lyrics
     if !update.lines.is_empty() {
+        let lyrics_changed = state
+            .last_update
+            .as_ref()
+            .is_none_or(|last| !std::sync::Arc::ptr_eq(&last.lines, &update.lines));
+        if lyrics_changed {
+            state.lyrics_language = crate::ui::translation::lyrics_language(&update.lines);
+            state.translation_mode = state.translation_prefs.get(state.lyrics_language);
+        }
         update_cache_and_state(state, &update);
         state.last_track_id = Some(track_id);
         return;
@@ -260,46 +536,142 @@ fn process_update(
 }
 
 /// Handle user input events (keyboard)
+///
+/// Keys are resolved through the active [`Keymap`]; of several actions
+/// bound to the same key, the first one that applies is performed.
 fn process_event(
     event: Event,
     state: &mut ModernUIState,
+    max_visible_lines: Option<usize>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    if let Event::Key(key) = event {
-        match key.code {
-            KeyCode::Char('q') | KeyCode::Esc => {
-                state.should_exit = true;
-            }
-            KeyCode::Char('k') => {
-                // Toggle karaoke at runtime
-                state.karaoke_enabled = !state.karaoke_enabled;
-            }
-            KeyCode::Up => {
-                // Scroll up when paused
-                if let Some(ref update) = state.last_update {
-                    if !update.playing {
-                        state.scroll_offset = state.scroll_offset.saturating_sub(1);
-                    }
+    let key = match event {
+        Event::Key(key) => key,
+        // Rewrap for the new width; the caller redraws after every event
+        Event::Resize(..) => {
+            state.wrapped_cache.clear();
+            return Ok(());
+        }
+        Event::FocusGained => {
+            state.focused = true;
+            return Ok(());
+        }
+        Event::FocusLost => {
+            state.focused = false;
+            return Ok(());
+        }
+        _ => return Ok(()),
+    };
+    if key.kind == KeyEventKind::Release {
+        return Ok(());
+    }
+
+    // Any key closes the help overlay
+    if state.show_help {
+        state.show_help = false;
+        state.pending_keys.clear();
+        return Ok(());
+    }
+
+    state.pending_keys.push(KeyChord::from(key));
+    let mut lookup = state.keymap.lookup(&state.pending_keys);
+    if lookup == Lookup::Unbound && state.pending_keys.len() > 1 {
+        // Broken sequence: start over with the last key on its own
+        state.pending_keys.drain(..state.pending_keys.len() - 1);
+        lookup = state.keymap.lookup(&state.pending_keys);
+    }
+
+    match lookup {
+        Lookup::Pending => {}
+        Lookup::Unbound => state.pending_keys.clear(),
+        Lookup::Actions(actions) => {
+            state.pending_keys.clear();
+            for action in actions {
+                if perform_action(state, action, max_visible_lines) {
+                    break;
                 }
             }
-            KeyCode::Down => {
-                // Scroll down when paused
-                if let Some(ref update) = state.last_update {
-                    if !update.playing {
-                        state.scroll_offset = state.scroll_offset.saturating_add(1);
-                    }
-                }
+        }
+    }
+    Ok(())
+}
+
+/// Performs `action`, returning `false` if it doesn't apply right now
+/// (so the next action bound to the same key is tried).
+fn perform_action(state: &mut ModernUIState, action: Action, max_visible_lines: Option<usize>) -> bool {
+    match action {
+        Action::Quit => state.should_exit = true,
+        Action::Help => state.show_help = !state.show_help,
+        Action::ToggleKaraoke => state.karaoke_enabled = !state.karaoke_enabled,
+        Action::CycleTranslation => {
+            // Only if the lyrics have translations
+            let has_translations = state
+                .last_update
+                .as_ref()
+                .is_some_and(|u| u.lines.iter().any(|l| l.translation.is_some()));
+            if !has_translations {
+                return false;
             }
-            KeyCode::Char('c')
-                if key
-                    .modifiers
-                    .contains(crossterm::event::KeyModifiers::CONTROL) =>
-            {
-                state.should_exit = true;
+            state.translation_mode = state.translation_mode.next();
+            state.translation_prefs.set(state.lyrics_language, state.translation_mode);
+        }
+        // Like a pager: snap back to the live line after scrolling
+        Action::Follow => {
+            if state.scroll_offset == 0 {
+                return false;
             }
-            _ => {}
+            state.scroll_offset = 0;
         }
+        // Scrolling (when paused)
+        Action::ScrollUp => scroll_by(state, -1),
+        Action::ScrollDown => scroll_by(state, 1),
+        Action::PageUp => scroll_by(state, -page_size(max_visible_lines)),
+        Action::PageDown => scroll_by(state, page_size(max_visible_lines)),
+        Action::ScrollTop => scroll_by(state, isize::MIN),
+        Action::ScrollBottom => scroll_by(state, isize::MAX),
+        Action::WrongMatch => return send_command(state, UserCommand::WrongMatch),
+        Action::PinProvider => return send_command(state, UserCommand::PinProvider),
+        Action::TeleprompterFaster => return send_command(state, UserCommand::TeleprompterFaster),
+        Action::TeleprompterSlower => return send_command(state, UserCommand::TeleprompterSlower),
     }
-    Ok(())
+    true
+}
+
+/// Sends `command` about the current lyrics to the event loop; `false`
+/// without lyrics.
+fn send_command(state: &ModernUIState, command: UserCommand) -> bool {
+    let has_lyrics = state.last_update.as_ref().is_some_and(|u| !u.lines.is_empty());
+    if !has_lyrics {
+        return false;
+    }
+    if let Some(commands) = &state.commands {
+        let _ = commands.try_send(command);
+    }
+    true
+}
+
+/// Moves the paused view by `delta` lyric lines, clamped to the first and
+/// last line. Does nothing while playing (the view follows playback).
+fn scroll_by(state: &mut ModernUIState, delta: isize) {
+    let Some(ref update) = state.last_update else {
+        return;
+    };
+    if update.playing || update.lines.is_empty() {
+        return;
+    }
+
+    let base = update.index.unwrap_or(0) as isize;
+    let last = update.lines.len() as isize - 1;
+    state.scroll_offset = (base + state.scroll_offset)
+        .saturating_add(delta)
+        .clamp(0, last)
+        - base;
+}
+
+/// Number of lyric lines PageUp/PageDown move by: the visible context on
+/// one side of the current line.
+fn page_size(max_visible_lines: Option<usize>) -> isize {
+    let rows = crossterm::terminal::size().map_or(24, |(_, rows)| rows as usize);
+    (max_visible_lines.unwrap_or(rows) / 2).max(1) as isize
 }
 
 fn to_boxed_err<E: std::error::Error + Send + Sync + 'static>(
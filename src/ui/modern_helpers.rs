@@ -1,14 +1,20 @@
 //! Rendering helpers for the modern TUI mode.
 //!
 //! This module provides:
-//! - Wrapped text caching for efficient re-rendering
+//! - Wrapped text caching for efficient re-rendering (blocks are wrapped on demand)
 //! - Visible line selection with context (before/after current line)
 //! - Per-word karaoke span generation for richsync lyrics
 //! - Centered vertical layout calculation
 
-use crate::text_utils::wrap_text;
+use crate::i18n::{Msg, tr};
+use crate::lyrics::LyricLine;
+use crate::lyrics::confidence::LOW_CONFIDENCE;
+use crate::lyrics::types::WordTiming;
+use crate::text_utils::{display_width, wrap_text, wrap_text_hyphenated};
 use crate::state::Update;
-use crate::ui::styles::LyricStyles;
+use crate::ui::styles::{LyricStyles, PartIndex};
+use crate::ui::keymap::{Action, Keymap};
+use crate::ui::translation::TranslationMode;
 use ratatui::{
     backend::Backend,
     layout::{Alignment, Rect},
@@ -16,28 +22,40 @@ use ratatui::{
     text::{Span, Line},
     widgets::Paragraph,
 };
+use std::borrow::Cow;
 use std::error::Error;
+use std::sync::Arc;
 /// Draw the UI using cached wrapped lines.
 ///
 /// This function handles:
 /// - Error message rendering
-/// - Wrapped text caching (invalidated on width change)
+/// - Wrapped text caching (only visible blocks are wrapped; invalidated on new lyrics or width change)
 /// - Visible line computation with context
 /// - Vertical centering
+/// - Paused treatment (dimmed block and badge)
+/// - "Scrolled" indicator while the view is away from the live line
+/// - Low-confidence indicator when the lyrics may not match the track
+/// - Help overlay listing the key bindings (when `help` is set)
+/// - The latest warning in the bottom row (when `status` is set)
+#[allow(clippy::too_many_arguments)]
 pub fn draw_ui_with_cache<B: Backend>(
     terminal: &mut Terminal<B>,
     last_update: &Option<Update>,
-    wrapped_cache: &mut Option<(usize, Vec<Vec<String>>)>,
+    wrapped_cache: &mut WrapCache,
     styles: &LyricStyles,
     karaoke_enabled: bool,
     max_visible_lines: Option<usize>,
     scroll_offset: isize,
+    translation: TranslationMode,
+    help: Option<&Keymap>,
+    status: Option<&str>,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
     terminal
         .draw(|f| {
             let size = f.area();
             let width = size.width as usize;
             let height = size.height as usize;
+            f.render_widget(ratatui::widgets::Block::new().style(styles.base), size);
 
             let visible_spans = compute_visible_spans(
                 last_update,
@@ -48,25 +66,112 @@ pub fn draw_ui_with_cache<B: Backend>(
                 karaoke_enabled,
                 max_visible_lines,
                 scroll_offset,
+                translation,
             );
 
-            render_centered_paragraph(f, size, visible_spans, height);
+            let paused = last_update
+                .as_ref()
+                .is_some_and(|u| !u.playing && u.err.is_none() && !visible_spans.is_empty());
+            let visible_spans = if paused { dim_lines(visible_spans, styles.paused) } else { visible_spans };
+            let has_lines = !visible_spans.is_empty();
+
+            // With --hide-past the current line is pinned to the top instead
+            let pinned = styles.hide_past && last_update.as_ref().is_some_and(|u| u.err.is_none());
+            let centered_height = if pinned { 0 } else { height };
+            render_centered_paragraph(f, size, visible_spans, centered_height);
+
+            if paused {
+                let symbol = if styles.ascii { PAUSED_SYMBOL_ASCII } else { PAUSED_SYMBOL };
+                let badge = format!("{} {}", symbol, tr(Msg::Paused));
+                render_badge(f, size, &badge, styles.paused_badge, Alignment::Right);
+            }
+            if scroll_offset != 0 && last_update.as_ref().is_some_and(|u| !u.lines.is_empty()) {
+                let symbol = if styles.ascii { "" } else { SCROLLED_SYMBOL };
+                let badge = format!("{}{}", symbol, tr(Msg::Scrolled));
+                render_badge(f, size, &badge, styles.scrolled_badge, Alignment::Left);
+            }
+            let doubtful = last_update.as_ref().and_then(|u| u.confidence).is_some_and(|c| c < LOW_CONFIDENCE);
+            if doubtful && has_lines {
+                let symbol = if styles.ascii { LOW_CONFIDENCE_SYMBOL_ASCII } else { LOW_CONFIDENCE_SYMBOL };
+                let badge = format!("{} {}", symbol, tr(Msg::LowConfidence));
+                render_badge(f, size, &badge, styles.status, Alignment::Center);
+            }
+
+            if let Some(text) = status {
+                render_status(f, size, text, styles);
+            }
+
+            if let Some(keymap) = help {
+                render_help(f, size, keymap, styles);
+            }
         })
         .map_err(|e| Box::new(e) as Box<dyn Error + Send + Sync>)?;
 
     Ok(())
 }
 
+/// Draw the track's title, artist and album centered on an otherwise empty
+/// screen (`--splash`). The title is letter-spaced when it fits, to stand
+/// out as a heading.
+pub fn draw_splash<B: Backend>(
+    terminal: &mut Terminal<B>,
+    update: &Update,
+    styles: &LyricStyles,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    terminal
+        .draw(|f| {
+            let size = f.area();
+            let width = (size.width as usize).max(1);
+            f.render_widget(ratatui::widgets::Block::new().style(styles.base), size);
+
+            let spaced: Vec<String> = update.title.chars().map(String::from).collect();
+            let spaced = spaced.join(" ");
+            let title = if display_width(&spaced) <= width { spaced } else { update.title.clone() };
+            let title_style = styles.current.add_modifier(ratatui::style::Modifier::BOLD);
+
+            let mut lines: Vec<Line> = wrap_text(&title, width)
+                .into_iter()
+                .map(|l| Line::from(Span::styled(l, title_style)))
+                .collect();
+            lines.push(Line::from(""));
+            for (text, style) in [(&update.artist, styles.after), (&update.album, styles.before)] {
+                lines.extend(wrap_text(text, width).into_iter().map(|l| Line::from(Span::styled(l, style))));
+            }
+            render_centered_paragraph(f, size, lines, size.height as usize);
+        })
+        .map_err(|e| Box::new(e) as Box<dyn Error + Send + Sync>)?;
+    Ok(())
+}
+
+/// Draw the startup placeholder: a dimmed "loading" in the middle of the
+/// screen, shown before the event loop has sent anything.
+pub fn draw_loading<B: Backend>(
+    terminal: &mut Terminal<B>,
+    styles: &LyricStyles,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    terminal
+        .draw(|f| {
+            let size = f.area();
+            f.render_widget(ratatui::widgets::Block::new().style(styles.base), size);
+            let lines = vec![Line::from(Span::styled(tr(Msg::Loading), styles.before))];
+            render_centered_paragraph(f, size, lines, size.height as usize);
+        })
+        .map_err(|e| Box::new(e) as Box<dyn Error + Send + Sync>)?;
+    Ok(())
+}
+
 /// Compute the visible spans to render based on current state.
+#[allow(clippy::too_many_arguments)]
 fn compute_visible_spans<'a>(
     last_update: &Option<Update>,
-    wrapped_cache: &mut Option<(usize, Vec<Vec<String>>)>,
+    wrapped_cache: &mut WrapCache,
     width: usize,
     height: usize,
     styles: &'a LyricStyles,
     karaoke_enabled: bool,
     max_visible_lines: Option<usize>,
     scroll_offset: isize,
+    translation: TranslationMode,
 ) -> Vec<Line<'a>> {
     let Some(update) = last_update else {
         return Vec::new();
@@ -74,7 +179,7 @@ fn compute_visible_spans<'a>(
 
     // Render error messages
     if let Some(err) = &update.err {
-        return wrap_text(err, width)
+        return wrap_text(&crate::i18n::lyrics_error(err), width)
             .into_iter()
             .map(|l| Line::from(Span::styled(l, styles.current)))
             .collect();
@@ -85,10 +190,10 @@ fn compute_visible_spans<'a>(
         return Vec::new();
     }
 
-    let blocks = ensure_wrapped_cache(wrapped_cache, &update.lines, width);
+    let mut blocks = WrappedBlocks::new(wrapped_cache, &update.lines, width, translation);
     let visible = gather_visible_lines(
         update,
-        blocks,
+        &mut blocks,
         width,
         height,
         styles,
@@ -96,32 +201,216 @@ fn compute_visible_spans<'a>(
         karaoke_enabled,
         max_visible_lines,
         scroll_offset,
+        translation,
     );
 
     visible.into_vec()
 }
 
-/// Ensure wrapped cache is valid for current width and line count.
-/// Returns a reference to the cached blocks.
-fn ensure_wrapped_cache<'a>(
-    wrapped_cache: &'a mut Option<(usize, Vec<Vec<String>>)>,
-    lines: &[crate::lyrics::LyricLine],
+/// Wrapped lyric blocks, memoized per line.
+///
+/// Blocks are wrapped the first time they are drawn, so a render only pays
+/// for the lines that fit on screen. The cache is keyed by the identity of
+/// the lyrics (`Arc` pointer, which changes with every new track or lyrics
+/// upgrade), the width and the translation mode. Holding the `Arc` keeps the
+/// pointer from being reused by a different set of lyrics.
+#[derive(Debug, Default)]
+pub struct WrapCache {
+    lines: Option<Arc<Vec<LyricLine>>>,
     width: usize,
-) -> &'a Vec<Vec<String>> {
-    let needs_rebuild = match wrapped_cache {
-        Some((cached_w, blocks)) => *cached_w != width || blocks.len() != lines.len(),
-        None => true,
+    translation: TranslationMode,
+    blocks: Vec<Option<Vec<String>>>,
+    /// Vocal part of each line, for duet coloring
+    parts: PartIndex,
+    /// Mark words broken across lines with a hyphen
+    hyphenate: bool,
+}
+
+impl WrapCache {
+    /// An empty cache; `hyphenate` enables hyphens at forced word breaks.
+    pub fn new(hyphenate: bool) -> Self {
+        Self { hyphenate, ..Default::default() }
+    }
+
+    /// Whether the cached blocks were wrapped for these lyrics and settings.
+    fn matches(&self, lines: &Arc<Vec<LyricLine>>, width: usize, translation: TranslationMode) -> bool {
+        self.lines.as_ref().is_some_and(|cached| Arc::ptr_eq(cached, lines))
+            && self.width == width
+            && self.translation == translation
+    }
+
+    /// Drops all wrapped blocks, keeping the settings.
+    pub fn clear(&mut self) {
+        self.lines = None;
+        self.blocks = Vec::new();
+    }
+
+    /// Number of blocks wrapped so far.
+    #[cfg(test)]
+    fn wrapped_count(&self) -> usize {
+        self.blocks.iter().filter(|b| b.is_some()).count()
+    }
+}
+
+/// A [`WrapCache`] bound to the lyrics and width of one frame.
+pub struct WrappedBlocks<'a> {
+    cache: &'a mut WrapCache,
+    lines: &'a [LyricLine],
+    translation: TranslationMode,
+}
+
+impl<'a> WrappedBlocks<'a> {
+    /// Binds `cache` to `lines`, invalidating it if the lyrics or settings changed.
+    pub fn new(
+        cache: &'a mut WrapCache,
+        lines: &'a Arc<Vec<LyricLine>>,
+        width: usize,
+        translation: TranslationMode,
+    ) -> Self {
+        if !cache.matches(lines, width, translation) {
+            *cache = WrapCache {
+                lines: Some(Arc::clone(lines)),
+                width,
+                translation,
+                blocks: vec![None; lines.len()],
+                parts: PartIndex::new(lines),
+                hyphenate: cache.hyphenate,
+            };
+        }
+        Self { cache, lines, translation }
+    }
+
+    /// Number of lyric blocks.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// The wrapped block for line `i`, wrapping it on first access.
+    pub fn get(&mut self, i: usize) -> &[String] {
+        let (lines, translation, width, hyphenate) =
+            (self.lines, self.translation, self.cache.width, self.cache.hyphenate);
+        self.cache.blocks[i].get_or_insert_with(|| wrap_lyric(&translation.display_text(&lines[i]), width, hyphenate))
+    }
+
+    /// Whether forced word breaks get a hyphen.
+    pub fn hyphenate(&self) -> bool {
+        self.cache.hyphenate
+    }
+
+    /// `style` in the color of line `i`'s vocal part, if the song has several.
+    fn part_style(&self, i: usize, style: ratatui::style::Style, styles: &LyricStyles) -> ratatui::style::Style {
+        styles.for_part(style, self.cache.parts.get(i))
+    }
+}
+
+/// Wraps lyric text, optionally hyphenating words wider than the line.
+fn wrap_lyric(text: &str, width: usize, hyphenate: bool) -> Vec<String> {
+    if hyphenate { wrap_text_hyphenated(text, width) } else { wrap_text(text, width) }
+}
+
+/// Symbol in front of the badge shown while playback is paused.
+const PAUSED_SYMBOL: &str = "⏸";
+const PAUSED_SYMBOL_ASCII: &str = "||";
+
+/// Symbol in front of the indicator shown after manual scrolling.
+const SCROLLED_SYMBOL: &str = "↕ ";
+
+/// Symbol in front of status bar warnings.
+const STATUS_SYMBOL: &str = "⚠ ";
+const STATUS_SYMBOL_ASCII: &str = "! ";
+
+/// Symbol in front of the badge shown when the lyrics may not match.
+const LOW_CONFIDENCE_SYMBOL: &str = "≈";
+const LOW_CONFIDENCE_SYMBOL_ASCII: &str = "~";
+
+/// Borders of the help overlay with `--ascii`.
+const ASCII_BORDER: ratatui::symbols::border::Set = ratatui::symbols::border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+/// Dim every line, so a paused screen is distinguishable from a hung one.
+fn dim_lines(lines: Vec<Line<'_>>, dim: ratatui::style::Style) -> Vec<Line<'_>> {
+    lines.into_iter().map(|line| line.patch_style(dim)).collect()
+}
+
+/// Render the help overlay: one row per bound action, generated from the keymap.
+fn render_help(frame: &mut ratatui::Frame, size: Rect, keymap: &Keymap, styles: &LyricStyles) {
+    let rows: Vec<(String, &str)> = Action::ALL
+        .iter()
+        .filter_map(|&action| {
+            let keys = keymap.keys_for(action);
+            let mut keys = keys.join(", ");
+            if styles.ascii {
+                for (arrow, name) in [("↑", "Up"), ("↓", "Down"), ("←", "Left"), ("→", "Right")] {
+                    keys = keys.replace(arrow, name);
+                }
+            }
+            (!keys.is_empty()).then_some((keys, action.description()))
+        })
+        .collect();
+
+    let key_width = rows.iter().map(|(keys, _)| keys.chars().count()).max().unwrap_or(0);
+    let lines: Vec<Line> = rows
+        .iter()
+        .map(|(keys, description)| {
+            Line::from(vec![
+                Span::styled(format!("{:>width$}", keys, width = key_width), styles.current),
+                Span::raw(format!("  {}", description)),
+            ])
+        })
+        .collect();
+
+    let text_width = lines.iter().map(Line::width).max().unwrap_or(0);
+    let width = (text_width as u16 + 4).min(size.width);
+    let height = (lines.len() as u16 + 2).min(size.height);
+    let area = Rect {
+        x: size.x + (size.width - width) / 2,
+        y: size.y + (size.height - height) / 2,
+        width,
+        height,
     };
 
-    if needs_rebuild {
-        let new_blocks: Vec<Vec<String>> = lines
-            .iter()
-            .map(|l| wrap_text(&l.text, width))
-            .collect();
-        *wrapped_cache = Some((width, new_blocks));
+    let mut block = ratatui::widgets::Block::bordered().title(format!(" {} ", tr(Msg::HelpTitle)));
+    if styles.ascii {
+        block = block.border_set(ASCII_BORDER);
+    }
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Render a one-line badge in the top row, aligned left or right.
+fn render_badge(
+    frame: &mut ratatui::Frame,
+    size: Rect,
+    text: &str,
+    style: ratatui::style::Style,
+    alignment: Alignment,
+) {
+    if size.height == 0 {
+        return;
     }
+    let area = Rect { height: 1, ..size };
+    let badge = Paragraph::new(Line::from(Span::styled(text, style))).alignment(alignment);
+    frame.render_widget(badge, area);
+}
 
-    &wrapped_cache.as_ref().unwrap().1
+/// Render a warning in the bottom row, over the lyrics.
+fn render_status(frame: &mut ratatui::Frame, size: Rect, text: &str, styles: &LyricStyles) {
+    if size.height == 0 {
+        return;
+    }
+    let symbol = if styles.ascii { STATUS_SYMBOL_ASCII } else { STATUS_SYMBOL };
+    let area = Rect { y: size.y + size.height - 1, height: 1, ..size };
+    let text = format!("{}{}", symbol, text.replace('\n', " "));
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(Paragraph::new(Span::styled(text, styles.status)).style(styles.base), area);
 }
 
 /// Render a paragraph centered vertically in the given area.
@@ -168,9 +457,10 @@ impl<'a> VisibleLines<'a> {
 /// Collect lines before the current index. Returns Line in visual top->down order.
 fn collect_before_spans<'a>(
     current_index: usize,
-    wrapped_blocks: &[Vec<String>],
+    wrapped_blocks: &mut WrappedBlocks<'_>,
     mut lines_needed: usize,
     style: ratatui::style::Style,
+    styles: &LyricStyles,
 ) -> Vec<Line<'a>> {
     let mut result = Vec::new();
 
@@ -178,7 +468,8 @@ fn collect_before_spans<'a>(
     let mut i = current_index;
     while i > 0 && lines_needed > 0 {
         i -= 1;
-        let block = &wrapped_blocks[i];
+        let style = wrapped_blocks.part_style(i, style, styles);
+        let block = wrapped_blocks.get(i);
         let take = block.len().min(lines_needed);
         let start = block.len() - take;
         // We want these in the same order they appear visually, so collect and then
@@ -198,14 +489,16 @@ fn collect_before_spans<'a>(
 /// Collect lines after the current index. Returns Line in visual top->down order.
 fn collect_after_spans<'a>(
     current_index: usize,
-    wrapped_blocks: &[Vec<String>],
+    wrapped_blocks: &mut WrappedBlocks<'_>,
     mut lines_needed: usize,
     style: ratatui::style::Style,
+    styles: &LyricStyles,
 ) -> Vec<Line<'a>> {
     let mut result = Vec::new();
     let mut j = current_index + 1;
     while j < wrapped_blocks.len() && lines_needed > 0 {
-        let block = &wrapped_blocks[j];
+        let style = wrapped_blocks.part_style(j, style, styles);
+        let block = wrapped_blocks.get(j);
         let take = block.len().min(lines_needed);
         for line in block.iter().take(take) {
             result.push(Line::from(Span::styled(line.clone(), style)));
@@ -220,16 +513,17 @@ fn collect_after_spans<'a>(
 /// Returns all wrapped lines from each block in visual top->down order.
 fn collect_before_blocks<'a>(
     current_index: usize,
-    wrapped_blocks: &[Vec<String>],
+    wrapped_blocks: &mut WrappedBlocks<'_>,
     blocks_needed: usize,
     style: ratatui::style::Style,
+    styles: &LyricStyles,
 ) -> Vec<Line<'a>> {
     let mut result = Vec::new();
     let start_index = current_index.saturating_sub(blocks_needed);
     
     for i in start_index..current_index {
-        let block = &wrapped_blocks[i];
-        for line in block {
+        let style = wrapped_blocks.part_style(i, style, styles);
+        for line in wrapped_blocks.get(i) {
             result.push(Line::from(Span::styled(line.clone(), style)));
         }
     }
@@ -241,16 +535,17 @@ fn collect_before_blocks<'a>(
 /// Returns all wrapped lines from each block in visual top->down order.
 fn collect_after_blocks<'a>(
     current_index: usize,
-    wrapped_blocks: &[Vec<String>],
+    wrapped_blocks: &mut WrappedBlocks<'_>,
     blocks_needed: usize,
     style: ratatui::style::Style,
+    styles: &LyricStyles,
 ) -> Vec<Line<'a>> {
     let mut result = Vec::new();
     let end_index = (current_index + 1 + blocks_needed).min(wrapped_blocks.len());
     
-    for i in (current_index + 1)..end_index {
-        let block = &wrapped_blocks[i];
-        for line in block {
+    for j in (current_index + 1)..end_index {
+        let style = wrapped_blocks.part_style(j, style, styles);
+        for line in wrapped_blocks.get(j) {
             result.push(Line::from(Span::styled(line.clone(), style)));
         }
     }
@@ -258,33 +553,62 @@ fn collect_after_blocks<'a>(
     result
 }
 
-/// Split a slice of WordTiming into visual lines that fit into `width` characters.
-fn split_words_into_lines<'b>(
-    words: &'b [crate::lyrics::types::WordTiming],
-    width: usize,
-) -> Vec<Vec<&'b crate::lyrics::types::WordTiming>> {
-    let mut lines: Vec<Vec<&'b crate::lyrics::types::WordTiming>> = Vec::new();
-    let mut current: Vec<&'b crate::lyrics::types::WordTiming> = Vec::new();
-    let mut cur_len: usize = 0;
-
-    for w in words {
-        let wlen = w.text.chars().count();
-        let candidate = if current.is_empty() { wlen } else { cur_len + 1 + wlen };
-        if !current.is_empty() && candidate > width && width > 0 {
-            lines.push(current);
-            current = Vec::new();
-            cur_len = 0;
+/// Split a slice of WordTiming into visual lines that fit into `width` columns.
+///
+/// The words are wrapped with [`wrap_lyric`], like the plain (non-karaoke)
+/// rendering of the same line, so toggling karaoke keeps the line breaks.
+/// A word broken across lines (typically CJK text without spaces) is cut
+/// into pieces, each keeping its share of the word's timing; with
+/// `hyphenate`, a piece that ends in a hyphen carries it highlighted
+/// together with its last grapheme.
+fn split_words_into_lines(words: &[WordTiming], width: usize, hyphenate: bool) -> Vec<Vec<Cow<'_, WordTiming>>> {
+    // The words as the plain path displays them, and where each one starts
+    let mut text = String::new();
+    let mut starts = Vec::with_capacity(words.len());
+    for (i, w) in words.iter().enumerate() {
+        if i > 0 {
+            text.push(' ');
         }
-        if current.is_empty() {
-            current.push(w);
-            cur_len = wlen;
-        } else {
-            current.push(w);
-            cur_len += 1 + wlen;
+        starts.push(text.len());
+        text.push_str(&w.text);
+    }
+    if width == 0 || text.is_empty() {
+        return vec![words.iter().map(Cow::Borrowed).collect()];
+    }
+
+    let mut lines = Vec::new();
+    let mut cursor = 0;
+    for wrapped in wrap_lyric(&text, width, hyphenate) {
+        // A hyphen added by the wrapping isn't part of the text
+        let (content, hyphen) = match wrapped.strip_suffix('-') {
+            Some(stripped) if hyphenate && !text[cursor..].trim_start().starts_with(wrapped.as_str()) => (stripped, true),
+            _ => (wrapped.as_str(), false),
+        };
+        let Some(offset) = text[cursor..].find(content).filter(|_| !content.is_empty()) else {
+            continue;
+        };
+        let (from, to) = (cursor + offset, cursor + offset + content.len());
+        cursor = to;
+
+        let mut line: Vec<Cow<'_, WordTiming>> = Vec::new();
+        for (w, &start) in words.iter().zip(&starts) {
+            let end = start + w.text.len();
+            if end <= from || start >= to {
+                continue;
+            }
+            if start >= from && end <= to {
+                line.push(Cow::Borrowed(w));
+                continue;
+            }
+            let grapheme = |byte: usize| w.grapheme_at(byte - start);
+            line.push(Cow::Owned(w.slice(grapheme(from.max(start)), grapheme(to.min(end)))));
         }
+        if hyphen && let Some(last) = line.pop() {
+            line.push(Cow::Owned(last.into_owned().with_hyphen()));
+        }
+        lines.push(line);
     }
 
-    if !current.is_empty() { lines.push(current); }
     if lines.is_empty() { lines.push(Vec::new()); }
     lines
 }
@@ -297,9 +621,10 @@ fn split_words_into_lines<'b>(
 /// # Arguments
 /// * `max_visible_lines` - Maximum number of lyric blocks to display (None = unlimited)
 /// * `scroll_offset` - Manual scroll offset in lyric blocks when paused
+#[allow(clippy::too_many_arguments)]
 pub fn gather_visible_lines<'a>(
     update: &Update,
-    wrapped_blocks: &[Vec<String>],
+    wrapped_blocks: &mut WrappedBlocks<'_>,
     w: usize,
     h: usize,
     styles: &'a LyricStyles,
@@ -307,6 +632,7 @@ pub fn gather_visible_lines<'a>(
     karaoke_enabled: bool,
     max_visible_lines: Option<usize>,
     scroll_offset: isize,
+    translation: TranslationMode,
 ) -> VisibleLines<'a> {
     // Calculate the effective index considering scroll offset when paused
     let base_index = update.index.unwrap_or(0);
@@ -319,21 +645,24 @@ pub fn gather_visible_lines<'a>(
         base_index
     };
     
-    let current_block = wrapped_blocks
-        .get(effective_index)
-        .map(|v| v.as_slice())
-        .unwrap_or(&[]);
+    let current_block = if effective_index < wrapped_blocks.len() {
+        wrapped_blocks.get(effective_index).to_vec()
+    } else {
+        Vec::new()
+    };
     let current_height = current_block.len();
 
     // Build current line spans (with karaoke if applicable, but only when not scrolled)
     let use_karaoke = karaoke_enabled && scroll_offset == 0 && update.playing;
     let current_spans = build_current_spans(
         update,
-        current_block,
+        &current_block,
         w,
         styles,
         position,
         use_karaoke,
+        translation,
+        wrapped_blocks.hyphenate(),
     );
 
     // Calculate available height considering max_visible_lines
@@ -345,19 +674,33 @@ pub fn gather_visible_lines<'a>(
         h
     };
 
+    // The pinned preview is shown regardless of the context settings
+    let preview: Vec<Line<'a>> = if styles.preview_next && effective_index + 1 < wrapped_blocks.len() {
+        wrapped_blocks
+            .get(effective_index + 1)
+            .iter()
+            .map(|l| Line::from(Span::styled(l.clone(), styles.preview)))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    // Context after the current line starts below the preview
+    let after_index = effective_index + usize::from(!preview.is_empty());
+    let pinned_height = current_height + preview.len();
+
     // If current block fills the available space, no context needed
-    if current_height >= available_height {
+    if pinned_height >= available_height {
         return VisibleLines {
             before: Vec::new(),
             current: current_spans,
-            after: Vec::new(),
+            after: preview,
         };
     }
 
     // Calculate context lines for max_visible_lines
     let (lines_before, lines_after) = if let Some(max) = max_visible_lines {
         // Limit to max blocks total
-        let context_blocks = max.saturating_sub(1); // -1 for current block
+        let context_blocks = max.saturating_sub(1 + usize::from(!preview.is_empty())); // minus current block and preview
         let before_blocks = context_blocks / 2;
         let after_blocks = context_blocks - before_blocks;
         
@@ -366,23 +709,30 @@ pub fn gather_visible_lines<'a>(
         (before_blocks, after_blocks)
     } else {
         // Original behavior: fill screen with wrapped lines
-        let context_lines = available_height.saturating_sub(current_height);
+        let context_lines = available_height.saturating_sub(pinned_height);
         let lines_before = context_lines / 2;
         let lines_after = context_lines - lines_before;
         (lines_before, lines_after)
     };
+    // With --hide-past all of the context goes below the current line
+    let (lines_before, lines_after) = if styles.hide_past {
+        (0, lines_before + lines_after)
+    } else {
+        (lines_before, lines_after)
+    };
 
     let before = if max_visible_lines.is_some() {
-        collect_before_blocks(effective_index, wrapped_blocks, lines_before, styles.before)
+        collect_before_blocks(effective_index, wrapped_blocks, lines_before, styles.before, styles)
     } else {
-        collect_before_spans(effective_index, wrapped_blocks, lines_before, styles.before)
+        collect_before_spans(effective_index, wrapped_blocks, lines_before, styles.before, styles)
     };
     
-    let after = if max_visible_lines.is_some() {
-        collect_after_blocks(effective_index, wrapped_blocks, lines_after, styles.after)
+    let mut after = preview;
+    if max_visible_lines.is_some() {
+        after.extend(collect_after_blocks(after_index, wrapped_blocks, lines_after, styles.after, styles));
     } else {
-        collect_after_spans(effective_index, wrapped_blocks, lines_after, styles.after)
-    };
+        after.extend(collect_after_spans(after_index, wrapped_blocks, lines_after, styles.after, styles));
+    }
 
     VisibleLines {
         before,
@@ -392,6 +742,7 @@ pub fn gather_visible_lines<'a>(
 }
 
 /// Build spans for the current line, applying karaoke highlighting if appropriate.
+#[allow(clippy::too_many_arguments)]
 fn build_current_spans<'a>(
     update: &Update,
     current_block: &[String],
@@ -399,11 +750,13 @@ fn build_current_spans<'a>(
     styles: &'a LyricStyles,
     position: f64,
     karaoke_enabled: bool,
+    translation: TranslationMode,
+    hyphenate: bool,
 ) -> Vec<Line<'a>> {
     // Try to build richsync karaoke spans
     if let Some(idx) = update.index
-        && karaoke_enabled && matches!(update.provider, Some(crate::state::Provider::MusixmatchRichsync))
-            && let Some(spans) = try_build_karaoke_spans(update, idx, width, styles, position) {
+        && karaoke_enabled && update.provider.is_some_and(crate::state::Provider::is_word_synced)
+            && let Some(spans) = try_build_karaoke_spans(update, idx, width, styles, position, translation, hyphenate) {
                 return spans;
             }
 
@@ -421,17 +774,25 @@ fn build_current_spans<'a>(
 }
 
 /// Try to build per-word karaoke spans for richsync lyrics.
+///
+/// In [`TranslationMode::Both`] the translation follows the highlighted
+/// words; in [`TranslationMode::Translation`] there are no words to highlight.
 fn try_build_karaoke_spans<'a>(
     update: &Update,
     idx: usize,
     width: usize,
     styles: &'a LyricStyles,
     position: f64,
+    translation: TranslationMode,
+    hyphenate: bool,
 ) -> Option<Vec<Line<'a>>> {
     let line = update.lines.get(idx)?;
     let words = line.words.as_ref()?;
+    if !translation.shows_original_words(line) {
+        return None;
+    }
 
-    let word_lines = split_words_into_lines(words, width);
+    let word_lines = split_words_into_lines(words, width, hyphenate);
     let mut result = Vec::new();
 
     for word_line in word_lines {
@@ -439,12 +800,18 @@ fn try_build_karaoke_spans<'a>(
         result.push(Line::from(line_spans));
     }
 
+    if translation == TranslationMode::Both
+        && let Some(text) = &line.translation
+    {
+        result.extend(wrap_lyric(text, width, hyphenate).into_iter().map(|l| Line::from(Span::styled(l, styles.current))));
+    }
+
     Some(result)
 }
 
 /// Build spans for a single line of words with per-word/grapheme highlighting.
 fn build_word_line_spans<'a>(
-    words: &[&crate::lyrics::types::WordTiming],
+    words: &[Cow<'_, WordTiming>],
     position: f64,
     styles: &'a LyricStyles,
 ) -> Vec<Span<'a>> {
@@ -461,7 +828,7 @@ fn build_word_line_spans<'a>(
 
 /// Build spans for a single word with partial grapheme highlighting.
 fn build_word_spans<'a>(
-    word: &crate::lyrics::types::WordTiming,
+    word: &WordTiming,
     position: f64,
     styles: &'a LyricStyles,
     is_last_in_line: bool,
@@ -480,7 +847,7 @@ fn build_word_spans<'a>(
 
     // Word partially highlighted
     let duration = (word.end - word.start).max(f64::EPSILON);
-    let fraction = ((position - word.start) / duration).clamp(0.0, 1.0);
+    let fraction = styles.easing.apply((position - word.start) / duration);
     let total_graphemes = word.grapheme_count();
     let highlighted_count = ((fraction * total_graphemes as f64).floor() as usize).min(total_graphemes);
 
@@ -493,12 +860,135 @@ fn build_word_spans<'a>(
     }
 
     // Split at grapheme boundary using the precomputed boundaries
-    let split_byte = word.grapheme_boundaries[highlighted_count];
-    let highlighted = &word.text[..split_byte];
-    let remaining = &word.text[split_byte..];
+    let (highlighted, remaining) = word.split_at_grapheme(highlighted_count);
 
     vec![
         Span::styled(highlighted.to_string(), styles.current),
         Span::styled(format!("{}{}", remaining, suffix), styles.after),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_visible_blocks_are_wrapped() {
+        let lines: Vec<LyricLine> = (0..200)
+            .map(|i| LyricLine { time: i as f64, text: format!("line {i}"), ..Default::default() })
+            .collect();
+        let update = Update {
+            lines: Arc::new(lines),
+            index: Some(100),
+            playing: true,
+            ..Default::default()
+        };
+        let styles = LyricStyles::default();
+        let mut cache = WrapCache::default();
+
+        let spans = compute_visible_spans(
+            &Some(update), &mut cache, 40, 9, &styles, false, None, 0, TranslationMode::Original,
+        );
+        assert_eq!(spans.len(), 9);
+        assert_eq!(cache.wrapped_count(), 9);
+    }
+
+    #[test]
+    fn test_next_line_preview_ignores_context_limit() {
+        let lines: Vec<LyricLine> = (0..5)
+            .map(|i| LyricLine { time: i as f64, text: format!("line {i}"), ..Default::default() })
+            .collect();
+        let update = Update { lines: Arc::new(lines), index: Some(2), playing: true, ..Default::default() };
+        let styles = LyricStyles { preview_next: true, ..LyricStyles::default() };
+
+        let spans = compute_visible_spans(
+            &Some(update.clone()), &mut WrapCache::default(), 40, 9, &styles, false, Some(1), 0, TranslationMode::Original,
+        );
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[1].to_string(), "line 3");
+        assert_eq!(spans[1].spans[0].style, styles.preview);
+
+        // With room for context, later lines follow the preview in the normal style
+        let spans = compute_visible_spans(
+            &Some(update), &mut WrapCache::default(), 40, 9, &styles, false, Some(5), 0, TranslationMode::Original,
+        );
+        let texts: Vec<String> = spans.iter().map(|l| l.to_string()).collect();
+        assert_eq!(texts, ["line 1", "line 2", "line 3", "line 4"]);
+        assert_eq!(spans[3].spans[0].style, styles.after);
+    }
+
+    #[test]
+    fn test_hide_past_lines() {
+        let lines: Vec<LyricLine> = (0..6)
+            .map(|i| LyricLine { time: i as f64, text: format!("line {i}"), ..Default::default() })
+            .collect();
+        let update = Update { lines: Arc::new(lines), index: Some(2), playing: true, ..Default::default() };
+        let styles = LyricStyles { hide_past: true, ..LyricStyles::default() };
+
+        for max_visible_lines in [None, Some(3)] {
+            let spans = compute_visible_spans(
+                &Some(update.clone()), &mut WrapCache::default(), 40, 3, &styles, false, max_visible_lines, 0, TranslationMode::Original,
+            );
+            let texts: Vec<String> = spans.iter().map(|l| l.to_string()).collect();
+            assert_eq!(texts, ["line 2", "line 3", "line 4"]);
+        }
+    }
+
+    #[test]
+    fn test_wrap_cache_invalidated_by_new_lyrics_of_same_length() {
+        let song = |word: &str| {
+            Arc::new((0..3).map(|i| LyricLine { time: i as f64, text: format!("{word} {i}"), ..Default::default() }).collect::<Vec<_>>())
+        };
+        let first = song("first");
+        let second = song("second");
+        let mut cache = WrapCache::default();
+
+        assert_eq!(WrappedBlocks::new(&mut cache, &first, 40, TranslationMode::Original).get(1), ["first 1"]);
+        assert_eq!(WrappedBlocks::new(&mut cache, &second, 40, TranslationMode::Original).get(1), ["second 1"]);
+    }
+
+    #[test]
+    fn test_karaoke_breaks_cjk_words() {
+        let text = "夜に駆ける沈むように";
+        let words = [WordTiming::new(0.0, 1.0, "a"), WordTiming::new(0.0, 10.0, text)];
+
+        let lines = split_words_into_lines(&words, 8, false);
+        let texts: Vec<String> = lines.iter().map(|l| l.iter().map(|w| w.text.as_str()).collect::<Vec<_>>().join(" ")).collect();
+        assert!(texts.iter().all(|t| display_width(t) <= 8), "{texts:?}");
+        assert_eq!(texts.concat().replace(' ', ""), format!("a{text}"));
+
+        // Pieces keep their share of the timing
+        let last = lines.last().unwrap().last().unwrap();
+        assert_eq!(last.end, 10.0);
+        assert!(last.start > 0.0);
+    }
+
+    #[test]
+    fn test_karaoke_breaks_match_plain_wrapping() {
+        let text = "Is this the real life, is this just a well-known fantasy";
+        let words: Vec<WordTiming> = text
+            .split(' ')
+            .enumerate()
+            .map(|(i, w)| WordTiming::new(i as f64, i as f64 + 1.0, w))
+            .collect();
+
+        for (width, hyphenate) in [(12, false), (7, true), (30, false)] {
+            let karaoke: Vec<String> = split_words_into_lines(&words, width, hyphenate)
+                .iter()
+                .map(|l| l.iter().map(|w| w.text.as_str()).collect::<Vec<_>>().join(" "))
+                .collect();
+            assert_eq!(karaoke, wrap_lyric(text, width, hyphenate), "width {width}");
+        }
+    }
+
+    #[test]
+    fn test_karaoke_cuts_overlong_words() {
+        let text = "Supercalifragilistic";
+        let word = WordTiming::new(0.0, 1.0, text);
+
+        let lines = split_words_into_lines(std::slice::from_ref(&word), 8, true);
+        let texts: Vec<&str> = lines.iter().map(|l| l[0].text.as_str()).collect();
+        assert_eq!(texts, ["Superca-", "lifragi-", "listic"]);
+        assert_eq!(lines[0][0].grapheme_count(), 7);
+    }
+}
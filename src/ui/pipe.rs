@@ -5,36 +5,232 @@
 //! - Uses progressive timing to print lines even between MPRIS updates
 //! - Handles track transitions cleanly
 //! - Outputs plain text suitable for pipes and redirects
+//! - Optionally marks track changes and pause/resume explicitly (`--markers`)
+//! - Optionally prefixes lines with timestamps (`--timestamps`)
+//! - Optionally prints the upcoming line too (`--with-next`) or JSON objects (`--json`)
+//! - Optionally rate-limits printed lines (`--min-interval`), printing only the latest
+//! - Optionally names the lyrics provider (`--attribution`) per line or per track
+//! - Optionally prints a heartbeat line at a fixed interval (`--heartbeat`)
+//!   while the event loop is responsive
 
 use crate::pool;
 use tokio::sync::mpsc;
 use std::pin::Pin;
 use tokio::time::Sleep;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use crate::ui::estimate_update_and_next_sleep;
+use crate::ui::progression::Easing;
+
+/// Heartbeat periods without any update after which the event loop is
+/// considered hung and heartbeats stop.
+const HEARTBEAT_STALE_PERIODS: u32 = 2;
+
+/// Style of the explicit transition markers printed with `--markers`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MarkerStyle {
+    /// `--- Artist - Title ---`, `--- paused ---`, `--- playing ---`
+    Text,
+    /// One JSON object per event: `{"event":"track","artist":…,"title":…,"album":…}`
+    Json,
+}
+
+/// Timestamp prefix printed before each line with `--timestamps`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampStyle {
+    /// The line's own LRC time: `[01:23.45]`
+    Lyric,
+    /// Local wall-clock time when the line was printed: `[14:03:27.12]`
+    Wall,
+}
+
+/// Where `--attribution` names the provider of the lyrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Attribution {
+    /// Prefix every line: `[lrclib] text` (a `"provider"` field with `--json`)
+    Line,
+    /// Once per track, when its lyrics arrive: `--- lyrics: lrclib ---`
+    Track,
+}
+
+/// Pipe-mode output options taken from the CLI.
+#[derive(Debug, Clone)]
+struct PipeOptions {
+    markers: Option<MarkerStyle>,
+    timestamps: Option<TimestampStyle>,
+    /// Print the upcoming line after the active one
+    with_next: bool,
+    /// Separator between active and upcoming line in text output
+    next_separator: String,
+    /// Print each line as a JSON object instead of plain text
+    json: bool,
+    /// Minimum time between printed lines
+    min_interval: Option<Duration>,
+    attribution: Option<Attribution>,
+    /// Interval of heartbeat lines
+    heartbeat: Option<Duration>,
+}
+
+impl PipeOptions {
+    fn from_config(config: &crate::Config) -> Self {
+        Self {
+            markers: config.markers,
+            timestamps: config.timestamps,
+            with_next: config.with_next,
+            next_separator: config.next_separator.clone(),
+            json: config.json,
+            min_interval: config.min_interval.map(Duration::from_millis),
+            attribution: config.attribution,
+            heartbeat: config.heartbeat.filter(|&secs| secs > 0).map(Duration::from_secs),
+        }
+    }
+
+    /// Formats the line at `idx` for output.
+    ///
+    /// Text output is the line (with the configured timestamp prefix),
+    /// followed by the separator and the upcoming line with `--with-next`.
+    /// JSON output is `{"text", "time", "progress", "next_line_in_ms"}`, plus
+    /// `"word"` (active word index) for word-synced lyrics and `"next"` with
+    /// `--with-next`. `next_line_in_ms` is `null` on the last line.
+    /// `--attribution line` prefixes `[provider] ` or adds `"provider"`.
+    fn format_output(&self, upd: &crate::state::Update, idx: usize) -> Option<String> {
+        let lines = &upd.lines;
+        let line = lines.get(idx)?;
+        let next = if self.with_next { lines.get(idx + 1) } else { None };
+        let provider = upd
+            .provider
+            .filter(|_| self.attribution == Some(Attribution::Line))
+            .map(crate::state::Provider::name);
+
+        if self.json {
+            let progress = crate::ui::progression::karaoke_progress(lines, idx, upd.position);
+            let mut obj = serde_json::json!({
+                "text": line.text,
+                "time": line.time,
+                "progress": (progress * 1000.0).round() / 1000.0,
+                "next_line_in_ms": crate::ui::progression::next_line_in_ms(upd),
+            });
+            if line.words.is_some() {
+                obj["word"] = crate::ui::progression::active_word_index(line, upd.position).into();
+            }
+            if self.with_next {
+                obj["next"] = next.map_or(serde_json::Value::Null, |n| n.text.clone().into());
+            }
+            if let Some(provider) = provider {
+                obj["provider"] = provider.into();
+            }
+            return Some(obj.to_string());
+        }
+
+        let mut out = match provider {
+            Some(provider) => format!("[{}] {}", provider, self.format_line(line)),
+            None => self.format_line(line),
+        };
+        if let Some(next) = next {
+            out.push_str(&self.next_separator);
+            out.push_str(&next.text);
+        }
+        Some(out)
+    }
+
+    /// Formats a lyric line for output, with the configured timestamp prefix.
+    fn format_line(&self, line: &crate::lyrics::LyricLine) -> String {
+        match self.timestamps {
+            None => line.text.clone(),
+            Some(TimestampStyle::Lyric) => {
+                format!("[{}]{}", crate::lyrics::export::format_lrc_time(line.time), line.text)
+            }
+            Some(TimestampStyle::Wall) => {
+                format!("[{}]{}", chrono::Local::now().format("%H:%M:%S%.2f"), line.text)
+            }
+        }
+    }
+}
 
 /// State tracker for pipe mode output.
 struct PipeState {
-    /// Current track identifier (artist, title, album)
-    last_track_id: Option<(String, String, String)>,
+    /// Output options
+    options: PipeOptions,
+    /// Whether a track was already printed (for spacing)
+    seen_track: bool,
     /// Whether the last track had lyrics (for spacing)
     last_track_had_lyric: bool,
     /// Last printed line index
     last_line_idx: Option<usize>,
+    /// Last printed active word index (JSON output of word-synced lyrics)
+    last_word_idx: Option<usize>,
     /// Last received update for position estimation
     last_update: Option<crate::state::Update>,
     /// Time when last update was received
     last_update_instant: Option<Instant>,
     /// Scheduled timer for next line/word boundary
     next_sleep: Option<Pin<Box<Sleep>>>,
+    /// Rate limiter for printed lines (`--min-interval`)
+    throttle: Throttle,
+}
+
+/// Coalesces printed lines to at most one per interval.
+///
+/// A line arriving too soon after the previous one is held back; newer
+/// lines replace it, so the consumer only ever sees the latest state.
+#[derive(Default)]
+struct Throttle {
+    interval: Option<Duration>,
+    last_print: Option<Instant>,
+    pending: Option<String>,
+    /// Wakeup for printing the held-back line
+    flush_sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl Throttle {
+    fn new(interval: Option<Duration>) -> Self {
+        Self { interval, ..Default::default() }
+    }
+
+    /// Returns `out` if it may be printed now, otherwise holds it back.
+    fn admit(&mut self, out: String, now: Instant) -> Option<String> {
+        if let (Some(interval), Some(last)) = (self.interval, self.last_print)
+            && now < last + interval
+        {
+            self.pending = Some(out);
+            if self.flush_sleep.is_none() {
+                self.flush_sleep = Some(Box::pin(tokio::time::sleep_until((last + interval).into())));
+            }
+            return None;
+        }
+        // A held-back line is older than this one; printing it later would
+        // put the output out of order
+        self.discard();
+        self.last_print = Some(now);
+        Some(out)
+    }
+
+    /// Takes the held-back line once its interval has passed.
+    fn flush(&mut self, now: Instant) -> Option<String> {
+        self.flush_sleep = None;
+        let out = self.pending.take()?;
+        self.last_print = Some(now);
+        Some(out)
+    }
+
+    /// Drops a held-back line that no longer applies (track change).
+    fn discard(&mut self) {
+        self.pending = None;
+        self.flush_sleep = None;
+    }
 }
 
 impl PipeState {
-    fn new() -> Self {
+    fn new(options: PipeOptions) -> Self {
         Self {
-            last_track_id: None,
+            throttle: Throttle::new(options.min_interval),
+            options,
+            seen_track: false,
             last_track_had_lyric: false,
             last_line_idx: None,
+            last_word_idx: None,
             last_update: None,
             last_update_instant: None,
             next_sleep: None,
@@ -43,17 +239,30 @@ impl PipeState {
 
     /// Update state with a new update from MPRIS.
     fn update_from_mpris(&mut self, upd: crate::state::Update) {
-        let track_id = crate::ui::track_id(&upd);
         let has_lyrics = !upd.lines.is_empty();
-        let track_changed = self.last_track_id.as_ref() != Some(&track_id);
 
-        if track_changed {
+        if upd.delta.track_changed {
             self.handle_track_change();
-            self.last_track_id = Some(track_id);
+            self.seen_track = true;
+            self.print_track_marker(&upd);
 
             // Don't print first line immediately - wait for it to become active
-        } else if has_lyrics && upd.index != self.last_line_idx {
-            self.print_current_line(&upd);
+        }
+        if upd.delta.lyrics_replaced && has_lyrics {
+            self.print_attribution(&upd);
+        }
+        if !upd.delta.track_changed {
+            if upd.delta.playing_changed {
+                self.print_playback_marker(upd.playing);
+            }
+            // On a rewind, forget the word highlight so it is re-emitted from the new position
+            let rewound = self.is_rewind(&upd);
+            if rewound {
+                self.last_word_idx = None;
+            }
+            if has_lyrics && (upd.index != self.last_line_idx || (rewound && self.options.json)) {
+                self.print_current_line(&upd);
+            }
         }
 
         // Store update for local position estimation
@@ -65,31 +274,113 @@ impl PipeState {
             &self.last_update,
             self.last_update_instant,
             true,
+            Easing::Linear,
         );
         self.next_sleep = next;
     }
 
+    /// Whether `upd` moves the position clearly behind the local estimate.
+    fn is_rewind(&self, upd: &crate::state::Update) -> bool {
+        let (estimated, _) = estimate_update_and_next_sleep(&self.last_update, self.last_update_instant, true, Easing::Linear);
+        estimated.is_some_and(|e| upd.position < e.position - crate::state::BACKWARD_JUMP_THRESHOLD)
+    }
+
     /// Handle track change transition.
     fn handle_track_change(&mut self) {
-        // Always print empty line for visual separation between tracks
-        if self.last_track_id.is_some() {
+        // Print empty line for visual separation between tracks (markers replace it)
+        if self.seen_track && self.options.markers.is_none() {
             println!();
         }
         
         // Explicitly clear old update to free memory
         self.last_update = None;
         self.last_line_idx = None;
+        self.last_word_idx = None;
         self.last_track_had_lyric = false;
+        self.throttle.discard();
+    }
+
+    /// Print a track-change marker (if enabled).
+    fn print_track_marker(&self, upd: &crate::state::Update) {
+        let Some(style) = self.options.markers else {
+            return;
+        };
+
+        match (style, upd.title.is_empty()) {
+            (MarkerStyle::Text, true) => println!("--- stopped ---"),
+            (MarkerStyle::Text, false) if upd.artist.is_empty() => println!("--- {} ---", upd.title),
+            (MarkerStyle::Text, false) => println!("--- {} - {} ---", upd.artist, upd.title),
+            (MarkerStyle::Json, true) => println!("{}", serde_json::json!({ "event": "stopped" })),
+            (MarkerStyle::Json, false) => println!(
+                "{}",
+                serde_json::json!({
+                    "event": "track",
+                    "artist": upd.artist,
+                    "title": upd.title,
+                    "album": upd.album,
+                })
+            ),
+        }
+    }
+
+    /// Print the provider of newly loaded lyrics (with `--attribution track`).
+    fn print_attribution(&self, upd: &crate::state::Update) {
+        let Some(provider) = upd.provider.filter(|_| self.options.attribution == Some(Attribution::Track)) else {
+            return;
+        };
+        if self.options.json || self.options.markers == Some(MarkerStyle::Json) {
+            println!("{}", serde_json::json!({ "event": "provider", "provider": provider.name() }));
+        } else {
+            println!("--- lyrics: {} ---", provider.name());
+        }
+    }
+
+    /// Print a pause/resume marker (if enabled).
+    fn print_playback_marker(&self, playing: bool) {
+        match self.options.markers {
+            Some(MarkerStyle::Text) => println!("--- {} ---", if playing { "playing" } else { "paused" }),
+            Some(MarkerStyle::Json) => println!(
+                "{}",
+                serde_json::json!({ "event": if playing { "play" } else { "pause" } })
+            ),
+            None => {}
+        }
+    }
+
+    /// Whether the event loop sent anything within the last heartbeat
+    /// periods, so a heartbeat would be truthful (it re-sends its state
+    /// every period). Before the first update, counts from `started`.
+    fn event_loop_alive(&self, started: Instant, now: Instant, period: Duration) -> bool {
+        let last = self.last_update_instant.unwrap_or(started);
+        now.saturating_duration_since(last) < period * HEARTBEAT_STALE_PERIODS
+    }
+
+    /// Print a heartbeat: an empty line, or a marker in the `--markers`
+    /// style (JSON with `--json`).
+    fn print_heartbeat(&self) {
+        if self.options.json || self.options.markers == Some(MarkerStyle::Json) {
+            println!("{}", serde_json::json!({ "event": "heartbeat" }));
+        } else if self.options.markers == Some(MarkerStyle::Text) {
+            println!("--- heartbeat ---");
+        } else {
+            println!();
+        }
     }
 
     /// Print the current line from an update.
     fn print_current_line(&mut self, upd: &crate::state::Update) {
         if let Some(idx) = upd.index {
-            if let Some(line) = upd.lines.get(idx) {
-                println!("{}", line.text);
+            if let Some(out) = self.options.format_output(upd, idx) {
+                if let Some(out) = self.throttle.admit(out, Instant::now()) {
+                    println!("{}", out);
+                }
                 self.last_track_had_lyric = true;
             }
             self.last_line_idx = Some(idx);
+            self.last_word_idx = upd
+                .lines
+                .get(idx)
+                .and_then(|line| crate::ui::progression::active_word_index(line, upd.position));
         }
     }
 
@@ -99,39 +390,103 @@ impl PipeState {
             &self.last_update,
             self.last_update_instant,
             true,
+            Easing::Linear,
         );
 
         if let Some(estimated) = maybe_estimated {
             // Print if line index has advanced
             if estimated.index != self.last_line_idx {
-                if let Some(idx) = estimated.index
-                    && let Some(line) = estimated.lines.get(idx) {
-                        println!("{}", line.text);
-                        self.last_track_had_lyric = true;
-                    }
+                // Keep the real update as anchor: the estimate is clamped to the
+                // line after it until the event loop confirms the position
+                self.print_current_line(&estimated);
                 self.last_line_idx = estimated.index;
-
-                // Update stored update to the estimated one
-                self.last_update = Some(estimated);
-                self.last_update_instant = Some(Instant::now());
+            } else if self.options.json
+                && let Some(idx) = estimated.index
+                && let Some(line) = estimated.lines.get(idx)
+            {
+                // JSON consumers animate karaoke themselves: re-emit on each new word
+                let word = crate::ui::progression::active_word_index(line, estimated.position);
+                if word.is_some() && word != self.last_word_idx {
+                    self.print_current_line(&estimated);
+                }
             }
         }
 
         self.next_sleep = next;
     }
+
+    /// Print the line held back by `--min-interval`, if still pending.
+    fn handle_flush_wakeup(&mut self) {
+        if let Some(out) = self.throttle.flush(Instant::now()) {
+            println!("{}", out);
+        }
+    }
+}
+
+/// Output format for `--dump`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DumpFormat {
+    /// Synced LRC (`[mm:ss.cc]text`)
+    Lrc,
+    /// Plain text, one line per lyric line
+    Plain,
+    /// ASS subtitles with `\k` karaoke tags from word timings
+    Ass,
+}
+
+/// Fetch lyrics for the playing track, print them in full and return.
+///
+/// Fails if no player is active or no lyrics were found.
+pub async fn dump_lyrics(
+    config: crate::Config,
+    format: DumpFormat,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let no_player = || crate::exit::Failure::NoPlayer(crate::i18n::tr(crate::i18n::Msg::NoPlayer).to_string());
+    let state = pool::fetch_current(config).await.ok_or_else(no_player)?;
+    let lines = &state.lyric_state.lines;
+
+    if lines.is_empty() {
+        let reason = state
+            .player_state
+            .err
+            .as_ref()
+            .map_or_else(|| crate::i18n::tr(crate::i18n::Msg::NoLyrics).to_string(), crate::i18n::lyrics_error);
+        let message = format!("{} - {}: {}", state.player_state.artist, state.player_state.title, reason);
+        return Err(crate::exit::Failure::NoLyrics(message).into());
+    }
+
+    let text = match format {
+        DumpFormat::Lrc => crate::lyrics::export::to_lrc(lines),
+        DumpFormat::Plain => crate::lyrics::export::to_plain(lines),
+        DumpFormat::Ass => {
+            let player = &state.player_state;
+            let title = if player.artist.is_empty() {
+                player.title.clone()
+            } else {
+                format!("{} - {}", player.artist, player.title)
+            };
+            crate::lyrics::export::to_ass(lines, &title)
+        }
+    };
+    print!("{}", text);
+    Ok(())
 }
 
 /// Display lyrics in pipe mode (stdout only, for scripting).
 pub async fn display_lyrics_pipe(
-    _meta: crate::mpris::TrackMetadata,
-    _pos: f64,
     mpris_config: crate::Config,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let (tx, mut rx) = mpsc::channel(32);
     let (_shutdown_tx, shutdown_rx) = mpsc::channel(1);
-    tokio::spawn(pool::listen(tx, shutdown_rx, mpris_config.clone()));
+    tokio::spawn(pool::listen(tx, shutdown_rx, None, mpris_config.clone()));
 
-    let mut state = PipeState::new();
+    let mut state = PipeState::new(PipeOptions::from_config(&mpris_config));
+    let started = Instant::now();
+    let mut heartbeat = state
+        .options
+        .heartbeat
+        .map(|period| tokio::time::interval_at(tokio::time::Instant::now() + period, period));
 
     loop {
         tokio::select! {
@@ -153,8 +508,105 @@ pub async fn display_lyrics_pipe(
             } => {
                 state.handle_timer_wakeup();
             }
+
+            // Held-back line of a rate-limited burst
+            _ = async {
+                if let Some(s) = &mut state.throttle.flush_sleep {
+                    s.as_mut().await;
+                } else {
+                    futures_util::future::pending::<()>().await;
+                }
+            } => {
+                state.handle_flush_wakeup();
+            }
+
+            // Proof of life for supervising scripts (`--heartbeat`)
+            _ = async {
+                if let Some(interval) = &mut heartbeat {
+                    interval.tick().await;
+                } else {
+                    futures_util::future::pending::<()>().await;
+                }
+            } => {
+                let period = state.options.heartbeat.unwrap_or_default();
+                if state.event_loop_alive(started, Instant::now(), period) {
+                    state.print_heartbeat();
+                } else {
+                    tracing::warn!("Event loop unresponsive, withholding heartbeat");
+                }
+            }
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heartbeat_follows_event_loop() {
+        let mut state = PipeState::new(PipeOptions::from_config(&crate::Config::default()));
+        let period = Duration::from_secs(30);
+        let started = Instant::now();
+
+        assert!(state.event_loop_alive(started, started + period, period));
+        assert!(!state.event_loop_alive(started, started + period * 3, period));
+
+        state.last_update_instant = Some(started + period * 2);
+        assert!(state.event_loop_alive(started, started + period * 3, period));
+    }
+
+    #[tokio::test]
+    async fn test_throttle_coalesces_to_latest() {
+        let mut throttle = Throttle::new(Some(Duration::from_millis(500)));
+        let start = Instant::now();
+
+        assert_eq!(throttle.admit("a".into(), start).as_deref(), Some("a"));
+        assert_eq!(throttle.admit("b".into(), start + Duration::from_millis(100)), None);
+        assert_eq!(throttle.admit("c".into(), start + Duration::from_millis(200)), None);
+        assert!(throttle.flush_sleep.is_some());
+        assert_eq!(throttle.flush(start + Duration::from_millis(500)).as_deref(), Some("c"));
+        assert_eq!(throttle.flush(start + Duration::from_millis(600)), None);
+
+        // Without an interval everything passes through
+        let mut unlimited = Throttle::new(None);
+        assert!(unlimited.admit("a".into(), start).is_some());
+        assert!(unlimited.admit("b".into(), start).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_throttle_pass_through_drops_held_line() {
+        let mut throttle = Throttle::new(Some(Duration::from_millis(500)));
+        let start = Instant::now();
+
+        assert!(throttle.admit("a".into(), start).is_some());
+        assert_eq!(throttle.admit("b".into(), start + Duration::from_millis(100)), None);
+        // The interval is over before the flush timer fired: "c" goes out at
+        // once and "b" must not follow it
+        let late = start + Duration::from_millis(600);
+        assert_eq!(throttle.admit("c".into(), late).as_deref(), Some("c"));
+        assert!(throttle.flush_sleep.is_none());
+        assert_eq!(throttle.flush(late + Duration::from_millis(1)), None);
+    }
+
+    #[test]
+    fn test_line_attribution() {
+        let config = crate::Config { attribution: Some(Attribution::Line), ..Default::default() };
+        let mut options = PipeOptions::from_config(&config);
+        let upd = crate::state::Update {
+            lines: std::sync::Arc::new(vec![crate::lyrics::LyricLine { text: "Hello".into(), ..Default::default() }]),
+            provider: Some(crate::state::Provider::LRCLIB),
+            ..Default::default()
+        };
+        assert_eq!(options.format_output(&upd, 0).as_deref(), Some("[lrclib] Hello"));
+
+        options.json = true;
+        let json: serde_json::Value = serde_json::from_str(&options.format_output(&upd, 0).unwrap()).unwrap();
+        assert_eq!(json["provider"], "lrclib");
+
+        options.attribution = Some(Attribution::Track);
+        assert!(!options.format_output(&upd, 0).unwrap().contains("provider"));
+    }
+}
@@ -4,18 +4,64 @@
 //! - Local position estimation based on elapsed time since last MPRIS update
 //! - Per-word and per-grapheme boundary scheduling for richsync karaoke
 //! - Line-level scheduling for standard synchronized lyrics
+//! - Karaoke progress values for machine-readable output
+//!
+//! Extrapolation never runs more than [`EXTRAPOLATION_TOLERANCE`] past the
+//! start of the line following the last real update. The event loop re-reads
+//! the player position at each line boundary, so normal playback proceeds
+//! without a visible stall, while lagging events can't highlight lines ahead.
 
 use crate::state::Update;
 use std::pin::Pin;
 use tokio::time::Sleep;
 use std::time::{Duration, Instant};
 
+/// How the karaoke fill advances through a word (`--karaoke-easing`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Easing {
+    /// Constant speed over the word's duration
+    #[default]
+    Linear,
+    /// Slow start and finish (cubic), so long held notes don't crawl robotically
+    EaseInOut,
+}
+
+impl Easing {
+    /// Filled fraction of a word `t` (0..=1) of the way through it.
+    #[must_use]
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseInOut if t < 0.5 => 4.0 * t * t * t,
+            Self::EaseInOut => 1.0 - (2.0 - 2.0 * t).powi(3) / 2.0,
+        }
+    }
+
+    /// Inverse of [`apply`](Self::apply): how far through a word the fill reaches `fill`.
+    #[must_use]
+    pub fn inverse(self, fill: f64) -> f64 {
+        let fill = fill.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => fill,
+            Self::EaseInOut if fill < 0.5 => (fill / 4.0).cbrt(),
+            Self::EaseInOut => 1.0 - (2.0 * (1.0 - fill)).cbrt() / 2.0,
+        }
+    }
+}
+
+/// How far (in seconds) the estimate may run past the next line's start
+/// before a real position update confirms the transition.
+pub const EXTRAPOLATION_TOLERANCE: f64 = 0.3;
+
 /// Compute the next tokio Sleep based on lyrics timing.
 ///
 /// For richsync lyrics, schedules wakeups at word/grapheme boundaries.
 /// For standard lyrics, schedules wakeups at line transitions.
 /// Returns `None` when playback is paused or no future boundary exists.
-pub fn compute_next_word_sleep_from_update(upd: &Update) -> Option<Pin<Box<Sleep>>> {
+/// Grapheme boundaries follow `easing`, like the rendered fill.
+pub fn compute_next_word_sleep_from_update(upd: &Update, easing: Easing) -> Option<Pin<Box<Sleep>>> {
     if !upd.playing {
         return None;
     }
@@ -25,10 +71,10 @@ pub fn compute_next_word_sleep_from_update(upd: &Update) -> Option<Pin<Box<Sleep
         return schedule_first_line_start(upd);
     }
 
-    let is_richsync = matches!(upd.provider, Some(crate::state::Provider::MusixmatchRichsync));
+    let is_richsync = upd.provider.is_some_and(crate::state::Provider::is_word_synced);
     
     if is_richsync {
-        schedule_next_richsync_boundary(upd)
+        schedule_next_richsync_boundary(upd, easing)
     } else {
         schedule_next_line_start(upd)
     }
@@ -48,50 +94,66 @@ fn schedule_first_line_start(upd: &Update) -> Option<Pin<Box<Sleep>>> {
 
 /// Schedule a wakeup at the next line start (non-richsync).
 fn schedule_next_line_start(upd: &Update) -> Option<Pin<Box<Sleep>>> {
-    let current_idx = upd.index?;
-    
+    upd.index?;
+    next_line_in(upd).map(create_sleep)
+}
+
+/// Seconds from `upd.position` until the next line starts (the first line
+/// before any is active), or `None` when no later line has a start time.
+pub fn next_line_in(upd: &Update) -> Option<f64> {
     // Search from current line onward for next line after current position
-    for line in upd.lines.iter().skip(current_idx) {
-        if line.time.is_finite() && line.time > upd.position {
-            let delay = (line.time - upd.position).max(0.0);
-            return Some(create_sleep(delay));
-        }
-    }
+    upd.lines
+        .iter()
+        .skip(upd.index.unwrap_or(0))
+        .map(|line| line.time)
+        .find(|&time| time.is_finite() && time > upd.position)
+        .map(|time| time - upd.position)
+}
 
-    None
+/// [`next_line_in`] in whole milliseconds, for JSON output.
+pub fn next_line_in_ms(upd: &Update) -> Option<u64> {
+    next_line_in(upd).map(|secs| (secs * 1000.0).round() as u64)
 }
 
+/// Shortest wakeup interval (in seconds) for richsync boundaries; caps
+/// grapheme-level redraws at 60 per second.
+const MIN_WAKEUP_INTERVAL: f64 = 1.0 / 60.0;
+
 /// Schedule a wakeup at the next word/grapheme boundary (richsync).
-fn schedule_next_richsync_boundary(upd: &Update) -> Option<Pin<Box<Sleep>>> {
+fn schedule_next_richsync_boundary(upd: &Update, easing: Easing) -> Option<Pin<Box<Sleep>>> {
+    next_richsync_delay(upd, easing).map(|delay| create_sleep(delay.max(MIN_WAKEUP_INTERVAL)))
+}
+
+/// Delay until the next word/grapheme boundary of the active line, or the
+/// start of the following line when the active line has none left.
+///
+/// Later lines are not scanned: once the next line starts, the wakeup
+/// there schedules its boundaries.
+fn next_richsync_delay(upd: &Update, easing: Easing) -> Option<f64> {
     let current_idx = upd.index?;
     let mut best_delay: Option<f64> = None;
 
-    // Scan from current line forward for the nearest future boundary
-    for line in upd.lines.iter().skip(current_idx) {
-        let Some(words) = &line.words else {
-            continue;
-        };
-
+    if let Some(words) = upd.lines.get(current_idx).and_then(|line| line.words.as_ref()) {
         for word in words {
             update_best_delay(&mut best_delay, word.start, upd.position);
             update_best_delay(&mut best_delay, word.end, upd.position);
 
             // Schedule grapheme boundaries for smooth per-character animation
             if word.grapheme_count() > 1 {
-                for grapheme_boundary in compute_grapheme_boundaries(word) {
+                for grapheme_boundary in compute_grapheme_boundaries(word, easing) {
                     update_best_delay(&mut best_delay, grapheme_boundary, upd.position);
                 }
             }
         }
+    }
 
-        // Early exit if we found a very near boundary
-        if let Some(d) = best_delay
-            && d <= 0.01 {
-                break;
-            }
+    if let Some(next) = upd.lines.get(current_idx + 1)
+        && next.time.is_finite()
+    {
+        update_best_delay(&mut best_delay, next.time, upd.position);
     }
 
-    best_delay.map(create_sleep)
+    best_delay
 }
 
 /// Update best_delay if boundary is in the future and closer than current best.
@@ -108,12 +170,12 @@ fn update_best_delay(best: &mut Option<f64>, boundary: f64, position: f64) {
 }
 
 /// Compute grapheme boundaries for a word with per-word timing.
-fn compute_grapheme_boundaries(word: &crate::lyrics::types::WordTiming) -> Vec<f64> {
+fn compute_grapheme_boundaries(word: &crate::lyrics::types::WordTiming, easing: Easing) -> Vec<f64> {
     let total = word.grapheme_count();
     let duration = (word.end - word.start).max(f64::EPSILON);
     
     (1..total)
-        .map(|k| word.start + (k as f64 / total as f64) * duration)
+        .map(|k| word.start + easing.inverse(k as f64 / total as f64) * duration)
         .collect()
 }
 
@@ -127,15 +189,18 @@ fn create_sleep(delay_secs: f64) -> Pin<Box<Sleep>> {
 /// Estimate current position and line index based on elapsed time.
 ///
 /// This function:
-/// 1. Advances position based on time elapsed since last MPRIS update
+/// 1. Advances position based on time elapsed since last MPRIS update,
+///    clamped to the next line boundary (see [`extrapolation_limit`])
 /// 2. Recomputes the current line index via binary search
 /// 3. Schedules the next timer wakeup for smooth rendering
 ///
 /// The `_karaoke_enabled` parameter is unused here (affects rendering only).
+/// `easing` is the karaoke fill curve, which places grapheme wakeups.
 pub fn estimate_update_and_next_sleep(
     last_update: &Option<Update>,
     last_update_instant: Option<Instant>,
     _karaoke_enabled: bool,
+    easing: Easing,
 ) -> (Option<Update>, Option<Pin<Box<Sleep>>>) {
     let Some(update) = last_update else {
         return (None, None);
@@ -147,17 +212,33 @@ pub fn estimate_update_and_next_sleep(
     if estimated.playing
         && let Some(since) = last_update_instant {
             estimated.position += since.elapsed().as_secs_f64();
+            if let Some(limit) = extrapolation_limit(update) {
+                estimated.position = estimated.position.min(limit);
+            }
         }
 
     // Recompute current line index from estimated position
     estimated.index = compute_line_index(&estimated);
 
     // Schedule next boundary for smooth rendering
-    let next_sleep = compute_next_word_sleep_from_update(&estimated);
+    let next_sleep = compute_next_word_sleep_from_update(&estimated, easing);
 
     (Some(estimated), next_sleep)
 }
 
+/// Latest position an estimate based on `update` may reach: the start of the
+/// first line after the update's position, plus [`EXTRAPOLATION_TOLERANCE`].
+///
+/// Returns `None` (no clamp) past the last line.
+fn extrapolation_limit(update: &Update) -> Option<f64> {
+    update
+        .lines
+        .iter()
+        .map(|line| line.time)
+        .find(|&time| time.is_finite() && time > update.position)
+        .map(|time| time + EXTRAPOLATION_TOLERANCE)
+}
+
 /// Compute the current line index from position using binary search.
 ///
 /// Returns `None` if:
@@ -193,3 +274,125 @@ fn compute_line_index(update: &Update) -> Option<usize> {
         Err(idx) => Some(idx - 1),  // Between lines
     }
 }
+
+/// Returns the index of the word active at `position` (the last word that
+/// has started), or `None` without word timing or before the first word.
+pub fn active_word_index(line: &crate::lyrics::LyricLine, position: f64) -> Option<usize> {
+    line.words.as_ref()?.iter().rposition(|w| w.start <= position)
+}
+
+/// Progress (0.0–1.0) within the active word of line `idx`.
+///
+/// Lines without word timing report progress through the whole line,
+/// measured up to the start of the next line.
+pub fn karaoke_progress(lines: &[crate::lyrics::LyricLine], idx: usize, position: f64) -> f64 {
+    let Some(line) = lines.get(idx) else {
+        return 0.0;
+    };
+
+    if let Some(words) = &line.words {
+        return active_word_index(line, position)
+            .and_then(|i| words.get(i))
+            .map_or(0.0, |w| fraction(position, w.start, w.end));
+    }
+
+    lines
+        .get(idx + 1)
+        .map_or(0.0, |next| fraction(position, line.time, next.time))
+}
+
+/// Fraction of `start..end` covered at `position`, clamped to 0.0–1.0.
+fn fraction(position: f64, start: f64, end: f64) -> f64 {
+    if end <= start {
+        return if position >= start { 1.0 } else { 0.0 };
+    }
+    ((position - start) / (end - start)).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lyrics::LyricLine;
+
+    #[test]
+    fn test_karaoke_progress() {
+        let mut lines = crate::lyrics::parse::parse_synced_lyrics("[00:10.00]First\n[00:20.00]Second\n");
+        assert_eq!(karaoke_progress(&lines, 0, 15.0), 0.5);
+        assert_eq!(karaoke_progress(&lines, 1, 25.0), 0.0); // last line has no end
+
+        lines[0] = crate::lyrics::parse::parse_yrc("[10000,4000](10000,2000,0)Two (12000,2000,0)words")
+            .unwrap()
+            .remove(0);
+        let line: &LyricLine = &lines[0];
+        assert_eq!(active_word_index(line, 9.0), None);
+        assert_eq!(active_word_index(line, 12.5), Some(1));
+        assert_eq!(karaoke_progress(&lines, 0, 12.5), 0.25);
+        assert_eq!(karaoke_progress(&lines, 0, 9.0), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_extrapolation_clamped_to_next_line() {
+        let update = Update {
+            lines: std::sync::Arc::new(crate::lyrics::parse::parse_synced_lyrics(
+                "[00:10.00]A\n[00:12.00]B\n[00:14.00]C\n",
+            )),
+            position: 11.0,
+            playing: true,
+            ..Default::default()
+        };
+        assert_eq!(extrapolation_limit(&update), Some(12.0 + EXTRAPOLATION_TOLERANCE));
+
+        // Events lagging by 5 s: the estimate stops in line B, never reaching C
+        let since = Instant::now() - Duration::from_secs(5);
+        let (estimated, _) = estimate_update_and_next_sleep(&Some(update.clone()), Some(since), true, Easing::Linear);
+        let estimated = estimated.unwrap();
+        assert_eq!(estimated.position, 12.0 + EXTRAPOLATION_TOLERANCE);
+        assert_eq!(estimated.index, Some(1));
+
+        let past_end = Update { position: 15.0, ..update };
+        assert_eq!(extrapolation_limit(&past_end), None);
+    }
+
+    #[test]
+    fn test_richsync_scheduling_stays_on_active_line() {
+        let lines = crate::lyrics::parse::parse_yrc(
+            "[10000,4000](10000,2000,0)Two (12000,2000,0)words\n[30000,2000](30000,2000,0)Later",
+        )
+        .unwrap();
+        let update = Update {
+            lines: std::sync::Arc::new(lines),
+            index: Some(0),
+            position: 13.9,
+            playing: true,
+            ..Default::default()
+        };
+        let delay = next_richsync_delay(&update, Easing::Linear).unwrap();
+        assert!((delay - 0.1).abs() < 1e-9);
+
+        // Past the last word: wake at the next line, not inside it
+        let done = Update { position: 14.5, ..update };
+        assert!((next_richsync_delay(&done, Easing::Linear).unwrap() - 15.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_easing_curves() {
+        for easing in [Easing::Linear, Easing::EaseInOut] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+            for t in [0.1, 0.3, 0.5, 0.8] {
+                assert!((easing.inverse(easing.apply(t)) - t).abs() < 1e-9);
+            }
+        }
+        // Slow start and finish, same midpoint
+        assert!(Easing::EaseInOut.apply(0.2) < 0.2);
+        assert!(Easing::EaseInOut.apply(0.8) > 0.8);
+        assert!((Easing::EaseInOut.apply(0.5) - 0.5).abs() < 1e-9);
+
+        // Grapheme wakeups follow the curve: the first of four graphemes takes longer
+        let word = crate::lyrics::types::WordTiming::new(0.0, 4.0, "abcd");
+        let linear = compute_grapheme_boundaries(&word, Easing::Linear);
+        let eased = compute_grapheme_boundaries(&word, Easing::EaseInOut);
+        assert_eq!(linear, [1.0, 2.0, 3.0]);
+        assert!(eased[0] > 1.0 && (eased[1] - 2.0).abs() < 1e-9 && eased[2] < 3.0);
+    }
+}
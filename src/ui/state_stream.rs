@@ -0,0 +1,157 @@
+//! Full state stream for external frontends (`--output state-jsonl`).
+//!
+//! One JSON object is printed per line. Whenever the track or its lyrics
+//! change, a `snapshot` carries everything a frontend needs to render on
+//! its own: metadata, every line with its timestamps (and word timings),
+//! plus the playback state. Every further update from the player is a small
+//! `delta` with the position and only the fields that changed (from the
+//! [`UpdateDelta`](crate::state::UpdateDelta) sent with each update):
+//!
+//! ```text
+//! {"event":"snapshot","artist":"…","title":"…","album":"…","provider":"lrclib","lines":[{"time":12.3,"text":"…"}],"index":null,"position":0.4,"next_line_in_ms":11900,"playing":true,"err":null,"err_detail":null}
+//! {"event":"delta","position":12.31,"next_line_in_ms":3990,"index":0}
+//! {"event":"delta","position":40.2,"next_line_in_ms":null,"playing":false}
+//! ```
+//!
+//! The position is a snapshot at the time of the event; while `playing`,
+//! frontends extrapolate it themselves. `next_line_in_ms` is the time from
+//! that position to the start of the next line (`null` after the last one),
+//! so visualizers can start animating the upcoming line ahead of time.
+//! `err` is a stable code (`network`, `api`, `parse`) with the untranslated
+//! `err_detail`.
+
+use crate::pool;
+use crate::state::Update;
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+
+/// Output format for `--output`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    /// Newline-delimited JSON: a snapshot per track, then deltas
+    StateJsonl,
+}
+
+/// Returns the event to print for `upd`, if any.
+fn event_for(upd: &Update) -> Option<Value> {
+    if upd.delta.lyrics_replaced {
+        Some(snapshot(upd))
+    } else {
+        delta(upd)
+    }
+}
+
+/// The complete state of the current track.
+fn snapshot(upd: &Update) -> Value {
+    let lines: Vec<Value> = upd
+        .lines
+        .iter()
+        .map(|line| {
+            let mut obj = json!({ "time": line.time, "text": line.text });
+            if let Some(translation) = &line.translation {
+                obj["translation"] = translation.clone().into();
+            }
+            if let Some(words) = &line.words {
+                obj["words"] = words
+                    .iter()
+                    .map(|w| json!({ "start": w.start, "end": w.end, "text": w.text }))
+                    .collect();
+            }
+            obj
+        })
+        .collect();
+
+    json!({
+        "event": "snapshot",
+        "artist": upd.artist,
+        "title": upd.title,
+        "album": upd.album,
+        "provider": upd.provider.map(crate::state::Provider::name),
+        "lines": lines,
+        "index": upd.index,
+        "position": round_position(upd.position),
+        "next_line_in_ms": crate::ui::progression::next_line_in_ms(upd),
+        "playing": upd.playing,
+        "err": upd.err.as_ref().map(|e| e.kind.code()),
+        "err_detail": upd.err.as_ref().map(|e| &e.detail),
+    })
+}
+
+/// Position plus the fields that changed; `None` if nothing did.
+fn delta(upd: &Update) -> Option<Value> {
+    let mut obj = json!({
+        "event": "delta",
+        "position": round_position(upd.position),
+        "next_line_in_ms": crate::ui::progression::next_line_in_ms(upd),
+    });
+    if upd.delta.index_changed {
+        obj["index"] = json!(upd.index);
+    }
+    if upd.delta.playing_changed {
+        obj["playing"] = upd.playing.into();
+    }
+    if upd.delta.err_changed {
+        obj["err"] = json!(upd.err.as_ref().map(|e| e.kind.code()));
+        obj["err_detail"] = json!(upd.err.as_ref().map(|e| &e.detail));
+    }
+    (!upd.delta.is_empty()).then_some(obj)
+}
+
+/// Positions are printed with millisecond precision.
+fn round_position(position: f64) -> f64 {
+    (position * 1000.0).round() / 1000.0
+}
+
+/// Stream the player state to stdout until the update channel closes.
+pub async fn display_state_stream(
+    config: crate::Config,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let OutputFormat::StateJsonl = format;
+    let (tx, mut rx) = mpsc::channel(32);
+    let (_shutdown_tx, shutdown_rx) = mpsc::channel(1);
+    tokio::spawn(pool::listen(tx, shutdown_rx, None, config));
+
+    while let Some(upd) = rx.recv().await {
+        if let Some(event) = event_for(&upd) {
+            println!("{}", event);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lyrics::LyricLine;
+    use crate::state::UpdateDelta;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_snapshot_then_deltas() {
+        let lines = Arc::new(vec![
+            LyricLine { time: 1.0, text: "one".into(), ..Default::default() },
+            LyricLine { time: 2.0, text: "two".into(), ..Default::default() },
+        ]);
+        let upd = Update { lines: Arc::clone(&lines), title: "Song".into(), playing: true, ..Default::default() };
+        // What the event loop does when sending updates
+        let mut last: Option<Update> = None;
+        let mut send = |mut upd: Update| {
+            upd.delta = UpdateDelta::between(last.as_ref(), &upd);
+            last = Some(upd.clone());
+            event_for(&upd)
+        };
+
+        let first = send(upd.clone()).unwrap();
+        assert_eq!(first["event"], "snapshot");
+        assert_eq!(first["lines"][1]["text"], "two");
+
+        let moved = send(Update { index: Some(0), position: 1.2, ..upd.clone() }).unwrap();
+        assert_eq!(moved, json!({ "event": "delta", "position": 1.2, "next_line_in_ms": 800, "index": 0 }));
+        assert!(send(Update { index: Some(0), position: 1.2, ..upd.clone() }).is_none());
+
+        let other = Update { title: "Other".into(), ..upd };
+        assert_eq!(send(other).unwrap()["event"], "snapshot");
+    }
+}
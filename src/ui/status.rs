@@ -0,0 +1,114 @@
+//! Status bar messages for warnings (TUI).
+//!
+//! Printing a warning would corrupt the TUI's alternate screen, and with
+//! logging off (the default) it wouldn't be seen at all. [`StatusLayer`]
+//! keeps the latest warning or error logged through `tracing` so the TUI can
+//! show it in its bottom row for [`STATUS_TIMEOUT`]. Results of key presses
+//! handled by the event loop are posted with [`show`].
+
+use once_cell::sync::Lazy;
+use std::fmt::Debug;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use tracing::field::{Field, Visit};
+use tracing_subscriber::layer::Context;
+
+/// How long a message stays in the status bar.
+pub const STATUS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A logged warning.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Status {
+    pub text: String,
+    pub at: Instant,
+}
+
+static LATEST: Lazy<watch::Sender<Option<Status>>> = Lazy::new(|| watch::channel(None).0);
+
+/// Receiver notified whenever a new warning is logged.
+pub fn subscribe() -> watch::Receiver<Option<Status>> {
+    LATEST.subscribe()
+}
+
+/// Puts `text` in the status bar.
+pub fn show(text: String) {
+    LATEST.send_replace(Some(Status { text, at: Instant::now() }));
+}
+
+/// The message to show at `now` and how long it stays up, if any.
+pub fn visible(now: Instant) -> Option<(String, Duration)> {
+    let latest = LATEST.borrow();
+    let status = latest.as_ref()?;
+    let remaining = STATUS_TIMEOUT.checked_sub(now.saturating_duration_since(status.at))?;
+    (!remaining.is_zero()).then(|| (status.text.clone(), remaining))
+}
+
+/// `tracing` layer recording WARN and ERROR events for the status bar.
+pub struct StatusLayer;
+
+impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for StatusLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        if *event.metadata().level() > tracing::Level::WARN {
+            return;
+        }
+        let mut message = Message::default();
+        event.record(&mut message);
+        show(message.text());
+    }
+}
+
+/// The `message` and `error` fields of an event.
+#[derive(Default)]
+struct Message {
+    message: String,
+    error: Option<String>,
+}
+
+impl Message {
+    fn text(self) -> String {
+        match self.error {
+            Some(error) => format!("{}: {}", self.message, error),
+            None => self.message,
+        }
+    }
+}
+
+impl Visit for Message {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        match field.name() {
+            "message" => self.message = format!("{:?}", value),
+            "error" => self.error = Some(format!("{:?}", value)),
+            _ => {}
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "message" => self.message = value.to_string(),
+            "error" => self.error = Some(value.to_string()),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_warnings_reach_the_status_bar() {
+        let subscriber = tracing_subscriber::registry().with(StatusLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("not shown");
+            assert!(visible(Instant::now()).is_none());
+            let error = std::io::Error::other("connection reset");
+            tracing::warn!(service = "spotify", error = %error, "Failed to handle metadata change");
+        });
+
+        let (text, remaining) = visible(Instant::now()).unwrap();
+        assert_eq!(text, "Failed to handle metadata change: connection reset");
+        assert!(remaining <= STATUS_TIMEOUT);
+        assert!(visible(Instant::now() + STATUS_TIMEOUT).is_none());
+    }
+}
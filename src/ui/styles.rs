@@ -4,6 +4,21 @@
 //! - **Before**: Lines that have already been sung (dimmed/italic)
 //! - **Current**: The currently active line (bold/green)
 //! - **After**: Upcoming lines (normal styling)
+//!
+//! While paused, the whole block is dimmed and a badge is shown.
+//!
+//! In duets (lines marked with a vocal part), past and upcoming lines take
+//! the color of their part from a palette (`[colors] parts`).
+//!
+//! With `--preview-next` the line after the current one always follows it
+//! in a distinct **preview** style, whatever the context settings.
+//!
+//! `--theme high-contrast` swaps in white-on-black styles without dim or
+//! italics, underlining the current line instead of coloring it.
+//!
+//! Styles are degraded to what the terminal can render ([`Capabilities`]):
+//! missing colors, italics or dim are replaced by safe alternatives.
+//! `--color never` (or a non-empty `NO_COLOR`) renders them monochrome.
 
 use ratatui::style::{Color, Modifier, Style};
 
@@ -16,6 +31,7 @@ use ratatui::style::{Color, Modifier, Style};
 /// // Use styles.before for past lines
 /// // Use styles.after for future lines
 /// ```
+#[derive(Clone)]
 pub struct LyricStyles {
     /// Style for lines that have already passed (dimmed, italic)
     pub before: Style,
@@ -23,6 +39,63 @@ pub struct LyricStyles {
     pub current: Style,
     /// Style for upcoming lines (normal text)
     pub after: Style,
+    /// Style for the "paused" badge
+    pub paused_badge: Style,
+    /// Style for the "scrolled" indicator
+    pub scrolled_badge: Style,
+    /// Style for warnings in the status bar
+    pub status: Style,
+    /// Style patched onto every line while paused
+    pub paused: Style,
+    /// Use ASCII instead of Unicode symbols in badges and borders
+    pub ascii: bool,
+    /// Style of the whole screen, painted before the lyrics
+    pub base: Style,
+    /// Karaoke fill curve within a word
+    pub easing: crate::ui::progression::Easing,
+    /// Style for the next line pinned under the current one
+    pub preview: Style,
+    /// Always show the next line as a preview (`--preview-next`)
+    pub preview_next: bool,
+    /// Omit past lines, pinning the current one to the top (`--hide-past`)
+    pub hide_past: bool,
+    /// Foreground of past/upcoming lines per vocal part, for duets
+    pub parts: Vec<Color>,
+}
+
+/// Built-in style themes (`--theme`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    /// Green current line, dimmed italic past lines
+    #[default]
+    Default,
+    /// Pure white on black, no dim, underlined current line (for low vision)
+    HighContrast,
+}
+
+/// When to use colors (`--color`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    /// Color terminals, unless `NO_COLOR` is set
+    #[default]
+    Auto,
+    /// Even if `NO_COLOR` is set or the output isn't a terminal
+    Always,
+    /// Never; bold, underline and the like still apply
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether to color output going to a `terminal` (or not).
+    pub fn enabled(self, terminal: bool) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => terminal && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()),
+        }
+    }
 }
 
 impl Default for LyricStyles {
@@ -37,9 +110,357 @@ impl Default for LyricStyles {
                 .add_modifier(Modifier::BOLD),
             // Future lines: normal styling
             after: Style::default(),
+            // Paused badge: visible but unobtrusive
+            paused_badge: Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            scrolled_badge: Style::default().fg(Color::Cyan),
+            status: Style::default().fg(Color::Yellow),
+            paused: Style::default().add_modifier(Modifier::DIM),
+            ascii: false,
+            base: Style::default(),
+            easing: Default::default(),
+            // Next-line preview: set apart from the other upcoming lines
+            preview: Style::default().fg(Color::LightBlue),
+            preview_next: false,
+            hide_past: false,
+            parts: vec![Color::LightCyan, Color::LightMagenta, Color::LightYellow],
         }
     }
 }
 
 impl LyricStyles {
+    /// Styles of a built-in theme.
+    pub fn for_theme(theme: Theme) -> Self {
+        match theme {
+            Theme::Default => Self::default(),
+            Theme::HighContrast => {
+                let text = Style::default().fg(Color::White).bg(Color::Black);
+                let badge = Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::White)
+                    .add_modifier(Modifier::BOLD);
+                Self {
+                    before: text,
+                    current: text.add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                    after: text,
+                    paused_badge: badge,
+                    scrolled_badge: badge,
+                    status: badge,
+                    // The badge alone marks a paused screen; dimming would cut contrast
+                    paused: Style::default(),
+                    ascii: false,
+                    base: text,
+                    easing: Default::default(),
+                    preview: text.add_modifier(Modifier::BOLD),
+                    preview_next: false,
+                    hide_past: false,
+                    // Colored text on black would lower the contrast
+                    parts: Vec::new(),
+                }
+            }
+        }
+    }
+
+    /// Adapts the styles to what the terminal can render.
+    pub fn degrade(mut self, caps: Capabilities) -> Self {
+        for style in [
+            &mut self.before,
+            &mut self.current,
+            &mut self.after,
+            &mut self.paused_badge,
+            &mut self.scrolled_badge,
+            &mut self.status,
+            &mut self.paused,
+            &mut self.base,
+            &mut self.preview,
+        ] {
+            *style = degrade_style(*style, caps);
+        }
+        self.parts = self.parts.into_iter().filter_map(|c| degrade_color(c, caps.colors)).collect();
+        self.ascii = !caps.unicode;
+        self
+    }
+
+    /// Replaces the part palette with `[colors] parts` from the config file;
+    /// names that aren't colors are skipped with a warning.
+    pub fn with_parts(mut self, parts: Option<&[String]>) -> Self {
+        if let Some(parts) = parts {
+            self.parts = parts
+                .iter()
+                .filter_map(|name| {
+                    name.parse::<Color>()
+                        .map_err(|_| tracing::warn!(color = %name, "Unknown color in [colors] parts, skipping"))
+                        .ok()
+                })
+                .collect();
+        }
+        self
+    }
+
+    /// The styles with every line dimmed like a paused screen, for fades.
+    pub fn dimmed(&self) -> Self {
+        let mut styles = self.clone();
+        for style in [&mut styles.before, &mut styles.current, &mut styles.after, &mut styles.preview] {
+            *style = style.patch(self.paused);
+        }
+        styles
+    }
+
+    /// The styles with the whole screen inverted, for cue flashes.
+    pub fn flashed(&self) -> Self {
+        let mut styles = self.clone();
+        styles.base = self.base.add_modifier(Modifier::REVERSED);
+        styles
+    }
+
+    /// `style` with the foreground of vocal part `part` (see [`PartIndex`]).
+    pub fn for_part(&self, style: Style, part: Option<usize>) -> Style {
+        match part {
+            Some(part) if !self.parts.is_empty() => style.fg(self.parts[part % self.parts.len()]),
+            _ => style,
+        }
+    }
+}
+
+/// Position of each line's vocal part among the parts of a song.
+///
+/// Songs with a single part (or none marked) get no part colors.
+#[derive(Debug, Default)]
+pub struct PartIndex {
+    parts: Vec<Option<usize>>,
+}
+
+impl PartIndex {
+    /// Numbers the agents of `lines` in order of first appearance.
+    pub fn new(lines: &[crate::lyrics::LyricLine]) -> Self {
+        let mut agents: Vec<&str> = Vec::new();
+        let parts: Vec<Option<usize>> = lines
+            .iter()
+            .map(|line| {
+                let agent = line.agent.as_deref()?;
+                Some(agents.iter().position(|a| *a == agent).unwrap_or_else(|| {
+                    agents.push(agent);
+                    agents.len() - 1
+                }))
+            })
+            .collect();
+        if agents.len() < 2 {
+            return Self::default();
+        }
+        Self { parts }
+    }
+
+    /// Part of line `i`, if the song has several.
+    pub fn get(&self, i: usize) -> Option<usize> {
+        self.parts.get(i).copied().flatten()
+    }
+}
+
+/// Number of colors the terminal supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    /// Monochrome (`TERM=dumb`, `*-mono`)
+    None,
+    /// The 16 ANSI colors
+    Basic,
+    /// The xterm 256-color palette
+    Ansi256,
+    /// 24-bit RGB
+    TrueColor,
+}
+
+/// What the terminal can render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    pub colors: ColorDepth,
+    pub italic: bool,
+    pub dim: bool,
+    /// Symbols beyond ASCII (badges, box drawing)
+    pub unicode: bool,
+}
+
+impl Capabilities {
+    /// Detects the capabilities from `TERM`, `COLORTERM` and the locale.
+    pub fn detect() -> Self {
+        let var = |name| std::env::var(name).ok();
+        let locale = var("LC_ALL").or_else(|| var("LC_CTYPE")).or_else(|| var("LANG"));
+        Self::from_env(var("TERM").as_deref(), var("COLORTERM").as_deref(), locale.as_deref())
+    }
+
+    fn from_env(term: Option<&str>, colorterm: Option<&str>, locale: Option<&str>) -> Self {
+        let term = term.unwrap_or("").to_ascii_lowercase();
+        let mono = term == "dumb" || term.ends_with("-mono") || term.ends_with("-m");
+        // The Linux console and old screen/vt terminals show italics as
+        // colors or garbage, and lack most symbols
+        let console = term == "linux" || term.starts_with("vt") || term == "screen";
+
+        let colors = if mono {
+            ColorDepth::None
+        } else if colorterm.is_some_and(|c| c.eq_ignore_ascii_case("truecolor") || c.eq_ignore_ascii_case("24bit")) {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Basic
+        };
+        let utf8 = locale.is_some_and(|l| {
+            let l = l.to_ascii_lowercase();
+            l.contains("utf-8") || l.contains("utf8")
+        });
+
+        Self {
+            colors,
+            italic: !mono && !console,
+            dim: !mono && !term.starts_with("vt"),
+            unicode: utf8 && !console && term != "dumb",
+        }
+    }
+
+    /// Applies `--color`: no colors when they are disabled, at least the
+    /// basic ones with `always`.
+    pub fn with_color(mut self, choice: ColorChoice) -> Self {
+        if choice == ColorChoice::Always {
+            self.colors = self.colors.max(ColorDepth::Basic);
+        } else if !choice.enabled(true) {
+            self.colors = ColorDepth::None;
+        }
+        self
+    }
+
+    /// Applies the `--no-italics` / `--ascii` overrides.
+    pub fn with_overrides(mut self, no_italics: bool, ascii: bool) -> Self {
+        self.italic &= !no_italics;
+        self.unicode &= !ascii;
+        self
+    }
+}
+
+/// Replaces what `caps` can't render in `style`.
+fn degrade_style(mut style: Style, caps: Capabilities) -> Style {
+    if !caps.italic && style.add_modifier.contains(Modifier::ITALIC) {
+        style.add_modifier.remove(Modifier::ITALIC);
+    }
+    if !caps.dim && style.add_modifier.contains(Modifier::DIM) {
+        style.add_modifier.remove(Modifier::DIM);
+        if style.fg.is_none() && caps.colors > ColorDepth::None {
+            style.fg = Some(Color::DarkGray);
+        }
+    }
+    style.fg = style.fg.and_then(|c| degrade_color(c, caps.colors));
+    style.bg = style.bg.and_then(|c| degrade_color(c, caps.colors));
+    style
+}
+
+/// Maps `color` to the closest one available at `depth`.
+fn degrade_color(color: Color, depth: ColorDepth) -> Option<Color> {
+    match (depth, color) {
+        (ColorDepth::None, _) => None,
+        (ColorDepth::TrueColor, c) => Some(c),
+        (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => {
+            let level = |v: u8| (u16::from(v) * 5 / 255) as u8;
+            Some(Color::Indexed(16 + 36 * level(r) + 6 * level(g) + level(b)))
+        }
+        (ColorDepth::Basic, Color::Rgb(r, g, b)) => Some(basic_color(r, g, b)),
+        (ColorDepth::Basic, Color::Indexed(i)) if i >= 16 => {
+            let (r, g, b) = indexed_rgb(i);
+            Some(basic_color(r, g, b))
+        }
+        (_, c) => Some(c),
+    }
+}
+
+/// RGB value of an xterm palette entry from the color cube or gray ramp.
+fn indexed_rgb(i: u8) -> (u8, u8, u8) {
+    if i >= 232 {
+        let v = 8 + (i - 232) * 10;
+        return (v, v, v);
+    }
+    let i = i - 16;
+    let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+    (level(i / 36), level(i / 6 % 6), level(i % 6))
+}
+
+/// Closest of the 16 ANSI colors to an RGB value.
+fn basic_color(r: u8, g: u8, b: u8) -> Color {
+    let bright = r.max(g).max(b) > 191;
+    match (r > 95, g > 95, b > 95) {
+        (false, false, false) if r.max(g).max(b) > 63 => Color::DarkGray,
+        (false, false, false) => Color::Black,
+        (true, false, false) if bright => Color::LightRed,
+        (true, false, false) => Color::Red,
+        (false, true, false) if bright => Color::LightGreen,
+        (false, true, false) => Color::Green,
+        (true, true, false) if bright => Color::LightYellow,
+        (true, true, false) => Color::Yellow,
+        (false, false, true) if bright => Color::LightBlue,
+        (false, false, true) => Color::Blue,
+        (true, false, true) if bright => Color::LightMagenta,
+        (true, false, true) => Color::Magenta,
+        (false, true, true) if bright => Color::LightCyan,
+        (false, true, true) => Color::Cyan,
+        (true, true, true) if bright => Color::White,
+        (true, true, true) => Color::Gray,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capability_detection_and_degradation() {
+        let kitty = Capabilities::from_env(Some("xterm-kitty"), Some("truecolor"), Some("en_US.UTF-8"));
+        assert_eq!(kitty.colors, ColorDepth::TrueColor);
+        assert!(kitty.italic && kitty.dim && kitty.unicode);
+
+        let console = Capabilities::from_env(Some("linux"), None, Some("en_US.UTF-8"));
+        assert_eq!(console.colors, ColorDepth::Basic);
+        assert!(!console.italic && !console.unicode);
+
+        let styles = LyricStyles::default().degrade(console);
+        assert!(!styles.before.add_modifier.contains(Modifier::ITALIC));
+        assert!(styles.ascii);
+
+        let vt = Capabilities::from_env(Some("vt100"), None, None);
+        let styles = LyricStyles::default().degrade(vt);
+        assert_eq!(styles.paused.fg, Some(Color::DarkGray));
+        assert!(!styles.paused.add_modifier.contains(Modifier::DIM));
+
+        assert_eq!(degrade_color(Color::Rgb(255, 255, 255), ColorDepth::Ansi256), Some(Color::Indexed(231)));
+        assert_eq!(degrade_color(Color::Rgb(200, 30, 30), ColorDepth::Basic), Some(Color::LightRed));
+        assert_eq!(degrade_color(Color::Green, ColorDepth::None), None);
+        assert!(!kitty.with_overrides(true, true).italic);
+        assert_eq!(kitty.with_color(ColorChoice::Never).colors, ColorDepth::None);
+        let dumb = Capabilities::from_env(Some("dumb"), None, None);
+        assert_eq!(dumb.with_color(ColorChoice::Always).colors, ColorDepth::Basic);
+    }
+
+    #[test]
+    fn test_duet_part_colors() {
+        use crate::lyrics::LyricLine;
+        let line = |agent: Option<&str>| LyricLine { agent: agent.map(String::from), ..Default::default() };
+
+        let parts = PartIndex::new(&[line(Some("v1")), line(Some("v2")), line(None), line(Some("v1"))]);
+        assert_eq!((parts.get(0), parts.get(1), parts.get(2), parts.get(3)), (Some(0), Some(1), None, Some(0)));
+        // A single singer isn't a duet
+        assert_eq!(PartIndex::new(&[line(Some("v1")), line(Some("v1"))]).get(0), None);
+
+        let styles = LyricStyles::default().with_parts(Some(&["red".to_string(), "nope".to_string()]));
+        assert_eq!(styles.parts, [Color::Red]);
+        assert_eq!(styles.for_part(styles.after, Some(1)).fg, Some(Color::Red));
+        assert_eq!(styles.for_part(styles.after, None), styles.after);
+        assert!(LyricStyles::default().with_parts(Some(&[])).parts.is_empty());
+    }
+
+    #[test]
+    fn test_high_contrast_theme() {
+        let styles = LyricStyles::for_theme(Theme::HighContrast);
+        for style in [styles.before, styles.current, styles.after] {
+            assert_eq!((style.fg, style.bg), (Some(Color::White), Some(Color::Black)));
+            assert!(!style.add_modifier.intersects(Modifier::DIM | Modifier::ITALIC));
+        }
+        assert!(styles.current.add_modifier.contains(Modifier::UNDERLINED));
+        assert!(!styles.paused.add_modifier.contains(Modifier::DIM));
+    }
 }
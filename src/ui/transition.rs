@@ -0,0 +1,91 @@
+//! Fade between tracks (`--fade MS`).
+//!
+//! Terminals can't blend colors, so the fade is done in steps over the
+//! configured duration: the old lyrics are dimmed, the screen is cleared,
+//! then the new lyrics appear dimmed before taking their normal styles.
+//! Without anything on screen (first track, no lyrics) only the fade-in runs.
+
+use crate::state::Update;
+use std::time::{Duration, Instant};
+
+/// What to draw at a point of the fade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// The previous track's lyrics, dimmed
+    FadeOut,
+    /// Nothing
+    Blank,
+    /// The new track's lyrics, dimmed
+    FadeIn,
+}
+
+/// A running fade.
+pub struct Fade {
+    started: Instant,
+    duration: Duration,
+    /// Last frame of the previous track, if it showed lyrics
+    pub previous: Option<Update>,
+}
+
+impl Fade {
+    /// Starts a fade away from `previous` at `now`.
+    pub fn new(now: Instant, duration: Duration, previous: Option<Update>) -> Self {
+        let previous = previous.filter(|u| !u.lines.is_empty());
+        Self { started: now, duration, previous }
+    }
+
+    /// Step shown at `now`, or `None` once the fade is over.
+    pub fn step(&self, now: Instant) -> Option<Step> {
+        let third = self.duration / 3;
+        let elapsed = self.skipped() + now.saturating_duration_since(self.started);
+        match elapsed {
+            e if e < third => Some(Step::FadeOut),
+            e if e < third * 2 => Some(Step::Blank),
+            e if e < self.duration => Some(Step::FadeIn),
+            _ => None,
+        }
+    }
+
+    /// Time from `now` until the next step.
+    pub fn next_step_in(&self, now: Instant) -> Duration {
+        let third = self.duration / 3;
+        let elapsed = self.skipped() + now.saturating_duration_since(self.started);
+        [third, third * 2, self.duration]
+            .into_iter()
+            .find(|&boundary| boundary > elapsed)
+            .map_or(Duration::ZERO, |boundary| boundary - elapsed)
+    }
+
+    /// Part of the fade skipped when there is nothing to fade out.
+    fn skipped(&self) -> Duration {
+        if self.previous.is_some() { Duration::ZERO } else { self.duration / 3 * 2 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lyrics::LyricLine;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_fade_steps() {
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+        let lyrics = Update { lines: Arc::new(vec![LyricLine::default()]), ..Default::default() };
+
+        let fade = Fade::new(start, ms(300), Some(lyrics));
+        assert_eq!(fade.step(start), Some(Step::FadeOut));
+        assert_eq!(fade.step(start + ms(150)), Some(Step::Blank));
+        assert_eq!(fade.step(start + ms(250)), Some(Step::FadeIn));
+        assert_eq!(fade.step(start + ms(300)), None);
+        assert_eq!(fade.next_step_in(start + ms(50)), ms(50));
+        assert_eq!(fade.next_step_in(start + ms(400)), Duration::ZERO);
+
+        // Nothing to fade out: straight to the fade-in
+        let fade = Fade::new(start, ms(300), Some(Update::default()));
+        assert!(fade.previous.is_none());
+        assert_eq!(fade.step(start), Some(Step::FadeIn));
+        assert_eq!(fade.next_step_in(start), ms(100));
+    }
+}
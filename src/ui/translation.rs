@@ -0,0 +1,138 @@
+//! Original / translated lyrics display mode.
+//!
+//! The TUI `t` key cycles between the original lyrics, their translation and
+//! both. The choice is remembered per lyrics language (guessed from the
+//! script, see [`crate::text_utils::detect_language`]) in
+//! `translations.toml` next to the configuration file:
+//!
+//! ```toml
+//! ja = "both"
+//! ko = "translation"
+//! default = "original"        # languages that can't be detected
+//! ```
+
+use crate::lyrics::LyricLine;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Which text of each lyric line is displayed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranslationMode {
+    #[default]
+    Original,
+    Translation,
+    /// Original followed by the translation
+    Both,
+}
+
+impl TranslationMode {
+    /// The mode selected by the next `t` press.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Original => Self::Translation,
+            Self::Translation => Self::Both,
+            Self::Both => Self::Original,
+        }
+    }
+
+    /// Text displayed for `line`; lines without a translation always show the original.
+    pub fn display_text(self, line: &LyricLine) -> String {
+        match (self, &line.translation) {
+            (Self::Translation, Some(translation)) => translation.clone(),
+            (Self::Both, Some(translation)) => format!("{}\n{}", line.text, translation),
+            _ => line.text.clone(),
+        }
+    }
+
+    /// Whether per-word karaoke applies: the translation has no word timing.
+    pub fn shows_original_words(self, line: &LyricLine) -> bool {
+        self != Self::Translation || line.translation.is_none()
+    }
+}
+
+/// Key under which the mode for undetectable languages is stored.
+const DEFAULT_LANGUAGE: &str = "default";
+
+/// Per-language translation modes, persisted across runs.
+#[derive(Debug, Default)]
+pub struct TranslationPrefs {
+    path: Option<PathBuf>,
+    modes: BTreeMap<String, TranslationMode>,
+}
+
+impl TranslationPrefs {
+    /// Loads the remembered modes. A missing or unreadable file yields no preferences.
+    pub fn load() -> Self {
+        let path = crate::config::default_config_path()
+            .and_then(|config| Some(config.parent()?.join("translations.toml")));
+        let modes = path
+            .as_ref()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .and_then(|text| match toml::from_str(&text) {
+                Ok(modes) => Some(modes),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Invalid translations.toml, ignoring");
+                    None
+                }
+            })
+            .unwrap_or_default();
+        Self { path, modes }
+    }
+
+    /// The remembered mode for `language` (`None` = undetected).
+    pub fn get(&self, language: Option<&str>) -> TranslationMode {
+        self.modes
+            .get(language.unwrap_or(DEFAULT_LANGUAGE))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Remembers `mode` for `language` and writes the file.
+    pub fn set(&mut self, language: Option<&str>, mode: TranslationMode) {
+        self.modes.insert(language.unwrap_or(DEFAULT_LANGUAGE).to_string(), mode);
+
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = toml::to_string(&self.modes)
+            .map_err(std::io::Error::other)
+            .and_then(|text| {
+                if let Some(dir) = path.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                std::fs::write(path, text)
+            });
+        if let Err(e) = result {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to save translation preference");
+        }
+    }
+}
+
+/// Guesses the language of a set of lyrics from their original text.
+pub fn lyrics_language(lines: &[LyricLine]) -> Option<&'static str> {
+    let sample: String = lines.iter().take(20).map(|l| l.text.as_str()).collect();
+    crate::text_utils::detect_language(&sample)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translation_display_text() {
+        let line = LyricLine {
+            text: "夜に駆ける".into(),
+            translation: Some("Racing into the night".into()),
+            ..Default::default()
+        };
+        assert_eq!(TranslationMode::Original.display_text(&line), "夜に駆ける");
+        assert_eq!(TranslationMode::Translation.display_text(&line), "Racing into the night");
+        assert_eq!(TranslationMode::Both.display_text(&line), "夜に駆ける\nRacing into the night");
+        assert_eq!(TranslationMode::Both.next(), TranslationMode::Original);
+
+        let untranslated = LyricLine { text: "Hello".into(), ..Default::default() };
+        assert_eq!(TranslationMode::Translation.display_text(&untranslated), "Hello");
+    }
+}
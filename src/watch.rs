@@ -0,0 +1,112 @@
+//! Drop-folder watcher for manual lyrics fixes.
+//!
+//! With `--drop-dir DIR`, the directory is watched (inotify on Linux) for
+//! `.lrc` and `.yrc` files. When `current.lrc` or `Artist - Title.lrc` matching
+//! the playing track (or the `.yrc` equivalent) is written or moved into the
+//! folder, it is loaded
+//! immediately and imported into the cache, so a bad match can be corrected
+//! mid-song by saving a fixed file.
+
+use crate::event::Event;
+use notify::event::{AccessKind, AccessMode, ModifyKind, RenameMode};
+use notify::{EventKind, RecursiveMode, Watcher};
+use crate::lyrics::providers::local::EXTENSIONS;
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+
+/// File name that always applies to the currently playing track.
+pub const CURRENT_FILE_NAME: &str = "current.lrc";
+
+/// [`CURRENT_FILE_NAME`] with each of the local lyrics [`EXTENSIONS`].
+pub fn current_file_names() -> Vec<String> {
+    let stem = CURRENT_FILE_NAME.strip_suffix(".lrc").unwrap_or(CURRENT_FILE_NAME);
+    EXTENSIONS.iter().map(|ext| format!("{stem}.{ext}")).collect()
+}
+
+/// Starts watching `dir` and forwards finished lyrics file writes as
+/// [`Event::LyricsFile`].
+///
+/// The directory is created if missing. Failures are logged and leave the
+/// rest of the application running without the watcher.
+pub fn spawn_drop_folder_watcher(dir: PathBuf, event_tx: mpsc::Sender<Event>) {
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        tracing::warn!(dir = %dir.display(), error = %e, "Failed to create drop folder");
+        return;
+    }
+
+    let tx = event_tx.clone();
+    let watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+        Ok(event) if is_finished_write(&event.kind) => {
+            for path in event.paths.into_iter().filter(|p| is_lyrics_file(p)) {
+                let _ = tx.try_send(Event::LyricsFile(path));
+            }
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!(error = %e, "Drop folder watcher error"),
+    });
+
+    let mut watcher = match watcher {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to initialize drop folder watcher");
+            return;
+        }
+    };
+
+    if let Err(e) = watcher.watch(&dir, RecursiveMode::NonRecursive) {
+        tracing::warn!(dir = %dir.display(), error = %e, "Failed to watch drop folder");
+        return;
+    }
+
+    tracing::debug!(dir = %dir.display(), "Watching drop folder");
+
+    // Keep the watcher alive for as long as the event loop is listening
+    tokio::spawn(async move {
+        let _watcher = watcher;
+        event_tx.closed().await;
+    });
+}
+
+/// Returns `true` for events signalling a file is complete: closed after
+/// writing, or renamed/moved into place (editors saving atomically).
+///
+/// Creating a file isn't one: a copy or save also ends with a close, and
+/// reacting to both would load the file twice.
+fn is_finished_write(kind: &EventKind) -> bool {
+    matches!(
+        kind,
+        EventKind::Access(AccessKind::Close(AccessMode::Write))
+            | EventKind::Modify(ModifyKind::Name(RenameMode::To | RenameMode::Both))
+    )
+}
+
+fn is_lyrics_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| EXTENSIONS.iter().any(|known| ext.eq_ignore_ascii_case(known)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, DataChange};
+
+    #[test]
+    fn test_is_finished_write() {
+        assert!(is_finished_write(&EventKind::Access(AccessKind::Close(AccessMode::Write))));
+        assert!(is_finished_write(&EventKind::Modify(ModifyKind::Name(RenameMode::To))));
+        assert!(is_finished_write(&EventKind::Modify(ModifyKind::Name(RenameMode::Both))));
+
+        assert!(!is_finished_write(&EventKind::Create(CreateKind::File)));
+        assert!(!is_finished_write(&EventKind::Modify(ModifyKind::Data(DataChange::Content))));
+        assert!(!is_finished_write(&EventKind::Modify(ModifyKind::Name(RenameMode::From))));
+        assert!(!is_finished_write(&EventKind::Access(AccessKind::Close(AccessMode::Read))));
+    }
+
+    #[test]
+    fn test_is_lyrics_file() {
+        assert!(is_lyrics_file(Path::new("/drop/current.lrc")));
+        assert!(is_lyrics_file(Path::new("/drop/Muse - Uprising.YRC")));
+        assert!(!is_lyrics_file(Path::new("/drop/.current.lrc.swp")));
+        assert!(!is_lyrics_file(Path::new("/drop/notes.txt")));
+    }
+}